@@ -1,10 +1,57 @@
-use clap::{Parser, Subcommand};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::cli::catchup::CatchupAction;
+use crate::models::StatsRange;
+use crate::tui::theme::THEME_NAMES;
 
 #[derive(Parser, Debug)]
 #[command(name = "sujood", version, author, about = "A beautiful terminal companion for Islamic practice tracking")]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+    /// Print a one-line glance (next prayer, what's left today) instead of opening the dashboard
+    #[arg(long)]
+    pub summary: bool,
+    /// Color theme for this session, overriding `ui.theme` in config
+    #[arg(long, global = true, value_parser = PossibleValuesParser::new(THEME_NAMES))]
+    pub theme: Option<String>,
+    /// When to color output: auto (only on a terminal, and unless NO_COLOR is set), always or never
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+    /// Use a saved location profile for this run only (see `sujood profile list`)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+    /// Database file to use instead of the default (also set by SUJOOD_DB; this flag wins)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub db: Option<PathBuf>,
+    /// Log debug detail, such as prayer time calculation inputs, to stderr (RUST_LOG wins if set)
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && std::io::stdout().is_terminal()
+            }
+        }
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -14,9 +61,29 @@ pub enum Commands {
         /// Reset existing configuration
         #[arg(long)]
         reset: bool,
+        /// Set only the high-latitude rule and re-cache times, skipping the wizard
+        /// (MiddleOfTheNight | SeventhOfTheNight | TwilightAngle | Recommended)
+        #[arg(long, value_name = "RULE")]
+        high_latitude_rule: Option<String>,
+        /// Prefill city and coordinates from an IP geolocation lookup (needs the
+        /// `auto-location` build feature); the wizard still asks you to confirm
+        #[arg(long, conflicts_with = "high_latitude_rule")]
+        auto_location: bool,
+    },
+    /// Show prayer times (today's, with a countdown to the next prayer)
+    Times {
+        #[command(subcommand)]
+        action: Option<TimesCommands>,
+        /// Also show Asr by the other madhab (Hanafi and Shafi side by side)
+        #[arg(long)]
+        both_asr: bool,
+        /// Show another day's times instead (YYYY-MM-DD)
+        #[arg(long, conflicts_with = "tomorrow")]
+        date: Option<String>,
+        /// Show tomorrow's times
+        #[arg(long)]
+        tomorrow: bool,
     },
-    /// Show today's prayer times and countdown to next prayer
-    Times,
     /// Mark a prayer as done or missed
     Mark {
         /// Prayer name (fajr, zuhr, asr, maghrib, isha)
@@ -24,6 +91,92 @@ pub enum Commands {
         /// Mark as missed and add to qada queue
         #[arg(long)]
         missed: bool,
+        /// Day to mark (YYYY-MM-DD) — defaults to today
+        #[arg(long)]
+        date: Option<String>,
+        /// Attach a short note, e.g. "prayed at the airport"
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// Show which of today's prayers are still pending
+    Remaining,
+    /// Print the next prayer and countdown on one line, for status bars
+    Next {
+        /// Output template: {prayer}, {countdown}, {time} (HH:MM) and {minutes}
+        #[arg(long, default_value = "{prayer} {countdown}", conflicts_with = "json")]
+        format: String,
+        /// Print a JSON object instead
+        #[arg(long)]
+        json: bool,
+    },
+    /// Save locations as named profiles and switch between them
+    Profile {
+        #[command(subcommand)]
+        action: ProfileCommands,
+    },
+    /// Stay running and send a desktop notification before each prayer
+    /// (see `lead_minutes` under [notifications]); Ctrl-C stops it
+    Daemon,
+    /// Answer read-only JSON requests (/times, /next, /stats, /today) for
+    /// widgets and scripts; needs the `serve` build feature
+    Serve {
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// Address to listen on. Anything other than localhost shares your
+        /// data with the network.
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+    },
+    /// Grid of prayer statuses per day over a date range
+    List {
+        /// First day (YYYY-MM-DD) — defaults to six days before --to
+        #[arg(long)]
+        from: Option<String>,
+        /// Last day (YYYY-MM-DD) — defaults to today
+        #[arg(long)]
+        to: Option<String>,
+    },
+    /// Show what was recorded on a past day: prayers, adhkar and Quran pages
+    History {
+        /// Day to inspect (YYYY-MM-DD)
+        date: String,
+    },
+    /// Print a plain-text summary of today: times, prayers, adhkar, Quran, qada
+    Today {
+        /// Strip ANSI colour codes — same as --color never
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Toggle qiyam (tahajjud) for the current night
+    Qiyam,
+    /// Record the Eid prayer on Eid day, or list past Eids
+    Eid {
+        /// Record that the Eid prayer was not attended
+        #[arg(long)]
+        missed: bool,
+        /// List every recorded Eid instead
+        #[arg(long)]
+        history: bool,
+    },
+    /// Record Jumu'ah attendance on a Friday, or show the Jummah streak
+    Jummah {
+        /// Record that Jummah was not attended
+        #[arg(long)]
+        missed: bool,
+        /// A past Friday to record (YYYY-MM-DD) — defaults to today
+        #[arg(long)]
+        date: Option<String>,
+    },
+    /// Toggle a voluntary fast for today (Mondays, Thursdays, white days…)
+    Fast {
+        /// A past day to record (YYYY-MM-DD) — defaults to today
+        #[arg(long)]
+        date: Option<String>,
+    },
+    /// Catch up after time away: record the untracked days since you last marked a prayer
+    Catchup {
+        /// What to record, without asking
+        action: Option<CatchupAction>,
     },
     /// Qada queue management
     Qada {
@@ -36,31 +189,167 @@ pub enum Commands {
         action: DhikrCommands,
     },
     /// Log Quran pages read today
+    #[command(args_conflicts_with_subcommands = true)]
     Quran {
-        /// Number of pages read
-        pages: f64,
+        #[command(subcommand)]
+        action: Option<QuranCommands>,
+        /// Number of pages read (defaults to your last entry)
+        pages: Option<f64>,
     },
     /// Show statistics
     Stats {
-        /// Show ASCII heatmap for the last 7 days
-        #[arg(long)]
+        /// Show ASCII heatmap for the last 7 days (same as --range week)
+        #[arg(long, conflicts_with = "range")]
         week: bool,
+        /// Heatmap range: week or month — remembered for next time
+        #[arg(long)]
+        range: Option<StatsRange>,
+        /// Count only one prayer (fajr … isha), or `all` — remembered for next time
+        #[arg(long, value_name = "PRAYER")]
+        prayer: Option<String>,
+        /// Forget the remembered range and prayer filter
+        #[arg(long)]
+        reset_view: bool,
+        /// Year in review: totals and a month-by-month sparkline [default: this year]
+        #[arg(long, value_name = "YEAR", conflicts_with_all = ["week", "range", "prayer", "reset_view"])]
+        year: Option<Option<i32>>,
+    },
+    /// Export a summary of recent days to stdout or a file
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Text)]
+        format: ExportFormat,
+        /// Number of days to include, ending today (starting today for ics) [default: 7, or 30 for csv/ics]
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        days: Option<u32>,
+        /// Write to this file instead of stdout
+        #[arg(long, value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
+    /// Restore prayers, adhkar, Quran and qada from a `export --format json` file
+    Import {
+        /// JSON file written by `sujood export --format json`
+        file: PathBuf,
+        /// Keep records already in the database (default)
+        #[arg(long, conflicts_with = "overwrite")]
+        merge: bool,
+        /// Replace records already in the database with the file's
+        #[arg(long)]
+        overwrite: bool,
+    },
+    /// Delete cached prayer times before a day; history only when asked
+    Prune {
+        /// First day to keep (YYYY-MM-DD); everything dated earlier goes
+        #[arg(long)]
+        before: String,
+        /// Also delete prayers, adhkar, Quran and other logs before that day — can't be undone
+        #[arg(long)]
+        include_history: bool,
+        /// Compact the database file afterwards to give the space back
+        #[arg(long)]
+        vacuum: bool,
     },
-    /// Export a weekly text summary to stdout
-    Export,
+    /// Print the man page (roff) for sujood, or for one of its commands
+    Man {
+        /// Command to document, e.g. `qada add`; the top-level page if left out
+        command: Vec<String>,
+        /// Write every page into this directory instead (sujood.1, sujood-times.1, …)
+        #[arg(long, value_name = "DIR", conflicts_with = "command")]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    /// Human-readable summary
+    Text,
+    /// Structured data for other tools
+    Json,
+    /// One `date,prayer,status` row per prayer, for spreadsheets
+    Csv,
+    /// Upcoming prayer times as calendar events
+    Ics,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum QadaCommands {
     /// Show the qada queue
     List,
-    /// Mark the oldest qada prayer as completed
-    Complete,
+    /// Mark a qada prayer as completed (the oldest unless an id is given)
+    Complete {
+        /// Entry id from `qada list`
+        id: Option<i64>,
+    },
     /// Manually add a prayer to the qada queue
     Add {
         /// Prayer name
         prayer: String,
     },
+    /// Delete a qada entry added by mistake (see ids in `qada list`)
+    Remove {
+        /// Entry id
+        id: i64,
+    },
+    /// Queue every prayer in a range of days for qada, skipping ones marked done
+    Backfill {
+        /// First day (YYYY-MM-DD)
+        #[arg(long)]
+        from: String,
+        /// Last day (YYYY-MM-DD) — defaults to today
+        #[arg(long)]
+        to: Option<String>,
+        /// Only these prayers, comma-separated (e.g. fajr,isha) — defaults to all five
+        #[arg(long, value_delimiter = ',')]
+        prayers: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProfileCommands {
+    /// List saved profiles, marking the active one
+    List,
+    /// Save the current location settings as a new profile and make it active
+    Add {
+        /// Profile name (letters, digits, - and _)
+        name: String,
+    },
+    /// Make another profile active and re-cache prayer times for it
+    Switch {
+        /// Profile name
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TimesCommands {
+    /// Load a published timetable (CSV: date,fajr,sunrise,zuhr,asr,maghrib,isha)
+    /// in place of calculated times — pair with `source = "file"` under [salah]
+    Import {
+        /// CSV file, one day per line with times as HH:MM
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum QuranCommands {
+    /// Show progress through the current khatm and a projected finish
+    Status,
+    /// Overwrite today's page count instead of adding to it
+    Set {
+        /// Today's total pages
+        pages: f64,
+    },
+    /// Revert the last Quran entry
+    Undo,
+    /// Log reading by surah and ayah range, e.g. `quran read baqarah 1 50`
+    Read {
+        /// Surah name or number
+        surah: String,
+        /// First ayah — defaults to 1
+        start: Option<u16>,
+        /// Last ayah — defaults to the end of the surah
+        end: Option<u16>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -73,9 +362,18 @@ pub enum DhikrCommands {
     Mark {
         /// Dhikr name
         name: String,
-        /// Add this count to a counter dhikr
-        #[arg(long)]
+        /// Add this count to a counter dhikr (negative to take some off)
+        #[arg(long, allow_negative_numbers = true)]
         count: Option<i32>,
+        /// Set a counter dhikr's count for today outright (0 resets it)
+        #[arg(long, conflicts_with = "count")]
+        set: Option<i32>,
+        /// Mark done on every day from this date (YYYY-MM-DD) through --to
+        #[arg(long)]
+        from: Option<String>,
+        /// Last day of the --from range — defaults to today
+        #[arg(long)]
+        to: Option<String>,
     },
     /// Add a custom dhikr
     Add {
@@ -90,7 +388,67 @@ pub enum DhikrCommands {
         /// Frequency: daily or weekly
         #[arg(long, default_value = "daily")]
         freq: String,
+        /// Free-text category used to group the list (e.g. travel, after-salah)
+        #[arg(long)]
+        category: Option<String>,
+    },
+    /// Change a dhikr's target, type or frequency, or rename it, keeping its history
+    Edit {
+        /// Dhikr name
+        name: String,
+        /// New target count (counter type)
+        #[arg(long)]
+        target: Option<i32>,
+        /// New type: checkbox or counter
+        #[arg(long)]
+        r#type: Option<String>,
+        /// New frequency: daily or weekly
+        #[arg(long)]
+        freq: Option<String>,
+        /// New name
+        #[arg(long, value_name = "NEW")]
+        rename: Option<String>,
+    },
+    /// Remove a custom dhikr and its history, or hide one with --deactivate
+    Remove {
+        /// Dhikr name
+        name: String,
+        /// Hide it from lists but keep its history (works for built-ins too)
+        #[arg(long)]
+        deactivate: bool,
     },
     /// List all active dhikr definitions
-    List,
+    List {
+        /// Only show dhikr in this category (a tag, or builtin/custom)
+        #[arg(long)]
+        category: Option<String>,
+        /// Only show dhikr whose name contains this text (case-insensitive)
+        #[arg(long)]
+        filter: Option<String>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("sujood").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn top_level_options_conflict_with_subcommands() {
+        assert!(parse(&["--summary", "stats"]).is_err());
+        assert!(parse(&["quran", "5", "status"]).is_err());
+
+        // Global options go after the subcommand instead
+        let cli = parse(&["stats", "--profile", "travel", "--db", "/tmp/s.db"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("travel"));
+        assert!(matches!(cli.command, Some(Commands::Stats { .. })));
+
+        let cli = parse(&["quran", "5"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Quran { action: None, pages: Some(_) })));
+        let cli = parse(&["quran", "status"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Quran { action: Some(_), pages: None })));
+    }
 }
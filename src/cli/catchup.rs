@@ -0,0 +1,176 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::config::AppConfig;
+use crate::db::repository::{MetaRepo, PrayerRepo, QadaRepo};
+use crate::models::PrayerType;
+use crate::prayer_times::calculator;
+use crate::utils::date::{date_str, DATE_FMT};
+
+/// Untracked days in a row before the catch-up flow is offered.
+pub const MIN_GAP_DAYS: i64 = 2;
+
+/// app_meta key holding the last day a catch-up already dealt with, so a
+/// skipped gap isn't offered again.
+const CATCHUP_THROUGH_KEY: &str = "catchup_through";
+
+/// A stretch of days with no prayer marked, ending yesterday.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gap {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl Gap {
+    pub fn days(&self) -> i64 {
+        (self.to - self.from).num_days() + 1
+    }
+
+    pub fn prayers(&self) -> i64 {
+        self.days() * PrayerType::all().len() as i64
+    }
+}
+
+/// What to record for every prayer in a gap.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum CatchupAction {
+    /// They were prayed, just not tracked
+    Done,
+    /// Mark them missed and queue each for qada
+    Missed,
+    /// Leave the days untracked
+    Skip,
+}
+
+/// The gap since the last day with any prayer marked done or missed, if it
+/// is at least `MIN_GAP_DAYS` long. First-time users have no gap.
+pub fn detect(conn: &Connection, today: NaiveDate) -> Result<Option<Gap>> {
+    let last_tracked: Option<String> = conn
+        .query_row(
+            "SELECT MAX(date) FROM prayers
+             WHERE is_qada = 0 AND status IN ('done', 'missed') AND date < ?1",
+            [date_str(today)],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+    let handled = MetaRepo::get(conn, CATCHUP_THROUGH_KEY)?;
+
+    let Some(last) = last_tracked.into_iter().chain(handled).max() else {
+        return Ok(None);
+    };
+    let last = NaiveDate::parse_from_str(&last, DATE_FMT)?;
+    let gap = Gap {
+        from: last + chrono::Duration::days(1),
+        to: today - chrono::Duration::days(1),
+    };
+    Ok((gap.days() >= MIN_GAP_DAYS).then_some(gap))
+}
+
+/// Record `action` for every prayer in `gap` and remember it as handled.
+/// Returns how many qada entries were added.
+pub fn apply(
+    conn: &Connection,
+    config: &AppConfig,
+    gap: Gap,
+    action: CatchupAction,
+) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut qada_added = 0;
+    if action != CatchupAction::Skip {
+        for day in gap.from.iter_days().take_while(|d| *d <= gap.to) {
+            let date = date_str(day);
+            calculator::ensure_rows(&tx, config, day)?;
+            for pt in PrayerType::all() {
+                match action {
+                    CatchupAction::Done => PrayerRepo::mark_status(&tx, pt.as_str(), &date, "done", None)?,
+                    CatchupAction::Missed => {
+                        PrayerRepo::mark_status(&tx, pt.as_str(), &date, "missed", None)?;
+                        QadaRepo::add_entry(&tx, pt.as_str(), &date)?;
+                        qada_added += 1;
+                    }
+                    CatchupAction::Skip => {}
+                }
+            }
+        }
+    }
+    MetaRepo::set(&tx, CATCHUP_THROUGH_KEY, &date_str(gap.to))?;
+    tx.commit()?;
+    Ok(qada_added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::run_migrations;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, DATE_FMT).unwrap()
+    }
+
+    fn mark_fajr(conn: &Connection, day: &str) {
+        PrayerRepo::ensure_rows_for_date(conn, day).unwrap();
+        PrayerRepo::mark_status(conn, "fajr", day, "done", None).unwrap();
+    }
+
+    #[test]
+    fn gap_runs_from_the_last_tracked_day_to_yesterday() {
+        let conn = test_db();
+        let today = date("2026-10-16");
+        assert_eq!(detect(&conn, today).unwrap(), None);
+
+        mark_fajr(&conn, "2026-09-20");
+        // Pending rows don't count as tracked
+        PrayerRepo::ensure_rows_for_date(&conn, "2026-10-01").unwrap();
+        let gap = detect(&conn, today).unwrap().unwrap();
+        assert_eq!(gap, Gap { from: date("2026-09-21"), to: date("2026-10-15") });
+        assert_eq!((gap.days(), gap.prayers()), (25, 125));
+
+        // A single untracked day is no gap
+        mark_fajr(&conn, "2026-10-14");
+        assert_eq!(detect(&conn, today).unwrap(), None);
+    }
+
+    #[test]
+    fn missed_plan_queues_every_prayer_and_closes_the_gap() {
+        let conn = test_db();
+        let today = date("2026-10-16");
+        mark_fajr(&conn, "2026-09-30");
+        let gap = detect(&conn, today).unwrap().unwrap();
+        assert_eq!(gap.days(), 15);
+
+        let added = apply(&conn, &AppConfig::default(), gap, CatchupAction::Missed).unwrap();
+        assert_eq!(added as i64, gap.prayers());
+        assert_eq!(QadaRepo::count_pending(&conn).unwrap(), 75);
+        for day in ["2026-10-01", "2026-10-15"] {
+            let prayers = PrayerRepo::get_by_date(&conn, day).unwrap();
+            assert_eq!(prayers.len(), 5);
+            assert!(prayers.iter().all(|p| p.status.as_str() == "missed"));
+        }
+        assert_eq!(detect(&conn, today).unwrap(), None);
+    }
+
+    #[test]
+    fn skipped_gap_writes_nothing_and_is_not_offered_again() {
+        let conn = test_db();
+        let today = date("2026-10-16");
+        mark_fajr(&conn, "2026-09-30");
+        let gap = detect(&conn, today).unwrap().unwrap();
+
+        assert_eq!(apply(&conn, &AppConfig::default(), gap, CatchupAction::Skip).unwrap(), 0);
+        assert!(PrayerRepo::get_by_date(&conn, "2026-10-01").unwrap().is_empty());
+        assert_eq!(QadaRepo::count_pending(&conn).unwrap(), 0);
+        assert_eq!(detect(&conn, today).unwrap(), None);
+        // A later absence is a new gap, starting after the skipped one
+        let later = detect(&conn, date("2026-10-20")).unwrap().unwrap();
+        assert_eq!(later.from, date("2026-10-16"));
+    }
+}
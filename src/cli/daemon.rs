@@ -0,0 +1,197 @@
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use rusqlite::Connection;
+
+use crate::config::AppConfig;
+use crate::db::repository::PrayerRepo;
+use crate::models::{PrayerStatus, PrayerType};
+use crate::prayer_times::PrayerCalculator;
+use crate::utils::date::{date_str, jummah_start, now_local};
+use crate::utils::format::format_time;
+
+/// Longest single wait. Waking now and then catches a changed system clock
+/// or a laptop resuming from sleep.
+const MAX_WAIT_SECS: i64 = 60;
+
+/// A reminder this late (say, after a suspend) is dropped rather than shown.
+const STALE_AFTER_SECS: i64 = 5 * 60;
+
+/// One notification: `lead` minutes before `prayer` enters at `time` on `date`.
+#[derive(Debug, Clone, PartialEq)]
+struct Reminder {
+    at: NaiveDateTime,
+    date: NaiveDate,
+    prayer: PrayerType,
+    label: &'static str,
+    time: NaiveTime,
+    lead: u32,
+}
+
+impl Reminder {
+    fn message(&self) -> String {
+        if self.lead == 0 {
+            format!("{} has entered ({})", self.label, format_time(self.time))
+        } else {
+            format!("{} in {} min ({})", self.label, self.lead, format_time(self.time))
+        }
+    }
+}
+
+/// Run until Ctrl-C, sending a desktop notification `lead_minutes` before
+/// each prayer. Times are re-read after every reminder, so the day rollover
+/// and a re-run setup are picked up without a restart.
+pub fn run(conn: &Connection, config: &AppConfig) -> Result<()> {
+    let calc = PrayerCalculator::from_config(&config.salah)?;
+    let mut leads = config.notifications.lead_minutes.clone();
+    leads.sort_unstable_by(|a, b| b.cmp(a));
+    leads.dedup();
+    if leads.is_empty() {
+        return Err(anyhow!(
+            "No reminders configured. Set lead_minutes under [notifications], e.g. [10, 0]"
+        ));
+    }
+
+    let (stop_tx, stop_rx) = mpsc::channel();
+    ctrlc::set_handler(move || {
+        let _ = stop_tx.send(());
+    })
+    .context("Installing the Ctrl-C handler")?;
+
+    let leads_text: Vec<String> = leads.iter().map(|m| format!("{}m", m)).collect();
+    println!(
+        "  sujood daemon — reminders {} before each prayer. Ctrl-C to stop.",
+        leads_text.join(", ")
+    );
+
+    // Reminders due before the daemon started are never sent
+    let mut sent_through = now_local(config);
+    loop {
+        let next = next_reminder(conn, config, &calc, &leads, sent_through)?;
+        let now = now_local(config);
+        let wait_ms = (next.at - now).num_milliseconds();
+        if wait_ms > 0 {
+            log::debug!("next: {} at {}, {}s away", next.message(), next.at, wait_ms / 1000);
+            let wait = Duration::from_millis(wait_ms.min(MAX_WAIT_SECS * 1000) as u64);
+            match stop_rx.recv_timeout(wait) {
+                Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Timeout) => continue,
+            }
+        }
+
+        sent_through = next.at;
+        if (now - next.at).num_seconds() > STALE_AFTER_SECS {
+            log::info!("skipping stale reminder: {} (due {})", next.message(), next.at);
+        } else if next.lead > 0 && is_done(conn, &next)? {
+            log::info!("skipping reminder, already prayed: {}", next.message());
+        } else {
+            send(&next);
+        }
+    }
+
+    println!("  sujood daemon stopped");
+    Ok(())
+}
+
+/// The earliest reminder strictly after `after`, looking at that day and the
+/// ones either side: the day before may still have an Isha past midnight.
+fn next_reminder(
+    conn: &Connection,
+    config: &AppConfig,
+    calc: &PrayerCalculator,
+    leads: &[u32],
+    after: NaiveDateTime,
+) -> Result<Reminder> {
+    let mut reminders = Vec::new();
+    let day = after.date();
+    for date in [day.pred_opt(), Some(day), day.succ_opt()].into_iter().flatten() {
+        let times = calc.get_cached_or_compute(conn, date)?;
+        for (prayer, start) in times.prayer_datetimes(date) {
+            let zuhr_jummah = match prayer {
+                PrayerType::Zuhr => jummah_start(config, date, times.zuhr),
+                _ => None,
+            };
+            let (label, time, start) = match zuhr_jummah {
+                Some(jummah) => ("Jummah", jummah, start.date().and_time(jummah)),
+                None => (prayer.display_name(), start.time(), start),
+            };
+            for &lead in leads {
+                let at = start - chrono::Duration::minutes(lead as i64);
+                if at > after {
+                    reminders.push(Reminder { at, date, prayer: prayer.clone(), label, time, lead });
+                }
+            }
+        }
+    }
+    reminders
+        .into_iter()
+        .min_by_key(|r| r.at)
+        .ok_or_else(|| anyhow!("No prayer times found after {}", after))
+}
+
+fn is_done(conn: &Connection, reminder: &Reminder) -> Result<bool> {
+    Ok(PrayerRepo::get_by_date(conn, &date_str(reminder.date))?
+        .iter()
+        .any(|p| !p.is_qada && p.prayer_type == reminder.prayer && p.status == PrayerStatus::Done))
+}
+
+/// Notify through the desktop, echoing to stdout either way.
+fn send(reminder: &Reminder) {
+    let message = reminder.message();
+    println!("  {}  {}", reminder.at.format("%Y-%m-%d %H:%M"), message);
+    if notify("sujood", &message) {
+        log::info!("notified: {}", message);
+    } else {
+        log::warn!("could not show a desktop notification for: {}", message);
+    }
+}
+
+/// `osascript` on macOS, `notify-send` elsewhere. Returns whether it succeeded.
+fn notify(title: &str, body: &str) -> bool {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("osascript");
+        cmd.arg("-e")
+            .arg(format!("display notification {:?} with title {:?}", body, title));
+        cmd
+    } else {
+        let mut cmd = Command::new("notify-send");
+        cmd.args(["--app-name", "sujood", title, body]);
+        cmd
+    };
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::run_migrations;
+
+    #[test]
+    fn reminders_past_midnight_belong_to_the_day_before() {
+        // Oulu, 65°N, in late July: Isha falls after midnight
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        let mut config = AppConfig::default();
+        config.salah.latitude = 65.0;
+        config.salah.longitude = 25.47;
+        config.salah.timezone_offset = 180;
+        let calc = PrayerCalculator::from_config(&config.salah).unwrap();
+        let day = NaiveDate::from_ymd_opt(2026, 7, 20).unwrap();
+        let isha = calc.get_cached_or_compute(&conn, day).unwrap().prayer_datetimes(day)[4].1;
+        assert_eq!(isha.date(), day.succ_opt().unwrap());
+
+        // After midnight, the day before's Isha is still ahead (this far
+        // north it lands minutes after the next day's Fajr)
+        let after = isha - chrono::Duration::minutes(2);
+        let reminder = next_reminder(&conn, &config, &calc, &[0], after).unwrap();
+        assert_eq!((reminder.prayer, reminder.date, reminder.at), (PrayerType::Isha, day, isha));
+    }
+}
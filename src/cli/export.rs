@@ -0,0 +1,253 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppConfig;
+use crate::db::repository::{DhikrRepo, PrayerRepo, QadaRepo, QuranRepo, StatsRepo};
+use crate::models::{DhikrDef, DhikrLog, Prayer, QadaEntry, Streak};
+use crate::prayer_times::calculator::PrayerCalculator;
+use crate::utils::date::date_str;
+
+/// Shape of the JSON export. Bump when a field changes meaning or goes away,
+/// so `sujood import` can refuse files it would misread.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Everything `sujood export --format json` emits and `sujood import` reads.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportData {
+    /// Missing in exports made before versioning, which match version 1
+    #[serde(default = "first_version")]
+    pub version: u32,
+    pub from: String,
+    pub to: String,
+    pub location: String,
+    pub method: String,
+    pub streak: Streak,
+    pub dhikr_definitions: Vec<DhikrDef>,
+    pub qada_queue: Vec<QadaEntry>,
+    pub days: Vec<DayExport>,
+}
+
+fn first_version() -> u32 {
+    1
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayExport {
+    pub date: String,
+    pub prayers: Vec<Prayer>,
+    pub dhikr: Vec<DhikrLog>,
+    pub quran_pages: f64,
+}
+
+/// Collect tracked data for `days` days ending at `today` (inclusive).
+pub fn collect(conn: &Connection, config: &AppConfig, today: NaiveDate, days: u32) -> Result<ExportData> {
+    let from = today - chrono::Duration::days(days.saturating_sub(1) as i64);
+    let from_str = date_str(from);
+    let to_str = date_str(today);
+
+    let prayers = PrayerRepo::get_date_range(conn, &from_str, &to_str)?;
+    let mut day_exports = Vec::new();
+    for date in from.iter_days().take_while(|d| *d <= today) {
+        let date = date_str(date);
+        day_exports.push(DayExport {
+            prayers: prayers.iter().filter(|p| p.date == date).cloned().collect(),
+            dhikr: DhikrRepo::get_log_for_date(conn, &date)?,
+            quran_pages: QuranRepo::get_today(conn, &date)?,
+            date,
+        });
+    }
+
+    // Deactivated adhkar go along too when they have logs here, so every log
+    // has its definition; `active` says which they are
+    let logged: HashSet<i64> =
+        day_exports.iter().flat_map(|d| d.dhikr.iter().map(|l| l.dhikr_id)).collect();
+    let dhikr_definitions = DhikrRepo::get_all_definitions(conn)?
+        .into_iter()
+        .filter(|d| d.active || logged.contains(&d.id))
+        .collect();
+
+    Ok(ExportData {
+        version: EXPORT_VERSION,
+        from: from_str,
+        to: to_str,
+        location: config.salah.location_name.clone(),
+        method: config.salah.calc_method.clone(),
+        streak: StatsRepo::calculate_streak(conn, &config.streak, today)?,
+        dhikr_definitions,
+        qada_queue: QadaRepo::get_queue(conn)?,
+        days: day_exports,
+    })
+}
+
+pub fn to_json(data: &ExportData) -> Result<String> {
+    Ok(serde_json::to_string_pretty(data)?)
+}
+
+/// `date,prayer,status` rows for `days` days ending at `today`, with a header line.
+pub fn to_csv(conn: &Connection, today: NaiveDate, days: u32) -> Result<String> {
+    let from = today - chrono::Duration::days(days.saturating_sub(1) as i64);
+    let prayers = PrayerRepo::get_date_range(conn, &date_str(from), &date_str(today))?;
+
+    let mut csv = String::from("date,prayer,status\n");
+    for p in &prayers {
+        let row = [p.date.as_str(), p.prayer_type.as_str(), p.status.as_str()];
+        let row: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+/// Quote a field per RFC 4180 when it contains a delimiter, quote or newline.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// How long each prayer event blocks out in the calendar.
+const ICS_EVENT_MINUTES: i64 = 15;
+/// Alarm lead time before each prayer.
+const ICS_ALARM_MINUTES: i64 = 5;
+
+/// A VCALENDAR with one event per prayer for `days` days starting at `today`,
+/// in local time under a fixed-offset VTIMEZONE built from `timezone_offset`.
+pub fn to_ics(conn: &Connection, config: &AppConfig, today: NaiveDate, days: u32) -> Result<String> {
+    let calc = PrayerCalculator::from_config(&config.salah)?;
+    calc.ensure_cached(conn, today, days.saturating_sub(1))?;
+
+    let offset = config.salah.timezone_offset;
+    // TZID parameter values can't contain ':' unquoted, so use the compact form
+    let tzid = format!("UTC{}", format_offset(offset));
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//sujood//Prayer Times//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "BEGIN:VTIMEZONE".to_string(),
+        format!("TZID:{}", tzid),
+        "BEGIN:STANDARD".to_string(),
+        "DTSTART:19700101T000000".to_string(),
+        format!("TZOFFSETFROM:{}", format_offset(offset)),
+        format!("TZOFFSETTO:{}", format_offset(offset)),
+        format!("TZNAME:{}", tzid),
+        "END:STANDARD".to_string(),
+        "END:VTIMEZONE".to_string(),
+    ];
+
+    for date in today.iter_days().take(days as usize) {
+        let times = calc.get_cached_or_compute(conn, date)?;
+        for (prayer, start) in times.prayer_datetimes(date) {
+            let end = start + Duration::minutes(ICS_EVENT_MINUTES);
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{}-{}@sujood", date.format("%Y%m%d"), prayer.as_str()),
+                format!("DTSTAMP:{}", stamp),
+                format!("DTSTART;TZID={}:{}", tzid, start.format("%Y%m%dT%H%M%S")),
+                format!("DTEND;TZID={}:{}", tzid, end.format("%Y%m%dT%H%M%S")),
+                format!("SUMMARY:{}", ics_text(prayer.display_name())),
+                format!("LOCATION:{}", ics_text(&config.salah.location_name)),
+                "TRANSP:TRANSPARENT".to_string(),
+                "BEGIN:VALARM".to_string(),
+                "ACTION:DISPLAY".to_string(),
+                format!("DESCRIPTION:{}", ics_text(prayer.display_name())),
+                format!("TRIGGER:-PT{}M", ICS_ALARM_MINUTES),
+                "END:VALARM".to_string(),
+                "END:VEVENT".to_string(),
+            ]);
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    Ok(lines.iter().map(|l| fold_ics_line(l) + "\r\n").collect())
+}
+
+/// UTC offset in minutes as `+0530`.
+fn format_offset(minutes: i32) -> String {
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let abs = minutes.abs();
+    format!("{}{:02}{:02}", sign, abs / 60, abs % 60)
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11).
+fn ics_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold lines longer than 75 octets, never splitting a UTF-8 character.
+fn fold_ics_line(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::run_migrations;
+
+    #[test]
+    fn json_keeps_logged_deactivated_adhkar_and_mark_times() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        let config = AppConfig::default();
+        let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        for name in ["Travel dua", "Unused"] {
+            DhikrRepo::add_custom(&conn, name, "checkbox", 1, "daily", None).unwrap();
+            let id = conn.last_insert_rowid();
+            if name == "Travel dua" {
+                DhikrRepo::upsert_log(&conn, id, "2026-10-16", 1, true).unwrap();
+            }
+            DhikrRepo::deactivate(&conn, id).unwrap();
+        }
+        PrayerRepo::ensure_rows_for_date(&conn, "2026-10-16").unwrap();
+        let marked = day.and_hms_opt(5, 40, 0).unwrap();
+        PrayerRepo::mark_status(&conn, "fajr", "2026-10-16", "done", Some(marked)).unwrap();
+
+        let data = collect(&conn, &config, day, 1).unwrap();
+        let travel = data.dhikr_definitions.iter().find(|d| d.name == "Travel dua").unwrap();
+        assert!(!travel.active);
+        assert!(!data.dhikr_definitions.iter().any(|d| d.name == "Unused"));
+        assert_eq!(data.days[0].prayers[0].marked_at, Some(marked));
+    }
+
+    #[test]
+    fn ics_puts_prayers_past_midnight_on_the_next_day() {
+        // Oulu, 65°N, in late July: the sun sets close to midnight local time
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        let mut config = AppConfig::default();
+        config.salah.latitude = 65.0;
+        config.salah.longitude = 25.47;
+        config.salah.timezone_offset = 180;
+        let day = NaiveDate::from_ymd_opt(2026, 7, 20).unwrap();
+
+        let ics = to_ics(&conn, &config, day, 1).unwrap();
+        let starts: Vec<&str> = ics
+            .lines()
+            .filter_map(|l| l.strip_prefix("DTSTART;TZID=UTC+0300:"))
+            .collect();
+        assert_eq!(starts.len(), 5);
+        assert!(starts.windows(2).all(|w| w[0] < w[1]), "{starts:?}");
+        assert!(starts[4].starts_with("20260721T"), "Isha at {}", starts[4]);
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::Result;
+
+/// Where an IP geolocation lookup places this machine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Located {
+    pub city: String,
+    pub country: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Current UTC offset there, in minutes
+    pub timezone_offset: Option<i32>,
+}
+
+/// Look up the approximate location of this machine's public IP address.
+/// Only compiled in with the `auto-location` feature — the one place sujood
+/// touches the network.
+#[cfg(feature = "auto-location")]
+pub fn lookup() -> Result<Located> {
+    use anyhow::{anyhow, Context};
+    use std::io::{Read, Write};
+    use std::net::{TcpStream, ToSocketAddrs};
+    use std::time::Duration;
+
+    const HOST: &str = "ip-api.com";
+    const TIMEOUT: Duration = Duration::from_secs(4);
+
+    let addr = (HOST, 80)
+        .to_socket_addrs()
+        .context("Resolving the geolocation service")?
+        .next()
+        .ok_or_else(|| anyhow!("No address for {}", HOST))?;
+    let mut stream =
+        TcpStream::connect_timeout(&addr, TIMEOUT).context("Connecting to the geolocation service")?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    // HTTP/1.0 keeps the reply a plain body that ends when the connection closes
+    write!(
+        stream,
+        "GET /json/?fields=status,message,city,countryCode,lat,lon,offset HTTP/1.0\r\n\
+         Host: {}\r\nUser-Agent: sujood\r\nAccept: application/json\r\n\r\n",
+        HOST
+    )?;
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .context("Reading the geolocation reply")?;
+
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body)
+        .ok_or_else(|| anyhow!("Malformed reply from {}", HOST))?;
+    let json: serde_json::Value =
+        serde_json::from_str(body.trim()).context("Parsing the geolocation reply")?;
+    if json["status"] != "success" {
+        let reason = json["message"].as_str().unwrap_or("lookup failed");
+        return Err(anyhow!("Geolocation service: {}", reason));
+    }
+
+    let (Some(latitude), Some(longitude)) = (json["lat"].as_f64(), json["lon"].as_f64()) else {
+        return Err(anyhow!("Geolocation reply had no coordinates"));
+    };
+    Ok(Located {
+        city: json["city"].as_str().unwrap_or_default().to_string(),
+        country: json["countryCode"].as_str().map(str::to_string),
+        latitude,
+        longitude,
+        timezone_offset: json["offset"].as_i64().map(|secs| (secs / 60) as i32),
+    })
+}
+
+#[cfg(not(feature = "auto-location"))]
+pub fn lookup() -> Result<Located> {
+    Err(anyhow::anyhow!(
+        "this build has no network support — reinstall with `--features auto-location`"
+    ))
+}
@@ -1,35 +1,72 @@
-use anyhow::{anyhow, Result};
-use chrono::Local;
+use anyhow::{anyhow, Context, Result};
+use chrono::Datelike;
+use clap::CommandFactory;
 use rusqlite::Connection;
+use std::fmt::Write as _;
+use std::fs;
 use std::io::{self, BufRead, Write};
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::cli::args::{DhikrCommands, QadaCommands};
-use crate::config::AppConfig;
-use crate::db::repository::{DhikrRepo, MetaRepo, PrayerRepo, QadaRepo, QuranRepo, StatsRepo};
-use crate::models::{DhikrType, PrayerType};
-use crate::prayer_times::calculator::PrayerCalculator;
-use crate::utils::format::{format_duration_secs, format_pages};
+use crate::cli::args::{Cli, DhikrCommands, ExportFormat, ProfileCommands, QadaCommands};
+use crate::cli::catchup::{self, CatchupAction};
+use crate::cli::export;
+use crate::cli::geolocate;
+use crate::cli::import;
+use crate::config::{AppConfig, TimesSource};
+use crate::db::repository::{
+    CacheRepo, DhikrRepo, EidRepo, FastingRepo, HistoryRepo, JummahRepo, MetaRepo, NaflRepo,
+    PrayerRepo, QadaRepo, QiyamRepo, QuranRepo, StatsRepo,
+};
+use crate::models::{
+    group_dhikr, remaining_prayers, DailyStats, DhikrFrequency, DhikrType, EidEntry, JummahEntry,
+    KhatmPlan, Nafl, PrayerStatus, PrayerType, StatsRange,
+};
+use crate::prayer_times::calculator::{self, PrayerCalculator};
+use crate::prayer_times::timetable;
+use crate::utils::date::{
+    date_str, jummah_leave_at, jummah_start, last_friday, logical_date, now_local, place_in_order,
+    today_date, today_str, DATETIME_FMT, DATE_FMT,
+};
+use crate::utils::format::{
+    format_days_approx, format_duration_secs, format_pages, format_time_as, progress_bar,
+    time_column_width,
+};
+use crate::utils::hijri::{eid_on, sunnah_fast_on};
+use crate::utils::quran;
 
 // ─── ANSI helpers ────────────────────────────────────────────────────────────
 
 #[allow(unused_macros)]
 macro_rules! print_colored {
     ($color:expr, $($arg:tt)*) => {{
-        print!("{}", $color);
-        print!($($arg)*);
-        print!("\x1b[0m");
+        print!("{}", paint($color, format!($($arg)*)));
     }};
 }
 
 macro_rules! println_colored {
     ($color:expr, $($arg:tt)*) => {{
-        print!("{}", $color);
-        print!($($arg)*);
-        println!("\x1b[0m");
+        println!("{}", paint($color, format!($($arg)*)));
     }};
 }
 
+static COLOR: AtomicBool = AtomicBool::new(true);
+
+/// Turn ANSI colors on or off for everything the CLI prints.
+pub fn set_color(enabled: bool) {
+    COLOR.store(enabled, Ordering::Relaxed);
+}
+
+/// `text` wrapped in `color`, or left plain when colors are off.
+fn paint(color: &str, text: impl std::fmt::Display) -> String {
+    if COLOR.load(Ordering::Relaxed) {
+        format!("{}{}\x1b[0m", color, text)
+    } else {
+        text.to_string()
+    }
+}
+
 const GREEN: &str = "\x1b[32m";
 const AMBER: &str = "\x1b[33m";
 const RED: &str = "\x1b[31m";
@@ -43,65 +80,275 @@ pub fn handle_setup(
     conn: &Connection,
     config: &mut AppConfig,
     reset: bool,
+    high_latitude_rule: Option<String>,
+    auto_location: bool,
 ) -> Result<()> {
-    if !reset {
-        if let Some(done) = MetaRepo::get(conn, "setup_done")? {
-            if done == "1" {
-                println!("Sujood is already configured. Use --reset to reconfigure.");
-                return Ok(());
+    if let Some(rule) = high_latitude_rule {
+        return set_high_latitude_rule(conn, config, rule);
+    }
+    if auto_location {
+        // Only prefills the wizard, so a failed lookup just means typing it in
+        match geolocate::lookup() {
+            Ok(found) => {
+                config.salah.location_name = found.city;
+                config.salah.latitude = found.latitude;
+                config.salah.longitude = found.longitude;
+                if found.country.is_some() {
+                    config.salah.country = found.country;
+                }
+                if let Some(offset) = found.timezone_offset {
+                    config.salah.timezone_offset = offset;
+                }
+            }
+            Err(e) => {
+                eprintln!("Couldn't detect your location ({:#}) — enter it manually.", e);
+                eprintln!("Press Enter to continue...");
+                let _ = io::stdin().lock().read_line(&mut String::new());
             }
         }
+    } else if !reset && MetaRepo::get(conn, "setup_done")?.as_deref() == Some("1") {
+        println!("Sujood is already configured. Use --reset to reconfigure.");
+        return Ok(());
     }
     crate::cli::setup_tui::run_setup_tui(conn, config)
 }
 
+fn set_high_latitude_rule(conn: &Connection, config: &mut AppConfig, rule: String) -> Result<()> {
+    let mut updated = config.clone();
+    updated.salah.high_latitude_rule = rule;
+    // Validates the rule name before anything is saved
+    let calc = PrayerCalculator::from_config(&updated.salah)?;
+
+    // Cached times were computed under the old rule
+    sync_cache(conn, &updated)?;
+    calc.ensure_cached(conn, now_local(&updated).date(), updated.salah.cache_days_ahead)?;
+
+    updated.save()?;
+    *config = updated;
+
+    println_colored!(
+        GREEN,
+        "  ✓ High-latitude rule set to {} — prayer times re-cached",
+        config.salah.high_latitude_rule
+    );
+    Ok(())
+}
+
+// ─── Profiles ────────────────────────────────────────────────────────────────
+
+pub fn handle_profile(conn: &Connection, config: &mut AppConfig, action: &ProfileCommands) -> Result<()> {
+    match action {
+        ProfileCommands::List => {
+            println!();
+            if config.profiles.is_empty() {
+                println_colored!(DIM, "  No profiles yet. Save this location with `sujood profile add <name>`");
+            }
+            for (name, salah) in &config.profiles {
+                let active = config.profile.as_deref() == Some(name.as_str());
+                // The active profile's live settings are in [salah]
+                let salah = if active { &config.salah } else { salah };
+                let line = format!(
+                    "  {} {:<12} {} · UTC{} · {}",
+                    if active { "●" } else { " " },
+                    name,
+                    salah.location_name,
+                    format_tz_offset(salah.timezone_offset),
+                    salah.calc_method
+                );
+                if active {
+                    println_colored!(GOLD, "{}", line);
+                } else {
+                    println!("{}", line);
+                }
+            }
+            println!();
+        }
+        ProfileCommands::Add { name } => {
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                return Err(anyhow!("Profile names may only use letters, digits, - and _"));
+            }
+            if config.profiles.contains_key(name) {
+                return Err(anyhow!("Profile '{}' already exists — `sujood profile switch {}` to use it", name, name));
+            }
+            let mut updated = config.clone();
+            updated.profiles.insert(name.clone(), updated.salah.clone());
+            updated.profile = Some(name.clone());
+            updated.save()?;
+            *config = updated;
+            println_colored!(
+                GREEN,
+                "  ✓ Saved {} as profile '{}' (active)",
+                config.salah.location_name,
+                name
+            );
+        }
+        ProfileCommands::Switch { name } => {
+            if config.profile.as_deref() == Some(name.as_str()) {
+                println_colored!(DIM, "  Already on profile '{}'", name);
+                return Ok(());
+            }
+            let mut updated = config.clone();
+            updated.switch_profile(name)?;
+            let calc = PrayerCalculator::from_config(&updated.salah)?;
+            sync_cache(conn, &updated)?;
+            calc.ensure_cached(conn, now_local(&updated).date(), updated.salah.cache_days_ahead)?;
+            updated.save()?;
+            *config = updated;
+            println_colored!(
+                GREEN,
+                "  ✓ Switched to '{}' — {}, prayer times re-cached",
+                name,
+                config.salah.location_name
+            );
+        }
+    }
+    Ok(())
+}
+
+/// app_meta key holding `SalahConfig::fingerprint` of the settings the
+/// prayer times cache was filled under.
+const CACHE_FINGERPRINT_KEY: &str = "cache_fingerprint";
+
+/// Clear cached prayer times computed under different `[salah]` settings —
+/// after a hand edit to config.toml, a profile switch or `--profile` — and
+/// record the settings now in use. An imported timetable survives while
+/// `source = "file"`. Returns true when the cache was cleared
+/// after having been filled under known settings, i.e. worth refilling.
+pub fn sync_cache(conn: &Connection, config: &AppConfig) -> Result<bool> {
+    let current = config.salah.fingerprint();
+    let previous = MetaRepo::get(conn, CACHE_FINGERPRINT_KEY)?;
+    if previous.as_deref() == Some(current.as_str()) {
+        return Ok(false);
+    }
+    match config.salah.source {
+        TimesSource::Calculated => CacheRepo::clear_all(conn)?,
+        TimesSource::File => CacheRepo::clear_computed(conn)?,
+    }
+    MetaRepo::set(conn, CACHE_FINGERPRINT_KEY, &current)?;
+    Ok(previous.is_some())
+}
+
 // ─── Times ───────────────────────────────────────────────────────────────────
 
-pub fn handle_times(conn: &Connection, config: &AppConfig) -> Result<()> {
-    let today = Local::now().date_naive();
-    let today_str = today.format("%Y-%m-%d").to_string();
-    let now_time = Local::now().time();
-
-    let calc = PrayerCalculator::new(
-        config.salah.latitude,
-        config.salah.longitude,
-        &config.salah.calc_method,
-        &config.salah.madhab,
-        config.salah.timezone_offset,
-    )?;
+/// `sujood times import`: load a published timetable into the cache in one
+/// transaction, replacing calculated times for the days it covers.
+pub fn handle_times_import(conn: &Connection, config: &AppConfig, file: &Path) -> Result<()> {
+    let csv = fs::read_to_string(file).with_context(|| format!("Reading {}", file.display()))?;
+    let days = timetable::parse(&csv, config.salah.imsak_minutes)
+        .with_context(|| format!("Importing {}", file.display()))?;
 
-    let times = calc.get_cached_or_compute(conn, today)?;
+    let tx = conn.unchecked_transaction()?;
+    for (date, times) in &days {
+        CacheRepo::store_imported(&tx, &date_str(*date), times)?;
+    }
+    tx.commit()?;
 
-    println!();
+    let (first, last) = (days[0].0, days[days.len() - 1].0);
     println_colored!(
-        GOLD,
-        "  Prayer Times — {} ({})",
-        config.salah.location_name,
-        today_str
+        GREEN,
+        "  ✓ Imported prayer times for {} day{} ({} to {})",
+        days.len(),
+        if days.len() == 1 { "" } else { "s" },
+        first,
+        last
     );
+    if config.salah.source != TimesSource::File {
+        println_colored!(
+            AMBER,
+            "  Set source = \"file\" under [salah] in config.toml, or changing a setting will recalculate over them"
+        );
+    }
+    Ok(())
+}
+
+/// Times for today, or for `date` / tomorrow. The countdown and the
+/// dimming of past prayers only apply to today.
+pub fn handle_times(
+    conn: &Connection,
+    config: &AppConfig,
+    date: Option<&str>,
+    tomorrow: bool,
+    both_asr: bool,
+) -> Result<()> {
+    // Prayer times follow the calendar day, not the tracking rollover
+    let now = now_local(config);
+    let today = now.date();
+    let day = match date {
+        Some(d) => parse_date(d)?,
+        None if tomorrow => today.succ_opt().unwrap_or(today),
+        None => today,
+    };
+    let is_today = day == today;
+
+    let calc = PrayerCalculator::from_config(&config.salah)?;
+
+    let times = calc.get_cached_or_compute(conn, day)?;
+
+    println!();
+    let label = match (day - today).num_days() {
+        0 => date_str(day),
+        1 => format!("{}, tomorrow", date_str(day)),
+        -1 => format!("{}, yesterday", date_str(day)),
+        _ => format!("{}, {}", date_str(day), day.format("%A")),
+    };
+    println_colored!(GOLD, "  Prayer Times — {} ({})", config.salah.location_name, label);
     println!();
 
+    let zuhr = match jummah_start(config, day, times.zuhr) {
+        Some(start) => ("Jummah", start),
+        None => ("Zuhr", times.zuhr),
+    };
     let prayers_with_times = [
+        ("Imsak", times.imsak),
         ("Fajr", times.fajr),
         ("Sunrise", times.sunrise),
-        ("Zuhr", times.zuhr),
+        zuhr,
         ("Asr", times.asr),
         ("Maghrib", times.maghrib),
         ("Isha", times.isha),
     ];
 
-    for (name, time) in &prayers_with_times {
-        let time_str = time.format("%H:%M").to_string();
-        let is_past = *time < now_time;
+    let other_asr = if both_asr { Some(calc.other_madhab_asr(day)?) } else { None };
+    let clock = config.ui.time_format;
+    // Right-aligned so "9:05 AM" and "12:05 PM" line up
+    let width = time_column_width(clock) - 2;
+    let show = |t: chrono::NaiveTime| format!("{:>width$}", format_time_as(t, clock));
+
+    let times_in_order: Vec<chrono::NaiveTime> = prayers_with_times.iter().map(|(_, t)| *t).collect();
+    let moments = place_in_order(day, &times_in_order);
+    for ((name, time), at) in prayers_with_times.iter().zip(moments) {
+        let time_str = show(*time);
+        let is_past = is_today && at < now;
         if is_past {
-            println_colored!(DIM, "  {:<10}  {}", name, time_str);
+            print_colored!(DIM, "  {:<10}  {}", name, time_str);
         } else {
-            println_colored!(BOLD, "  {:<10}  {}", name, time_str);
+            print_colored!(BOLD, "  {:<10}  {}", name, time_str);
         }
+        if at.date() != day {
+            print_colored!(DIM, "  (after midnight)");
+        }
+        match other_asr {
+            Some((madhab, asr)) if *name == "Asr" => {
+                println_colored!(DIM, "   {} {}", madhab, format_time_as(asr, clock))
+            }
+            _ => println!(),
+        }
+    }
+    // That night's, so today's is still ahead even after Isha
+    println_colored!(DIM, "  {:<10}  {}  (tahajjud)", "Last third", show(times.last_third_start));
+
+    if let Some(leave_at) = jummah_leave_at(config, day, times.zuhr) {
+        println!();
+        println_colored!(AMBER, "  Leave for Jummah by {}", format_time_as(leave_at, clock));
+    }
+
+    if !is_today {
+        println!();
+        return Ok(());
     }
 
     // Countdown to next prayer
-    if let Some((next_prayer, secs)) = calc.get_next_prayer(conn, today, now_time)? {
+    if let Some((next_prayer, secs)) = calc.get_next_prayer(conn, today, now.time())? {
         println!();
         println_colored!(
             AMBER,
@@ -118,35 +365,660 @@ pub fn handle_times(conn: &Connection, config: &AppConfig) -> Result<()> {
 
 pub fn handle_mark(
     conn: &Connection,
+    config: &AppConfig,
     prayer_str: &str,
     missed: bool,
+    date: Option<&str>,
+    note: Option<&str>,
 ) -> Result<()> {
+    if let Ok(nafl) = Nafl::from_str(prayer_str) {
+        return handle_mark_nafl(conn, config, nafl, missed, date, note);
+    }
     let prayer_type = PrayerType::from_str(prayer_str)
         .map_err(|_| anyhow!("Unknown prayer '{}'. Use: fajr, zuhr, asr, maghrib, isha", prayer_str))?;
-    let today = Local::now().date_naive();
-    let today_str = today.format("%Y-%m-%d").to_string();
+    let today = today_date(config);
+    let day = match date {
+        Some(s) => parse_past_date(s, today)?,
+        None => today,
+    };
+    let day_str = date_str(day);
+    // Only mention the date when backfilling
+    let on_day = if day == today {
+        String::new()
+    } else {
+        format!(" on {}", day_str)
+    };
 
     // Ensure rows exist
-    PrayerRepo::ensure_today_rows(conn, &today_str)?;
+    calculator::ensure_rows(conn, config, day)?;
 
     if missed {
-        PrayerRepo::mark_status(conn, prayer_type.as_str(), &today_str, "missed")?;
-        QadaRepo::add_entry(conn, prayer_type.as_str(), &today_str)?;
+        PrayerRepo::mark_status(conn, prayer_type.as_str(), &day_str, "missed", None)?;
+        QadaRepo::add_entry(conn, prayer_type.as_str(), &day_str)?;
         println_colored!(
             RED,
-            "  ✗ {} marked as missed — added to qada queue",
-            prayer_type.display_name()
+            "  ✗ {} marked as missed{} — added to qada queue",
+            prayer_type.display_name(),
+            on_day
         );
     } else {
-        PrayerRepo::mark_status(conn, prayer_type.as_str(), &today_str, "done")?;
-        println_colored!(GREEN, "  ✓ {} marked as done", prayer_type.display_name());
+        let was_missed = PrayerRepo::get_by_date(conn, &day_str)?
+            .iter()
+            .any(|p| p.prayer_type == prayer_type && p.status == PrayerStatus::Missed);
+        // Only a mark made on the day itself says when the prayer was prayed
+        let marked_at = (day == today).then(|| now_local(config));
+        PrayerRepo::mark_status(conn, prayer_type.as_str(), &day_str, "done", marked_at)?;
+        // It was prayed after all, so it's no longer owed
+        let unqueued = if was_missed {
+            QadaRepo::remove_for(conn, prayer_type.as_str(), &day_str)?
+        } else {
+            0
+        };
+        if unqueued > 0 {
+            println_colored!(
+                GREEN,
+                "  ✓ {} marked as done{} — removed from qada queue",
+                prayer_type.display_name(),
+                on_day
+            );
+        } else {
+            println_colored!(GREEN, "  ✓ {} marked as done{}", prayer_type.display_name(), on_day);
+        }
+    }
+    if let Some(note) = note.map(str::trim).filter(|n| !n.is_empty()) {
+        PrayerRepo::set_note(conn, prayer_type.as_str(), &day_str, Some(note))?;
+        println_colored!(DIM, "  ✎ {}", note);
+    }
+    Ok(())
+}
+
+/// `sujood mark duha`: record a voluntary prayer, leaving the fard untouched.
+fn handle_mark_nafl(
+    conn: &Connection,
+    config: &AppConfig,
+    nafl: Nafl,
+    missed: bool,
+    date: Option<&str>,
+    note: Option<&str>,
+) -> Result<()> {
+    if !config.nafl.contains(&nafl) {
+        return Err(anyhow!(
+            "{} isn't tracked — add \"{}\" to `nafl` in config.toml",
+            nafl.display_name(),
+            nafl.as_str()
+        ));
+    }
+    if missed || note.is_some() {
+        return Err(anyhow!("Nafl prayers can only be marked prayed (no --missed or --note)"));
+    }
+    if nafl == Nafl::Tahajjud {
+        if date.is_some() {
+            return Err(anyhow!("Tahajjud is recorded for the current night only"));
+        }
+        let night = PrayerCalculator::from_config(&config.salah)?.night_at(conn, now_local(config))?;
+        if QiyamRepo::get(conn, &date_str(night.date))?.is_some() {
+            println_colored!(DIM, "  Tahajjud is already recorded for the night of {}", date_str(night.date));
+            return Ok(());
+        }
+        return handle_qiyam(conn, config);
+    }
+
+    let today = today_date(config);
+    let day = match date {
+        Some(s) => parse_past_date(s, today)?,
+        None => today,
+    };
+    let prayed_at = now_local(config).format(DATETIME_FMT).to_string();
+    NaflRepo::mark(conn, nafl, &date_str(day), &prayed_at)?;
+    let on_day = if day == today { String::new() } else { format!(" on {}", date_str(day)) };
+    println_colored!(GREEN, "  ✓ {} prayed{}", nafl.display_name(), on_day);
+    Ok(())
+}
+
+// ─── Remaining ───────────────────────────────────────────────────────────────
+
+pub fn handle_remaining(conn: &Connection, config: &AppConfig) -> Result<()> {
+    let today = today_date(config);
+    let today_str = date_str(today);
+    calculator::ensure_rows(conn, config, today)?;
+
+    let prayers = PrayerRepo::get_by_date(conn, &today_str)?;
+    let remaining = remaining_prayers(&prayers);
+
+    if remaining.is_empty() {
+        println_colored!(GREEN, "  ✓ All prayers done for today — alhamdulillah");
+    } else {
+        let names: Vec<&str> = remaining.iter().map(|p| p.display_name()).collect();
+        let noun = if remaining.len() == 1 { "prayer" } else { "prayers" };
+        println_colored!(
+            AMBER,
+            "  {} {} left today: {}",
+            remaining.len(),
+            noun,
+            names.join(", ")
+        );
+    }
+    Ok(())
+}
+
+// ─── Next ────────────────────────────────────────────────────────────────────
+
+/// `sujood next`: "Asr 1h 12m", or the `template` filled in, with no colour so
+/// status bars can show it as is.
+pub fn handle_next(conn: &Connection, config: &AppConfig, template: &str, json: bool) -> Result<()> {
+    if json {
+        println!("{}", next_prayer_json(conn, config)?);
+        return Ok(());
     }
+    let Some((prayer, secs, time)) = next_prayer(conn, config)? else {
+        return Ok(());
+    };
+    println!(
+        "{}",
+        template
+            .replace("{prayer}", prayer.display_name())
+            .replace("{countdown}", &format_duration_secs(secs))
+            .replace("{time}", &time)
+            .replace("{minutes}", &(secs / 60).to_string())
+    );
+    Ok(())
+}
+
+/// The next prayer as `sujood next --json` prints it, or null when none is known.
+pub fn next_prayer_json(conn: &Connection, config: &AppConfig) -> Result<serde_json::Value> {
+    Ok(match next_prayer(conn, config)? {
+        Some((prayer, secs, time)) => serde_json::json!({
+            "prayer": prayer.display_name(),
+            "time": time,
+            "seconds": secs,
+            "countdown": format_duration_secs(secs),
+        }),
+        None => serde_json::Value::Null,
+    })
+}
+
+/// The next prayer, seconds until it, and its time as HH:MM.
+fn next_prayer(conn: &Connection, config: &AppConfig) -> Result<Option<(PrayerType, i64, String)>> {
+    let now = now_local(config);
+    let calc = PrayerCalculator::from_config(&config.salah)?;
+    let Some((prayer, secs)) = calc.get_next_prayer(conn, now.date(), now.time())? else {
+        return Ok(None);
+    };
+    // Prayer times are whole minutes, so round away the seconds lost to the countdown
+    let time = (now + chrono::Duration::seconds(secs + 30)).format("%H:%M").to_string();
+    Ok(Some((prayer, secs, time)))
+}
+
+// ─── Summary ─────────────────────────────────────────────────────────────────
+
+/// One line for `sujood --summary`: "Next: Asr in 1h 12m · 3 left today: Asr, Maghrib, Isha"
+pub fn handle_summary(conn: &Connection, config: &AppConfig) -> Result<()> {
+    println!("{}", summary_line(conn, config, now_local(config))?);
+    Ok(())
+}
+
+/// The `--summary` glance as of the local time `now`.
+fn summary_line(
+    conn: &Connection,
+    config: &AppConfig,
+    now: chrono::NaiveDateTime,
+) -> Result<String> {
+    let calc = PrayerCalculator::from_config(&config.salah)?;
+    let next = calc.get_next_prayer(conn, now.date(), now.time())?;
+
+    let today = logical_date(now, config.day.rollover_hour);
+    calculator::ensure_rows(conn, config, today)?;
+    let remaining = remaining_prayers(&PrayerRepo::get_by_date(conn, &date_str(today))?);
+
+    let mut parts = Vec::new();
+    if let Some((prayer, secs)) = next {
+        parts.push(format!("Next: {} in {}", prayer.display_name(), format_duration_secs(secs)));
+    }
+    if remaining.is_empty() {
+        parts.push("all prayers done today".to_string());
+    } else {
+        let names: Vec<&str> = remaining.iter().map(|p| p.display_name()).collect();
+        parts.push(format!("{} left today: {}", remaining.len(), names.join(", ")));
+    }
+    Ok(parts.join(" · "))
+}
+
+// ─── List ────────────────────────────────────────────────────────────────────
+
+pub fn handle_list(
+    conn: &Connection,
+    config: &AppConfig,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<()> {
+    let to = match to {
+        Some(s) => parse_date(s)?,
+        None => today_date(config),
+    };
+    let from = match from {
+        Some(s) => parse_date(s)?,
+        None => to - chrono::Duration::days(6),
+    };
+    if from > to {
+        return Err(anyhow!("--from {} is after --to {}", from, to));
+    }
+
+    let grid = list_grid(conn, from, to)?;
+
+    println!();
+    println_colored!(GOLD, "  Prayers {} → {}", date_str(from), date_str(to));
+    println_colored!(DIM, "  ✓ done · ✗ missed · • pending · – no record");
+    println!();
+    print!("{:18}", "");
+    for pt in PrayerType::all() {
+        let short: String = pt.display_name().chars().take(4).collect();
+        print!("{:<5}", short);
+    }
+    println!();
+
+    for (day, statuses) in grid {
+        print!("  {}  ", day.format("%a %Y-%m-%d"));
+        for status in statuses {
+            let (color, symbol) = list_symbol(status.as_ref());
+            print!("{}    ", paint(color, symbol));
+        }
+        println!();
+    }
+    println!();
+    Ok(())
+}
+
+/// Each day from `from` to `to` with its five statuses in prayer order.
+/// Days with no rows at all still get a line, all None ("no record").
+fn list_grid(
+    conn: &Connection,
+    from: chrono::NaiveDate,
+    to: chrono::NaiveDate,
+) -> Result<Vec<(chrono::NaiveDate, Vec<Option<PrayerStatus>>)>> {
+    let prayers = PrayerRepo::get_date_range(conn, &date_str(from), &date_str(to))?;
+    let mut statuses: std::collections::HashMap<(String, PrayerType), PrayerStatus> = prayers
+        .into_iter()
+        .map(|p| ((p.date, p.prayer_type), p.status))
+        .collect();
+    Ok(from
+        .iter_days()
+        .take_while(|d| *d <= to)
+        .map(|day| {
+            let day_str = date_str(day);
+            let row = PrayerType::all()
+                .into_iter()
+                .map(|pt| statuses.remove(&(day_str.clone(), pt)))
+                .collect();
+            (day, row)
+        })
+        .collect())
+}
+
+/// Color and symbol `sujood list` shows for a status, or for no record.
+fn list_symbol(status: Option<&PrayerStatus>) -> (&'static str, &'static str) {
+    match status {
+        Some(PrayerStatus::Done) => (GREEN, "✓"),
+        Some(PrayerStatus::Missed) => (RED, "✗"),
+        Some(PrayerStatus::Pending) => (AMBER, "•"),
+        None => (DIM, "–"),
+    }
+}
+
+// ─── History ─────────────────────────────────────────────────────────────────
+
+pub fn handle_history(conn: &Connection, config: &AppConfig, date: &str) -> Result<()> {
+    let day = parse_date(date)?;
+    if day > today_date(config) {
+        return Err(anyhow!("{} is in the future — nothing has been recorded yet", day));
+    }
+    let day_str = date_str(day);
+
+    let prayers = PrayerRepo::get_by_date(conn, &day_str)?;
+    let logs = DhikrRepo::get_log_for_date(conn, &day_str)?;
+    let pages = QuranRepo::get_today(conn, &day_str)?;
+
+    println!();
+    println_colored!(GOLD, "  {}", day.format("%A, %Y-%m-%d"));
+    println!();
+    if prayers.is_empty() && logs.is_empty() && pages == 0.0 {
+        println_colored!(DIM, "  No data recorded on this day");
+        println!();
+        return Ok(());
+    }
+
+    // Times frozen on the rows win over the cache, which may since have
+    // been rebuilt under a different config
+    let cached = CacheRepo::get_times_for_date(conn, &day_str)?;
+    for pt in PrayerType::all() {
+        let row = prayers.iter().find(|p| p.prayer_type == pt);
+        let time = row
+            .and_then(|p| p.scheduled_time)
+            .or_else(|| {
+                cached.as_ref().map(|c| match pt {
+                    PrayerType::Fajr => c.fajr,
+                    PrayerType::Zuhr => c.zuhr,
+                    PrayerType::Asr => c.asr,
+                    PrayerType::Maghrib => c.maghrib,
+                    PrayerType::Isha => c.isha,
+                })
+            })
+            .map(|t| t.format("%H:%M").to_string())
+            .unwrap_or_else(|| "--:--".to_string());
+        let (mark, color) = match row.map(|p| &p.status) {
+            Some(PrayerStatus::Done) => ("✓ done", GREEN),
+            Some(PrayerStatus::Missed) => ("✗ missed", RED),
+            Some(PrayerStatus::Pending) => ("○ not marked", DIM),
+            None => ("– no record", DIM),
+        };
+        let note = row
+            .and_then(|p| p.note.as_deref())
+            .map(|n| paint(DIM, format!("   ✎ {}", n)))
+            .unwrap_or_default();
+        println!("  {:<10}  {}   {}{}", pt.display_name(), time, paint(color, mark), note);
+    }
+
+    let defs = DhikrRepo::get_active_definitions(conn)?;
+    if config.dhikr.enabled && !defs.is_empty() {
+        println!();
+        println_colored!(GOLD, "  Adhkar");
+        for def in &defs {
+            let log = logs.iter().find(|l| l.dhikr_id == def.id);
+            let status = match (log, &def.dhikr_type) {
+                (Some(l), _) if l.completed => paint(GREEN, "✓"),
+                (Some(l), DhikrType::Counter) => format!("{}/{}", l.count, def.target_count),
+                _ => paint(DIM, "○"),
+            };
+            println!("  {:<28}  {}", def.name, status);
+        }
+    }
+
+    println!();
+    println!("  Quran:  {} pages", format_pages(pages));
+    println!();
+    Ok(())
+}
+
+// ─── Today ───────────────────────────────────────────────────────────────────
+
+pub fn handle_today(conn: &Connection, config: &AppConfig) -> Result<()> {
+    let today = today_date(config);
+    let today_str = date_str(today);
+
+    let calc = PrayerCalculator::from_config(&config.salah)?;
+    let times = calc.get_cached_or_compute(conn, today)?;
+    calculator::ensure_rows(conn, config, today)?;
+    let prayers = PrayerRepo::get_by_date(conn, &today_str)?;
+
+    println!();
+    println!(
+        "{}",
+        paint(GOLD, format!("  Today — {} ({})", config.salah.location_name, today_str))
+    );
+    println!();
+    println!("{}", paint(DIM, format!("  {:<10}  {}", "Sunrise", times.sunrise.format("%H:%M"))));
+    let schedule = [
+        (PrayerType::Fajr, times.fajr),
+        (PrayerType::Zuhr, times.zuhr),
+        (PrayerType::Asr, times.asr),
+        (PrayerType::Maghrib, times.maghrib),
+        (PrayerType::Isha, times.isha),
+    ];
+    for (prayer_type, time) in &schedule {
+        let row = prayers.iter().find(|p| p.prayer_type == *prayer_type);
+        let status = row.map(|p| p.status.clone()).unwrap_or(PrayerStatus::Pending);
+        let (mark, color) = match status {
+            PrayerStatus::Done => ("✓ done", GREEN),
+            PrayerStatus::Missed => ("✗ missed", RED),
+            PrayerStatus::Pending => ("○ pending", DIM),
+        };
+        let (name, time) = match (prayer_type, jummah_start(config, today, times.zuhr)) {
+            (PrayerType::Zuhr, Some(start)) => ("Jummah", start),
+            _ => (prayer_type.display_name(), *time),
+        };
+        let note = row
+            .and_then(|p| p.note.as_deref())
+            .map(|n| paint(DIM, format!("   ✎ {}", n)))
+            .unwrap_or_default();
+        println!(
+            "  {:<10}  {}   {}{}",
+            name,
+            time.format("%H:%M"),
+            paint(color, mark.to_string()),
+            note
+        );
+    }
+
+    if !config.nafl.is_empty() {
+        let prayed = NaflRepo::prayed_on(conn, &today_str)?;
+        let night = calc.night_at(conn, now_local(config))?;
+        let qiyam = QiyamRepo::get(conn, &date_str(night.date))?.is_some();
+        println!();
+        println!("{}", paint(GOLD, "  Nafl".to_string()));
+        for nafl in &config.nafl {
+            let done = match nafl {
+                Nafl::Tahajjud => qiyam,
+                _ => prayed.contains(nafl),
+            };
+            let status = if done { paint(GREEN, "✓".to_string()) } else { "○".to_string() };
+            println!("  {:<10}  {}", nafl.display_name(), status);
+        }
+    }
+
+    if config.fasting.enabled {
+        let fasted = FastingRepo::fasted_on(conn, &today_str)?;
+        let reason = sunnah_fast_on(today, config.salah.hijri_offset);
+        if fasted || reason.is_some() {
+            println!();
+            let status = if fasted { paint(GREEN, "✓ fasted".to_string()) } else { "○".to_string() };
+            match reason {
+                Some(r) => println!("  Sunnah fast recommended ({})  {}", r.display_name(), status),
+                None => println!("  Fasting  {}", status),
+            }
+        }
+    }
+
+    let defs = DhikrRepo::get_active_definitions(conn)?;
+    if config.dhikr.enabled && !defs.is_empty() {
+        let logs = DhikrRepo::get_log_for_date(conn, &today_str)?;
+        println!();
+        println!("{}", paint(GOLD, "  Adhkar".to_string()));
+        for def in &defs {
+            let log = logs.iter().find(|l| l.dhikr_id == def.id);
+            let (count, completed) = log.map(|l| (l.count, l.completed)).unwrap_or((0, false));
+            let status = if completed {
+                paint(GREEN, "✓".to_string())
+            } else {
+                match def.dhikr_type {
+                    DhikrType::Counter => format!("{}/{}", count, def.target_count),
+                    DhikrType::Checkbox => "○".to_string(),
+                }
+            };
+            println!("  {:<28}  {}", def.name, status);
+        }
+    }
+
+    let pages = QuranRepo::get_today(conn, &today_str)?;
+    let target = KhatmPlan::target(khatm_plan(conn, config)?.as_ref(), config.quran.daily_target);
+    let qada_count = QadaRepo::count_pending(conn)?;
+    println!();
+    let quran_color = if pages >= target { GREEN } else { BOLD };
+    println!(
+        "  Quran:  {}",
+        paint(
+            quran_color,
+            format!("{} / {} pages", format_pages(pages), format_pages(target))
+        )
+    );
+    let qada_color = if qada_count == 0 { GREEN } else { AMBER };
+    println!("  Qada:   {}", paint(qada_color, format!("{} owed", qada_count)));
+    println!();
+    Ok(())
+}
+
+// ─── Qiyam ───────────────────────────────────────────────────────────────────
+
+pub fn handle_qiyam(conn: &Connection, config: &AppConfig) -> Result<()> {
+    let now = now_local(config);
+    let calc = PrayerCalculator::from_config(&config.salah)?;
+    let night = calc.night_at(conn, now)?;
+    let night_str = date_str(night.date);
+    let last_third = night.last_third.format("%H:%M");
+
+    if QiyamRepo::get(conn, &night_str)?.is_some() {
+        QiyamRepo::unmark(conn, &night_str)?;
+        println_colored!(DIM, "  ○ Qiyam for the night of {} — unmarked", night_str);
+        return Ok(());
+    }
+
+    let entry = night.qiyam_at(now);
+    QiyamRepo::mark(conn, &entry)?;
+    if entry.in_last_third {
+        println_colored!(GREEN, "  ✓ Qiyam prayed — in the last third of the night");
+    } else {
+        println_colored!(
+            GREEN,
+            "  ✓ Qiyam prayed — outside the last third (from {})",
+            last_third
+        );
+    }
+    let streak = QiyamRepo::current_streak(conn, night.date)?;
+    let noun = if streak == 1 { "night" } else { "nights" };
+    println_colored!(DIM, "  Qiyam streak: {} {}", streak, noun);
+    Ok(())
+}
+
+// ─── Eid ─────────────────────────────────────────────────────────────────────
+
+pub fn handle_eid(conn: &Connection, config: &AppConfig, missed: bool, history: bool) -> Result<()> {
+    let today = today_date(config);
+    let eid_today = eid_on(today, config.salah.hijri_offset).filter(|_| config.eid.enabled);
+
+    let Some((eid, hijri_year)) = eid_today.filter(|_| !history) else {
+        if missed {
+            return Err(anyhow!("Today isn't Eid — nothing to mark"));
+        }
+        let entries = EidRepo::history(conn)?;
+        println!();
+        if entries.is_empty() {
+            println_colored!(DIM, "  No Eid prayers recorded yet");
+        } else {
+            println_colored!(GOLD, "  Eid prayers");
+            println!();
+            for e in &entries {
+                let (color, mark) = if e.attended { (GREEN, "✓") } else { (RED, "✗") };
+                println_colored!(
+                    color,
+                    "  {} {} {} AH  ({})",
+                    mark,
+                    e.eid.display_name(),
+                    e.hijri_year,
+                    e.date
+                );
+            }
+        }
+        println!();
+        return Ok(());
+    };
+
+    let entry = EidEntry {
+        hijri_year: hijri_year as i64,
+        eid,
+        date: date_str(today),
+        attended: !missed,
+    };
+    EidRepo::record(conn, &entry)?;
+    if entry.attended {
+        println_colored!(GREEN, "  ✓ {} prayer attended — Eid Mubarak", eid.display_name());
+    } else {
+        println_colored!(DIM, "  ✗ {} prayer recorded as not attended", eid.display_name());
+    }
+    Ok(())
+}
+
+// ─── Jummah ──────────────────────────────────────────────────────────────────
+
+pub fn handle_jummah(
+    conn: &Connection,
+    config: &AppConfig,
+    missed: bool,
+    date: Option<&str>,
+) -> Result<()> {
+    if !config.jummah.enabled {
+        return Err(anyhow!("Jummah tracking is off — set enabled = true under [jummah]"));
+    }
+    let today = today_date(config);
+    let day = match date {
+        Some(d) => parse_date(d)?,
+        None => today,
+    };
+    if day > today {
+        return Err(anyhow!("{} hasn't happened yet", date_str(day)));
+    }
+
+    if last_friday(day) != day {
+        if missed || date.is_some() {
+            return Err(anyhow!("{} isn't a Friday", date_str(day)));
+        }
+        let streak = JummahRepo::streak(conn, today)?;
+        let next = last_friday(today) + chrono::Duration::days(7);
+        println!();
+        println_colored!(GOLD, "  Jummah streak: {} weeks (best {})", streak.current, streak.best);
+        println_colored!(DIM, "  Next Jummah: {}", next.format("%A, %b %d"));
+        println!();
+        return Ok(());
+    }
+
+    let entry = JummahEntry {
+        date: date_str(day),
+        attended: !missed,
+    };
+    JummahRepo::record(conn, &entry)?;
+    let streak = JummahRepo::streak(conn, today)?;
+    if entry.attended {
+        println_colored!(GREEN, "  ✓ Jummah attended ({}) — {}-week streak", entry.date, streak.current);
+    } else {
+        println_colored!(DIM, "  ✗ Jummah recorded as not attended ({})", entry.date);
+    }
+    Ok(())
+}
+
+// ─── Fasting ─────────────────────────────────────────────────────────────────
+
+pub fn handle_fast(conn: &Connection, config: &AppConfig, date: Option<&str>) -> Result<()> {
+    if !config.fasting.enabled {
+        return Err(anyhow!("Fasting tracking is off — set enabled = true under [fasting]"));
+    }
+    let today = today_date(config);
+    let day = match date {
+        Some(d) => parse_past_date(d, today)?,
+        None => today,
+    };
+    let day_str = date_str(day);
+    let label = day.format("%a %b %d");
+
+    if FastingRepo::fasted_on(conn, &day_str)? {
+        FastingRepo::unmark(conn, &day_str)?;
+        println_colored!(DIM, "  Fast removed for {}", label);
+        return Ok(());
+    }
+
+    let recorded_at = now_local(config).format(DATETIME_FMT).to_string();
+    FastingRepo::mark(conn, &day_str, &recorded_at)?;
+    match sunnah_fast_on(day, config.salah.hijri_offset) {
+        Some(reason) => {
+            println_colored!(GREEN, "  ✓ Fast recorded for {} ({})", label, reason.display_name())
+        }
+        None => println_colored!(GREEN, "  ✓ Fast recorded for {}", label),
+    }
+    let month_start = day.with_day(1).unwrap_or(day);
+    let count = FastingRepo::count_between(conn, &date_str(month_start), &day_str)?;
+    println_colored!(DIM, "  {} fast{} this month", count, if count == 1 { "" } else { "s" });
     Ok(())
 }
 
 // ─── Qada ────────────────────────────────────────────────────────────────────
 
-pub fn handle_qada(conn: &Connection, action: &QadaCommands) -> Result<()> {
+pub fn handle_qada(conn: &Connection, config: &AppConfig, action: &QadaCommands) -> Result<()> {
     match action {
         QadaCommands::List => {
             let queue = QadaRepo::get_queue(conn)?;
@@ -159,82 +1031,215 @@ pub fn handle_qada(conn: &Connection, action: &QadaCommands) -> Result<()> {
                 println!();
                 for entry in &queue {
                     println!(
-                        "  {} — {}",
+                        "  #{:<4} {} — {}",
+                        entry.id,
                         entry.prayer_type.display_name(),
                         entry.original_date
                     );
                 }
-                // Rough estimate: 1 per day
+                let today = today_date(config);
+                let pace = QadaRepo::pace(conn, today)?;
                 println!();
-                println_colored!(DIM, "  At 1 per day: ~{} days to clear", count);
+                println_colored!(DIM, "  {}", pace.estimate(today));
+                println_colored!(DIM, "  {}", pace.rate_label());
+            }
+            if let Some(last) = QadaRepo::last_completed(conn)?
+                && let Some(when) = last.completed_label(now_local(config))
+            {
+                println_colored!(
+                    DIM,
+                    "  Last made up: {} ({}) at {}",
+                    last.prayer_type.display_name(),
+                    last.original_date,
+                    when
+                );
             }
             println!();
         }
-        QadaCommands::Complete => {
-            let completed = QadaRepo::complete_oldest(conn)?;
-            if completed {
-                println_colored!(GREEN, "  ✓ Oldest qada prayer marked as completed");
-            } else {
-                println_colored!(GREEN, "  ✓ No qada prayers in queue");
+        QadaCommands::Complete { id } => {
+            let now = now_local(config).format(DATETIME_FMT).to_string();
+            let completed_id = match id {
+                Some(id) => {
+                    let entry = QadaRepo::get(conn, *id)?
+                        .ok_or_else(|| anyhow!("No qada entry #{}. See `sujood qada list`", id))?;
+                    if entry.completed {
+                        return Err(anyhow!(
+                            "Qada entry #{} ({} — {}) is already completed",
+                            id,
+                            entry.prayer_type.display_name(),
+                            entry.original_date
+                        ));
+                    }
+                    QadaRepo::complete_by_id(conn, *id, &now)?;
+                    Some(*id)
+                }
+                None => QadaRepo::complete_oldest(conn, &now)?,
+            };
+            match completed_id.map(|id| QadaRepo::get(conn, id)).transpose()?.flatten() {
+                Some(entry) => println_colored!(
+                    GREEN,
+                    "  ✓ Qada {} ({}) marked as completed",
+                    entry.prayer_type.display_name(),
+                    entry.original_date
+                ),
+                None => println_colored!(GREEN, "  ✓ No qada prayers in queue"),
             }
         }
         QadaCommands::Add { prayer } => {
             let prayer_type = PrayerType::from_str(prayer)
                 .map_err(|_| anyhow!("Unknown prayer '{}'", prayer))?;
-            let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+            let today = today_str(config);
             QadaRepo::add_entry(conn, prayer_type.as_str(), &today)?;
             println_colored!(AMBER, "  Added {} to qada queue", prayer_type.display_name());
         }
+        QadaCommands::Remove { id } => {
+            let entry = QadaRepo::get(conn, *id)?
+                .ok_or_else(|| anyhow!("No qada entry #{}. See `sujood qada list`", id))?;
+            QadaRepo::remove_entry(conn, *id)?;
+            println_colored!(
+                GREEN,
+                "  ✓ Removed {} ({}) from the qada queue",
+                entry.prayer_type.display_name(),
+                entry.original_date
+            );
+        }
+        QadaCommands::Backfill { from, to, prayers } => {
+            let today = today_date(config);
+            let to = match to {
+                Some(s) => parse_past_date(s, today)?,
+                None => today,
+            };
+            let from = parse_past_date(from, today)?;
+            if from > to {
+                return Err(anyhow!("--from {} is after --to {}", from, to));
+            }
+            let prayer_types = if prayers.is_empty() {
+                PrayerType::all()
+            } else {
+                prayers
+                    .iter()
+                    .map(|p| PrayerType::from_str(p).map_err(|_| anyhow!("Unknown prayer '{}'", p)))
+                    .collect::<Result<Vec<_>>>()?
+            };
+
+            let considered = ((to - from).num_days() as usize + 1) * prayer_types.len();
+            let added = QadaRepo::add_range(conn, from, to, &prayer_types)?;
+            println_colored!(
+                AMBER,
+                "  Added {} prayer{} to the qada queue ({} → {})",
+                added,
+                if added == 1 { "" } else { "s" },
+                date_str(from),
+                date_str(to)
+            );
+            if added < considered {
+                println_colored!(
+                    DIM,
+                    "  Skipped {} already marked done or queued",
+                    considered - added
+                );
+            }
+        }
     }
     Ok(())
 }
 
 // ─── Dhikr ───────────────────────────────────────────────────────────────────
 
-pub fn handle_dhikr(conn: &Connection, action: &DhikrCommands) -> Result<()> {
-    let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+pub fn handle_dhikr(conn: &Connection, config: &AppConfig, action: &DhikrCommands) -> Result<()> {
+    let today = today_str(config);
 
     match action {
         DhikrCommands::Morning => {
-            toggle_dhikr_by_name(conn, "Morning Adhkar", &today, None)?;
+            toggle_dhikr_by_name(conn, "Morning Adhkar", &today, None, None)?;
         }
         DhikrCommands::Evening => {
-            toggle_dhikr_by_name(conn, "Evening Adhkar", &today, None)?;
+            toggle_dhikr_by_name(conn, "Evening Adhkar", &today, None, None)?;
         }
-        DhikrCommands::Mark { name, count } => {
-            toggle_dhikr_by_name(conn, name, &today, *count)?;
+        DhikrCommands::Mark { name, count, set, from, to } => {
+            if from.is_none() && to.is_none() {
+                toggle_dhikr_by_name(conn, name, &today, *count, *set)?;
+            } else {
+                if count.is_some() || set.is_some() {
+                    return Err(anyhow!("--count and --set can't be combined with --from/--to"));
+                }
+                mark_dhikr_range(conn, config, name, from.as_deref(), to.as_deref())?;
+            }
         }
         DhikrCommands::Add {
             name,
             r#type,
             target,
             freq,
+            category,
         } => {
-            DhikrRepo::add_custom(conn, name, r#type, *target, freq)?;
+            let tag = category.as_deref().map(str::trim).filter(|c| !c.is_empty());
+            DhikrRepo::add_custom(conn, name, r#type, *target, freq, tag)?;
             println_colored!(GREEN, "  ✓ Added dhikr: {}", name);
         }
-        DhikrCommands::List => {
-            let defs = DhikrRepo::get_active_definitions(conn)?;
+        DhikrCommands::Edit { name, target, r#type, freq, rename } => {
+            edit_dhikr(
+                conn,
+                &today,
+                name,
+                *target,
+                r#type.as_deref(),
+                freq.as_deref(),
+                rename.as_deref(),
+            )?;
+        }
+        DhikrCommands::Remove { name, deactivate } => {
+            let def = DhikrRepo::find_by_name(conn, name)?
+                .ok_or_else(|| anyhow!("No active dhikr named '{}'. See `sujood dhikr list`", name))?;
+            if *deactivate {
+                DhikrRepo::deactivate(conn, def.id)?;
+                println_colored!(GREEN, "  ✓ Deactivated dhikr: {} (history kept)", def.name);
+            } else {
+                DhikrRepo::delete_custom(conn, def.id)?;
+                println_colored!(GREEN, "  ✓ Removed dhikr: {}", def.name);
+            }
+        }
+        DhikrCommands::List { category, filter } => {
+            let mut defs = DhikrRepo::get_active_definitions(conn)?;
+            if let Some(category) = category {
+                defs.retain(|d| d.in_category(category));
+            }
+            if let Some(filter) = filter {
+                defs.retain(|d| d.name_matches(filter));
+            }
             let logs = DhikrRepo::get_log_for_date(conn, &today)?;
             println!();
             println_colored!(GOLD, "  Adhkar");
-            println!();
-            for def in &defs {
-                let log = logs.iter().find(|l| l.dhikr_id == def.id);
-                let (count, completed) = log
-                    .map(|l| (l.count, l.completed))
-                    .unwrap_or((0, false));
-                let status = if completed {
-                    format!("{}✓\x1b[0m", GREEN)
-                } else {
-                    match def.dhikr_type {
-                        DhikrType::Counter => {
-                            format!("{}/{}", count, def.target_count)
+            if defs.is_empty() {
+                println!();
+                println_colored!(DIM, "  No dhikr matches");
+            }
+            for (group, members) in group_dhikr(&defs) {
+                println!();
+                println_colored!(DIM, "  {}", group);
+                for def in members {
+                    let log = logs.iter().find(|l| l.dhikr_id == def.id);
+                    let (count, completed) = log
+                        .map(|l| (l.count, l.completed))
+                        .unwrap_or((0, false));
+                    let status = if completed {
+                        paint(GREEN, "✓")
+                    } else {
+                        match def.dhikr_type {
+                            DhikrType::Counter => {
+                                format!("{}/{}", count, def.target_count)
+                            }
+                            DhikrType::Checkbox => format!("○"),
                         }
-                        DhikrType::Checkbox => format!("○"),
-                    }
-                };
-                println!("  {:<30}  {}", def.name, status);
+                    };
+                    let streak = StatsRepo::dhikr_streak(conn, def.id, today_date(config))?;
+                    let streak = if streak.current > 0 {
+                        format!("  {}", paint(DIM, streak_label(streak.current, &def.frequency)))
+                    } else {
+                        String::new()
+                    };
+                    println!("    {:<28}  {}{}", def.name, status, streak);
+                }
             }
             println!();
         }
@@ -242,17 +1247,136 @@ pub fn handle_dhikr(conn: &Connection, action: &DhikrCommands) -> Result<()> {
     Ok(())
 }
 
+fn edit_dhikr(
+    conn: &Connection,
+    today: &str,
+    name: &str,
+    target: Option<i32>,
+    dhikr_type: Option<&str>,
+    frequency: Option<&str>,
+    rename: Option<&str>,
+) -> Result<()> {
+    if target.is_none() && dhikr_type.is_none() && frequency.is_none() && rename.is_none() {
+        return Err(anyhow!("Nothing to change — pass --target, --type, --freq or --rename"));
+    }
+    let def = DhikrRepo::find_by_name(conn, name)?
+        .ok_or_else(|| anyhow!("No active dhikr named '{}'. See `sujood dhikr list`", name))?;
+
+    let dhikr_type = match dhikr_type {
+        Some(t) => DhikrType::from_str(t)
+            .map_err(|_| anyhow!("Unknown type '{}'. Use: checkbox, counter", t))?,
+        None => def.dhikr_type.clone(),
+    };
+    let frequency = match frequency {
+        Some(f) => DhikrFrequency::from_str(f)
+            .map_err(|_| anyhow!("Unknown frequency '{}'. Use: daily, weekly", f))?,
+        None => def.frequency.clone(),
+    };
+    let target = match (target, &dhikr_type) {
+        (Some(n), _) if n < 1 => return Err(anyhow!("--target must be 1 or more")),
+        (Some(_), DhikrType::Checkbox) => {
+            return Err(anyhow!("--target only applies to counter dhikr"));
+        }
+        (Some(n), DhikrType::Counter) => n,
+        (None, DhikrType::Checkbox) => 1,
+        (None, DhikrType::Counter) => def.target_count,
+    };
+    let new_name = rename.map(str::trim).unwrap_or(&def.name);
+    if new_name.is_empty() {
+        return Err(anyhow!("--rename needs a name"));
+    }
+
+    DhikrRepo::update_definition(conn, def.id, new_name, &dhikr_type, target, &frequency)?;
+
+    // Today's count is judged against the new target; earlier days keep theirs
+    if def.dhikr_type == DhikrType::Counter && dhikr_type == DhikrType::Counter {
+        let log = DhikrRepo::get_log_for_date(conn, today)?
+            .into_iter()
+            .find(|l| l.dhikr_id == def.id);
+        if let Some(log) = log {
+            DhikrRepo::upsert_log(conn, def.id, &log.date, log.count, log.count >= target)?;
+        }
+    }
+
+    let shape = match dhikr_type {
+        DhikrType::Checkbox => format!("checkbox, {}", frequency.as_str()),
+        DhikrType::Counter => format!("counter to {}, {}", target, frequency.as_str()),
+    };
+    println_colored!(GREEN, "  ✓ Updated dhikr: {} ({})", new_name, shape);
+    Ok(())
+}
+
+/// "5-day streak" / "2-week streak", matching the dhikr's frequency.
+fn streak_label(count: u32, frequency: &DhikrFrequency) -> String {
+    let unit = match frequency {
+        DhikrFrequency::Daily => "day",
+        DhikrFrequency::Weekly => "week",
+    };
+    format!("{}-{} streak", count, unit)
+}
+
+fn mark_dhikr_range(
+    conn: &Connection,
+    config: &AppConfig,
+    name: &str,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<()> {
+    let def = DhikrRepo::find_by_name(conn, name)?
+        .ok_or_else(|| anyhow!("Dhikr '{}' not found", name))?;
+    let today = today_date(config);
+    let to = match to {
+        Some(s) => parse_past_date(s, today)?,
+        None => today,
+    };
+    let from = match from {
+        Some(s) => parse_past_date(s, today)?,
+        None => to,
+    };
+    if from > to {
+        return Err(anyhow!("--from {} is after --to {}", from, to));
+    }
+
+    let written = DhikrRepo::upsert_log_range(conn, &def, from, to)?;
+    let unit = match def.frequency {
+        DhikrFrequency::Daily => "day",
+        DhikrFrequency::Weekly => "week",
+    };
+    println_colored!(
+        GREEN,
+        "  ✓ {} — done for {} {}{} ({} → {})",
+        def.name,
+        written,
+        unit,
+        if written == 1 { "" } else { "s" },
+        date_str(from),
+        date_str(to)
+    );
+    Ok(())
+}
+
 fn toggle_dhikr_by_name(
     conn: &Connection,
     name: &str,
     date: &str,
     extra_count: Option<i32>,
+    set_count: Option<i32>,
 ) -> Result<()> {
     let def = DhikrRepo::find_by_name(conn, name)?
         .ok_or_else(|| anyhow!("Dhikr '{}' not found", name))?;
+    if set_count.is_some_and(|n| n < 0) {
+        return Err(anyhow!("--set must be 0 or more"));
+    }
+    if set_count.is_some() && def.dhikr_type == DhikrType::Checkbox {
+        return Err(anyhow!(
+            "--set only applies to counter dhikr; mark '{}' again to unmark it",
+            def.name
+        ));
+    }
 
     let log = DhikrRepo::get_log_for_date(conn, date)?;
     let current = log.iter().find(|l| l.dhikr_id == def.id);
+    let date = &def.log_date(date);
 
     match def.dhikr_type {
         DhikrType::Checkbox => {
@@ -267,8 +1391,10 @@ fn toggle_dhikr_by_name(
         }
         DhikrType::Counter => {
             let current_count = current.map(|l| l.count).unwrap_or(0);
-            let add = extra_count.unwrap_or(1);
-            let new_count = current_count + add;
+            let new_count = match set_count {
+                Some(n) => n,
+                None => (current_count + extra_count.unwrap_or(1)).max(0),
+            };
             let completed = new_count >= def.target_count;
             DhikrRepo::upsert_log(conn, def.id, date, new_count, completed)?;
             if completed {
@@ -295,9 +1421,23 @@ fn toggle_dhikr_by_name(
 
 // ─── Quran ───────────────────────────────────────────────────────────────────
 
-pub fn handle_quran(conn: &Connection, pages: f64) -> Result<()> {
-    let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+pub fn handle_quran(conn: &Connection, config: &AppConfig, pages: Option<f64>) -> Result<()> {
+    let pages = match pages {
+        Some(p) => p,
+        None => {
+            let last = QuranRepo::get_input_history(conn)?.first().copied().ok_or_else(|| {
+                anyhow!("Pass the number of pages read, e.g. `sujood quran 2`")
+            })?;
+            println_colored!(DIM, "  Repeating your last entry: {} pages", format_pages(last));
+            last
+        }
+    };
+
+    let today = today_str(config);
     QuranRepo::log_pages(conn, &today, pages)?;
+    if config.quran.input_history_size > 0 {
+        QuranRepo::remember_input(conn, pages, config.quran.input_history_size)?;
+    }
     let total = QuranRepo::get_today(conn, &today)?;
     println_colored!(
         GREEN,
@@ -305,28 +1445,261 @@ pub fn handle_quran(conn: &Connection, pages: f64) -> Result<()> {
         format_pages(pages),
         format_pages(total)
     );
+    report_khatm(conn, config)
+}
+
+pub fn handle_quran_set(conn: &Connection, config: &AppConfig, pages: f64) -> Result<()> {
+    if pages < 0.0 {
+        return Err(anyhow!("Pages can't be negative"));
+    }
+    let today = today_str(config);
+    QuranRepo::set_pages(conn, &today, pages)?;
+    println_colored!(GREEN, "  ✓ Today's total set to {} pages", format_pages(pages));
+    report_khatm(conn, config)
+}
+
+pub fn handle_quran_undo(conn: &Connection) -> Result<()> {
+    match QuranRepo::undo_last(conn)? {
+        Some((date, pages)) => println_colored!(
+            GREEN,
+            "  ✓ Undone — {} is back to {} pages",
+            date,
+            format_pages(pages)
+        ),
+        None => println_colored!(DIM, "  Nothing to undo"),
+    }
+    Ok(())
+}
+
+pub fn handle_quran_read(
+    conn: &Connection,
+    config: &AppConfig,
+    surah: &str,
+    start: Option<u16>,
+    end: Option<u16>,
+) -> Result<()> {
+    let number = quran::find_surah(surah)
+        .ok_or_else(|| anyhow!("Unknown surah '{}'. Use a name like al-Kahf or a number 1–114", surah))?;
+    let info = quran::surah(number);
+    let start = start.unwrap_or(1);
+    let end = end.unwrap_or(info.ayahs);
+    if start == 0 || start > end || end > info.ayahs {
+        return Err(anyhow!(
+            "{} has ayahs 1–{}; got {}–{}",
+            info.name,
+            info.ayahs,
+            start,
+            end
+        ));
+    }
+
+    let pages = quran::approx_pages(number, start, end, config.quran.mushaf_pages);
+    let today = today_str(config);
+    QuranRepo::log_range(conn, &today, number, start, end, pages)?;
+    let total = QuranRepo::get_today(conn, &today)?;
+    println_colored!(
+        GREEN,
+        "  ✓ Logged {} {}–{} (~{:.1} pages) — today's total: {}",
+        info.name,
+        start,
+        end,
+        pages,
+        format_pages(total)
+    );
+    report_khatm(conn, config)
+}
+
+/// Roll the khatm over if the last entry finished it, and say so.
+fn report_khatm(conn: &Connection, config: &AppConfig) -> Result<()> {
+    let mushaf_pages = config.quran.mushaf_pages;
+    if QuranRepo::advance_khatm(conn, today_date(config), mushaf_pages)? {
+        let khatm = QuranRepo::khatm(conn, today_date(config), mushaf_pages)?;
+        println_colored!(GOLD, "  ✦ Khatm complete — {} so far", khatm.completed);
+    }
+    Ok(())
+}
+
+/// The current khatm against `quran.khatm_deadline`, if one is set.
+fn khatm_plan(conn: &Connection, config: &AppConfig) -> Result<Option<KhatmPlan>> {
+    let Some(deadline) = config.quran.khatm_deadline else {
+        return Ok(None);
+    };
+    let today = today_date(config);
+    let khatm = QuranRepo::khatm(conn, today, config.quran.mushaf_pages)?;
+    let read_today = QuranRepo::get_today(conn, &date_str(today))?;
+    Ok(Some(khatm.plan(deadline, today, read_today)))
+}
+
+pub fn handle_quran_status(conn: &Connection, config: &AppConfig) -> Result<()> {
+    let today = today_date(config);
+    let khatm = QuranRepo::khatm(conn, today, config.quran.mushaf_pages)?;
+
+    println!();
+    println_colored!(GOLD, "  Khatm");
+    println!();
+    println_colored!(
+        BOLD,
+        "  {}/{} pages ({}%)  {}",
+        format_pages(khatm.pages),
+        khatm.mushaf_pages,
+        khatm.percent(),
+        progress_bar(khatm.percent(), 100, 20)
+    );
+    match &khatm.started {
+        Some(start) => println_colored!(
+            DIM,
+            "  Started {} · {} completed before this one",
+            start,
+            khatm.completed
+        ),
+        None => println_colored!(DIM, "  Nothing logged yet — `sujood quran 2` to start"),
+    }
+    match khatm.days_to_finish() {
+        Some(days) => println_colored!(
+            DIM,
+            "  Averaging {:.1} pages/day · ~{} to finish (around {})",
+            khatm.daily_average,
+            format_days_approx(days),
+            date_str(today + chrono::Duration::days(days as i64))
+        ),
+        None => println_colored!(DIM, "  No reading in the last two weeks to project a finish"),
+    }
+    if let Some(plan) = khatm_plan(conn, config)? {
+        if plan.days_left == 0 {
+            println_colored!(RED, "  Deadline {} has passed", plan.deadline);
+        } else {
+            println_colored!(
+                if plan.on_pace { GREEN } else { AMBER },
+                "  {} pages/day to finish by {} ({}) · {}",
+                format_pages(plan.pages_per_day),
+                plan.deadline,
+                plan.days_left_label(),
+                plan.pace_label()
+            );
+        }
+    }
+    println!();
+    Ok(())
+}
+
+// ─── Catch-up ────────────────────────────────────────────────────────────────
+
+pub fn handle_catchup(
+    conn: &Connection,
+    config: &AppConfig,
+    action: Option<CatchupAction>,
+) -> Result<()> {
+    let Some(gap) = catchup::detect(conn, today_date(config))? else {
+        println_colored!(GREEN, "  ✓ Nothing to catch up on — you're up to date");
+        return Ok(());
+    };
+
+    println!();
+    println_colored!(GOLD, "  Welcome back");
+    println!();
+    println!(
+        "  It's been {} days since you last tracked a prayer ({} → {}).",
+        gap.days(),
+        date_str(gap.from),
+        date_str(gap.to)
+    );
+    println_colored!(
+        DIM,
+        "  However those days went, today is a fresh start. Nothing is recorded unless you choose."
+    );
+    println!();
+
+    let action = match action {
+        Some(action) => action,
+        None => {
+            println!("  What should {} prayers be recorded as?", gap.prayers());
+            println!("    [1] I prayed them — mark done");
+            println!("    [2] Missed — add them to the qada queue");
+            println!("    [3] Leave those days blank");
+            loop {
+                let answer = prompt("  Choose 1–3 (Enter to decide later): ")?;
+                match answer.trim() {
+                    "" => {
+                        println_colored!(DIM, "  Nothing changed — run `sujood catchup` any time");
+                        return Ok(());
+                    }
+                    "1" => break CatchupAction::Done,
+                    "2" => break CatchupAction::Missed,
+                    "3" => break CatchupAction::Skip,
+                    _ => println_colored!(AMBER, "  Please enter 1, 2 or 3"),
+                }
+            }
+        }
+    };
+
+    let qada_added = catchup::apply(conn, config, gap, action)?;
+    match action {
+        CatchupAction::Done => {
+            println_colored!(GREEN, "  ✓ Marked {} prayers done", gap.prayers());
+        }
+        CatchupAction::Missed => {
+            println_colored!(
+                AMBER,
+                "  Added {} prayers to qada — make them up one at a time with `sujood qada complete`",
+                qada_added
+            );
+        }
+        CatchupAction::Skip => {
+            println_colored!(DIM, "  Left those days blank — they won't be offered again");
+        }
+    }
+    println_colored!(DIM, "  Start with the next prayer; that's enough for today.");
     Ok(())
 }
 
 // ─── Stats ───────────────────────────────────────────────────────────────────
 
-pub fn handle_stats(conn: &Connection, week: bool) -> Result<()> {
-    let today = Local::now().date_naive();
-    let today_str = today.format("%Y-%m-%d").to_string();
+pub fn handle_stats(
+    conn: &Connection,
+    config: &AppConfig,
+    range: Option<StatsRange>,
+    prayer: Option<&str>,
+    reset_view: bool,
+) -> Result<()> {
+    let today = today_date(config);
+    let today_str = date_str(today);
+
+    // Start from the remembered view; any flag given updates it
+    if reset_view {
+        StatsRepo::reset_view(conn)?;
+    }
+    let mut view = StatsRepo::load_view(conn)?;
+    if let Some(range) = range {
+        view.range = range;
+    }
+    if let Some(p) = prayer {
+        view.prayer = match p.to_lowercase().as_str() {
+            "all" => None,
+            _ => Some(PrayerType::from_str(p).map_err(|_| {
+                anyhow!("Unknown prayer '{}'. Use: fajr, zuhr, asr, maghrib, isha, all", p)
+            })?),
+        };
+    }
+    if range.is_some() || prayer.is_some() {
+        StatsRepo::save_view(conn, &view)?;
+    }
 
     // Streak
-    let streak = StatsRepo::calculate_streak(conn)?;
+    let streak = StatsRepo::calculate_streak(conn, &config.streak, today)?;
 
     // Qada count
     let qada_count = QadaRepo::count_pending(conn)?;
 
     // Quran this week
     let week_start = today - chrono::Duration::days(6);
-    let week_start_str = week_start.format("%Y-%m-%d").to_string();
+    let week_start_str = date_str(week_start);
     let quran_weekly = QuranRepo::get_weekly_total(conn, &week_start_str, &today_str)?;
 
     println!();
     println_colored!(GOLD, "  Statistics");
+    if reset_view {
+        println_colored!(DIM, "  (view reset to the last 7 days, all prayers)");
+    }
     println!();
     println_colored!(
         BOLD,
@@ -335,59 +1708,213 @@ pub fn handle_stats(conn: &Connection, week: bool) -> Result<()> {
         streak.best
     );
 
+    let on_time = StatsRepo::on_time(
+        conn,
+        &date_str(today - chrono::Duration::days(29)),
+        &today_str,
+    )?;
+    if let Some(percent) = on_time.percent() {
+        println!(
+            "  On time (30d): {}%  ({} of {} marked on the day)",
+            percent, on_time.on_time, on_time.timed
+        );
+    }
+
     if qada_count == 0 {
         println_colored!(GREEN, "  Qada queue:  0 prayers ✓");
     } else {
         println_colored!(AMBER, "  Qada queue:  {} prayers", qada_count);
     }
+    let made_up = QadaRepo::completed_in_range(conn, &week_start_str, &today_str)?.len();
+    println!("  Qada made up this week: {}", made_up);
 
     println!(
         "  Quran (7d):  {} pages",
         format_pages(quran_weekly)
     );
 
-    if week {
-        println!();
-        println_colored!(DIM, "  Last 7 days  (● = 5/5, ◕ = 3-4, ◑ = 1-2, ○ = 0/5)");
-        println!();
-        print!("  ");
-        let daily = StatsRepo::get_weekly_grid(conn, &week_start_str, &today_str)?;
-        for stat in &daily {
-            let icon = match stat.prayers_done {
-                5 => format!("{}●\x1b[0m ", GREEN),
-                3 | 4 => format!("{}◕\x1b[0m ", AMBER),
-                1 | 2 => format!("{}◑\x1b[0m ", AMBER),
-                _ => format!("{}○\x1b[0m ", DIM),
-            };
-            print!("{}", icon);
+    // Heatmap over the remembered range
+    let start = match view.range {
+        StatsRange::Week => week_start,
+        StatsRange::Month => today.with_day(1).unwrap_or(today),
+    };
+    let daily =
+        StatsRepo::get_daily_stats_range(conn, &date_str(start), &today_str, view.prayer.as_ref())?;
+    let by_date: std::collections::HashMap<&str, &DailyStats> =
+        daily.iter().map(|s| (s.date.as_str(), s)).collect();
+
+    let heading = match view.range {
+        StatsRange::Week => "Last 7 days",
+        StatsRange::Month => "This month",
+    };
+    let legend = if view.prayer.is_some() {
+        "● = prayed, ○ = not, · = untracked"
+    } else {
+        "● = 5/5, ◕ = 3-4, ◑ = 1-2, ○ = 0/5"
+    };
+    println!();
+    println_colored!(DIM, "  {} · {}  ({})", heading, view.prayer_label(), legend);
+    if view.prayer.is_some() {
+        let done: u32 = daily.iter().map(|s| s.prayers_done as u32).sum();
+        println_colored!(DIM, "  Prayed on {} of {} tracked days", done, daily.len());
+    }
+    println!();
+    for (i, day) in start.iter_days().take_while(|d| *d <= today).enumerate() {
+        if i % 7 == 0 {
+            if i > 0 {
+                println!();
+            }
+            print!("  ");
         }
-        println!();
+        let icon = match by_date.get(date_str(day).as_str()) {
+            None => paint(DIM, "·"),
+            Some(stat) => match stat.level() {
+                5 => paint(GREEN, "●"),
+                3 | 4 => paint(AMBER, "◕"),
+                1 | 2 => paint(AMBER, "◑"),
+                _ => paint(DIM, "○"),
+            },
+        };
+        print!("{} ", icon);
+    }
+    println!();
+
+    println!();
+    Ok(())
+}
+
+/// `sujood stats --year`: the calendar year's totals, defaulting to this one.
+pub fn handle_year_review(conn: &Connection, config: &AppConfig, year: Option<i32>) -> Result<()> {
+    let year = year.unwrap_or_else(|| today_date(config).year());
+    let review = StatsRepo::year_review(conn, &config.streak, year)?;
+
+    println!();
+    println_colored!(GOLD, "  Year in review — {}", review.year);
+    println!();
+    match review.percent() {
+        Some(percent) => println_colored!(
+            BOLD,
+            "  Prayers:      {} of {} done ({}%)",
+            review.prayers_done(),
+            review.prayers_tracked(),
+            percent
+        ),
+        None => println_colored!(DIM, "  Prayers:      nothing tracked in {}", review.year),
     }
+    println!("  Best streak:  {} days", review.best_streak);
+    println!("  Quran:        {} pages", format_pages(review.quran_pages));
+
+    println!();
+    println_colored!(DIM, "  J F M A M J J A S O N D   (completion by month, · = untracked)");
+    let bars: Vec<String> = review.sparkline().chars().map(String::from).collect();
+    println_colored!(GREEN, "  {}", bars.join(" "));
 
+    if !review.dhikr.is_empty() {
+        println!();
+        println_colored!(DIM, "  Adhkar completed");
+        for (name, count) in &review.dhikr {
+            println!("    {:<28}  {}", name, count);
+        }
+    }
     println!();
     Ok(())
 }
 
 // ─── Export ──────────────────────────────────────────────────────────────────
 
-pub fn handle_export(conn: &Connection, config: &AppConfig) -> Result<()> {
-    let today = Local::now().date_naive();
-    let week_start = today - chrono::Duration::days(6);
-    let today_str = today.format("%Y-%m-%d").to_string();
-    let week_start_str = week_start.format("%Y-%m-%d").to_string();
+pub fn handle_export(
+    conn: &Connection,
+    config: &AppConfig,
+    format: ExportFormat,
+    days: Option<u32>,
+    out: Option<&Path>,
+) -> Result<()> {
+    let today = today_date(config);
+    let output = match format {
+        ExportFormat::Text => summary_text(conn, config, today, days.unwrap_or(7))?,
+        ExportFormat::Json => {
+            let data = export::collect(conn, config, today, days.unwrap_or(7))?;
+            export::to_json(&data)? + "\n"
+        }
+        ExportFormat::Csv => export::to_csv(conn, today, days.unwrap_or(30))?,
+        ExportFormat::Ics => export::to_ics(conn, config, today, days.unwrap_or(30))?,
+    };
+
+    match out {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Creating {}", parent.display()))?;
+            }
+            fs::write(path, output).with_context(|| format!("Writing {}", path.display()))?;
+            println_colored!(GREEN, "✓ Exported to {}", path.display());
+        }
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+pub fn handle_import(conn: &Connection, file: &Path, overwrite: bool) -> Result<()> {
+    let json = fs::read_to_string(file).with_context(|| format!("Reading {}", file.display()))?;
+    let data = import::parse(&json).with_context(|| format!("Importing {}", file.display()))?;
+    let summary = import::import(conn, &data, overwrite)?;
+
+    println_colored!(GREEN, "✓ Imported {} ({} to {})", file.display(), data.from, data.to);
+    let verb = if overwrite { "unchanged" } else { "already here, kept" };
+    for (label, tally) in [
+        ("prayers", summary.prayers),
+        ("adhkar", summary.dhikr),
+        ("Quran days", summary.quran),
+        ("qada", summary.qada),
+    ] {
+        print!("  {:<11}{} written", label, tally.written);
+        if tally.skipped > 0 {
+            print_colored!(DIM, "  · {} {}", tally.skipped, verb);
+        }
+        println!();
+    }
+    if !summary.new_dhikr.is_empty() {
+        println_colored!(DIM, "  Added custom adhkar: {}", summary.new_dhikr.join(", "));
+    }
+    if summary.orphan_dhikr_logs > 0 {
+        println_colored!(
+            AMBER,
+            "  Left out {} dhikr log{} for adhkar the file doesn't define",
+            summary.orphan_dhikr_logs,
+            if summary.orphan_dhikr_logs == 1 { "" } else { "s" }
+        );
+    }
+    Ok(())
+}
+
+/// The plain-text summary printed by `sujood export`.
+fn summary_text(
+    conn: &Connection,
+    config: &AppConfig,
+    today: chrono::NaiveDate,
+    days: u32,
+) -> Result<String> {
+    let start = today - chrono::Duration::days(days as i64 - 1);
+    let today_str = date_str(today);
+    let start_str = date_str(start);
 
-    let streak = StatsRepo::calculate_streak(conn)?;
+    let streak = StatsRepo::calculate_streak(conn, &config.streak, today)?;
     let qada_count = QadaRepo::count_pending(conn)?;
-    let quran_weekly = QuranRepo::get_weekly_total(conn, &week_start_str, &today_str)?;
-    let daily = StatsRepo::get_weekly_grid(conn, &week_start_str, &today_str)?;
+    let quran_total = QuranRepo::get_weekly_total(conn, &start_str, &today_str)?;
+    let daily = StatsRepo::get_weekly_grid(conn, &start_str, &today_str)?;
 
-    println!("# sujood — Weekly Summary");
-    println!("# {}", today_str);
-    println!();
-    println!("Location: {}", config.salah.location_name);
-    println!("Method:   {}", config.salah.calc_method);
-    println!();
-    println!("## Prayer Completion (last 7 days)");
+    let mut text = String::new();
+    if days == 7 {
+        writeln!(text, "# sujood — Weekly Summary")?;
+    } else {
+        writeln!(text, "# sujood — {}-Day Summary", days)?;
+    }
+    writeln!(text, "# {}", today_str)?;
+    writeln!(text)?;
+    writeln!(text, "Location: {}", config.salah.location_name)?;
+    writeln!(text, "Method:   {}", config.salah.calc_method)?;
+    writeln!(text)?;
+    writeln!(text, "## Prayer Completion (last {} days)", days)?;
     for stat in &daily {
         let bar = match stat.prayers_done {
             5 => "█████",
@@ -397,18 +1924,124 @@ pub fn handle_export(conn: &Connection, config: &AppConfig) -> Result<()> {
             1 => "█░░░░",
             _ => "░░░░░",
         };
-        println!("  {}  {}/5  {}", stat.date, stat.prayers_done, bar);
+        writeln!(text, "  {}  {}/5  {}", stat.date, stat.prayers_done, bar)?;
     }
-    println!();
-    println!("## Summary");
-    println!("  Streak:     {} days (best: {})", streak.current, streak.best);
-    println!("  Qada owed:  {}", qada_count);
-    println!("  Quran (7d): {} pages", format_pages(quran_weekly));
+    writeln!(text)?;
+    writeln!(text, "## Summary")?;
+    writeln!(text, "  Streak:     {} days (best: {})", streak.current, streak.best)?;
+    writeln!(text, "  Qada owed:  {}", qada_count)?;
+    writeln!(text, "  Quran ({}d): {} pages", days, format_pages(quran_total))?;
+    Ok(text)
+}
+
+// ─── Prune ───────────────────────────────────────────────────────────────────
+
+/// `sujood prune`: delete calculated prayer times dated before `before`,
+/// and with `include_history` every record before it too.
+pub fn handle_prune(
+    conn: &Connection,
+    config: &AppConfig,
+    before: &str,
+    include_history: bool,
+    vacuum: bool,
+) -> Result<()> {
+    let before = parse_date(before)?;
+    let today = today_date(config);
+    if before > today {
+        return Err(anyhow!("{} is in the future — pick today or an earlier day to keep", before));
+    }
+    let before = date_str(before);
+
+    let days = CacheRepo::prune_computed_before(conn, &before)?;
+    println_colored!(
+        GREEN,
+        "✓ Removed {} cached day{} of prayer times before {}",
+        days,
+        if days == 1 { "" } else { "s" },
+        before
+    );
+    if include_history {
+        let rows = HistoryRepo::prune_before(conn, &before)?;
+        println_colored!(
+            GREEN,
+            "✓ Removed {} history record{} before {} (qada still owed is kept)",
+            rows,
+            if rows == 1 { "" } else { "s" },
+            before
+        );
+    } else {
+        println_colored!(
+            DIM,
+            "  Prayers, adhkar and Quran logs were kept — --include-history removes them too"
+        );
+    }
+    if vacuum {
+        MetaRepo::vacuum(conn)?;
+        println_colored!(GREEN, "✓ Database compacted");
+    }
+    Ok(())
+}
+
+// ─── Man page ────────────────────────────────────────────────────────────────
+
+/// Print the roff man page for sujood, or for the subcommand named by
+/// `command` (e.g. `["qada", "add"]`). With `dir`, every page is written
+/// there instead, one file per command.
+pub fn handle_man(command: &[String], dir: Option<&Path>) -> Result<()> {
+    let mut cmd = Cli::command().disable_help_subcommand(true);
+    // Gives subcommands their `sujood-times` page names
+    cmd.build();
+
+    if let Some(dir) = dir {
+        fs::create_dir_all(dir).with_context(|| format!("Creating {}", dir.display()))?;
+        let written = write_man_pages(&cmd, dir)
+            .with_context(|| format!("Writing man pages to {}", dir.display()))?;
+        println_colored!(GREEN, "  ✓ {} man pages written to {}", written, dir.display());
+        return Ok(());
+    }
+
+    let mut page = &cmd;
+    for name in command {
+        page = page.find_subcommand(name).ok_or_else(|| {
+            anyhow!("No command `{}` — `sujood man` lists them all", command.join(" "))
+        })?;
+    }
+    man_page(page).render(&mut io::stdout())?;
     Ok(())
 }
 
+/// A command's page, footed with sujood's version (subcommands have none of their own).
+fn man_page(cmd: &clap::Command) -> clap_mangen::Man {
+    clap_mangen::Man::new(cmd.clone()).source(format!("sujood {}", env!("CARGO_PKG_VERSION")))
+}
+
+/// Write the pages for `cmd` and every command under it, returning how many.
+fn write_man_pages(cmd: &clap::Command, dir: &Path) -> io::Result<usize> {
+    let mut written = 1;
+    for sub in cmd.get_subcommands().filter(|s| !s.is_hide_set()) {
+        written += write_man_pages(sub, dir)?;
+    }
+    man_page(cmd).generate_to(dir)?;
+    Ok(written)
+}
+
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
+/// Parse a `YYYY-MM-DD` argument.
+fn parse_date(s: &str) -> Result<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(s.trim(), DATE_FMT)
+        .map_err(|_| anyhow!("Invalid date '{}'. Use YYYY-MM-DD", s))
+}
+
+/// Parse a `YYYY-MM-DD` argument, rejecting days after `today`.
+fn parse_past_date(s: &str, today: chrono::NaiveDate) -> Result<chrono::NaiveDate> {
+    let date = parse_date(s)?;
+    if date > today {
+        return Err(anyhow!("{} is in the future — only today or earlier can be marked", date));
+    }
+    Ok(date)
+}
+
 fn prompt(message: &str) -> Result<String> {
     print!("{}", message);
     io::stdout().flush()?;
@@ -453,3 +2086,95 @@ fn format_tz_offset(minutes: i32) -> String {
         format!("{}{}:{:02}", sign, h, m)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::run_migrations;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn list_shows_each_prayers_symbol_per_day() {
+        let conn = test_db();
+        PrayerRepo::ensure_rows_for_date(&conn, "2026-10-14").unwrap();
+        for (prayer, status) in [("fajr", "done"), ("zuhr", "missed"), ("isha", "done")] {
+            PrayerRepo::mark_status(&conn, prayer, "2026-10-14", status, None).unwrap();
+        }
+        PrayerRepo::ensure_rows_for_date(&conn, "2026-10-16").unwrap();
+        PrayerRepo::mark_status(&conn, "maghrib", "2026-10-16", "done", None).unwrap();
+
+        let from = parse_date("2026-10-14").unwrap();
+        let to = parse_date("2026-10-16").unwrap();
+        let rows: Vec<(String, String)> = list_grid(&conn, from, to)
+            .unwrap()
+            .into_iter()
+            .map(|(day, statuses)| {
+                let symbols = statuses.iter().map(|s| list_symbol(s.as_ref()).1).collect();
+                (date_str(day), symbols)
+            })
+            .collect();
+
+        assert_eq!(
+            rows,
+            [
+                ("2026-10-14".to_string(), "✓✗••✓".to_string()),
+                ("2026-10-15".to_string(), "–––––".to_string()),
+                ("2026-10-16".to_string(), "•••✓•".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn marking_a_missed_prayer_done_takes_it_off_the_qada_queue() {
+        let conn = test_db();
+        let config = AppConfig::default();
+        let day = date_str(today_date(&config) - chrono::Duration::days(2));
+
+        handle_mark(&conn, &config, "asr", true, Some(&day), None).unwrap();
+        assert_eq!(QadaRepo::count_pending(&conn).unwrap(), 1);
+
+        handle_mark(&conn, &config, "asr", false, Some(&day), None).unwrap();
+        assert_eq!(QadaRepo::count_pending(&conn).unwrap(), 0);
+        let asr = PrayerRepo::get_by_date(&conn, &day)
+            .unwrap()
+            .into_iter()
+            .find(|p| p.prayer_type == PrayerType::Asr)
+            .unwrap();
+        assert_eq!(asr.status, PrayerStatus::Done);
+    }
+
+    #[test]
+    fn summary_is_a_single_line_glance() {
+        let conn = test_db();
+        let config = AppConfig::default();
+        let now = parse_date("2026-10-16").unwrap().and_hms_opt(10, 0, 0).unwrap();
+        PrayerRepo::ensure_rows_for_date(&conn, "2026-10-16").unwrap();
+        PrayerRepo::mark_status(&conn, "fajr", "2026-10-16", "done", None).unwrap();
+
+        let line = summary_line(&conn, &config, now).unwrap();
+        assert!(!line.contains('\n'), "{line}");
+        assert!(line.starts_with("Next: "), "{line}");
+        assert!(line.ends_with("4 left today: Zuhr, Asr, Maghrib, Isha"), "{line}");
+
+        for prayer in ["zuhr", "asr", "maghrib", "isha"] {
+            PrayerRepo::mark_status(&conn, prayer, "2026-10-16", "done", None).unwrap();
+        }
+        let line = summary_line(&conn, &config, now).unwrap();
+        assert!(line.ends_with("all prayers done today"), "{line}");
+    }
+
+    #[test]
+    fn summary_flag_parses_without_a_subcommand() {
+        use crate::cli::args::Cli;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(["sujood", "--summary"]).unwrap();
+        assert!(cli.summary);
+        assert!(cli.command.is_none());
+    }
+}
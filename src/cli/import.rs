@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::cli::export::{ExportData, EXPORT_VERSION};
+use crate::db::repository::DhikrRepo;
+use crate::models::PrayerStatus;
+use crate::utils::date::{DATETIME_FMT, DATE_FMT};
+
+/// Rows written and rows left alone for one kind of record.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Tally {
+    pub written: usize,
+    pub skipped: usize,
+}
+
+impl Tally {
+    fn count(&mut self, changed: usize) {
+        if changed > 0 {
+            self.written += 1;
+        } else {
+            self.skipped += 1;
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub prayers: Tally,
+    pub dhikr: Tally,
+    pub quran: Tally,
+    pub qada: Tally,
+    /// Custom adhkar created because the file logs them and this database lacks them
+    pub new_dhikr: Vec<String>,
+    /// Dhikr logs left out because the file has no definition for them
+    pub orphan_dhikr_logs: usize,
+}
+
+/// Parse and check a `sujood export --format json` file.
+pub fn parse(json: &str) -> Result<ExportData> {
+    let value: serde_json::Value = serde_json::from_str(json).context("Not a JSON file")?;
+    // Checked before the rest, since a newer export may not fit ExportData at all
+    let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(1);
+    if version > EXPORT_VERSION as u64 {
+        bail!(
+            "This export is version {}, but this sujood reads up to version {}. Update sujood and try again",
+            version,
+            EXPORT_VERSION
+        );
+    }
+    let data: ExportData = serde_json::from_value(value).context("Not a sujood JSON export")?;
+    for day in &data.days {
+        check_date(&day.date)?;
+    }
+    for entry in &data.qada_queue {
+        check_date(&entry.original_date)?;
+    }
+    Ok(data)
+}
+
+fn check_date(date: &str) -> Result<()> {
+    NaiveDate::parse_from_str(date, DATE_FMT)
+        .map(|_| ())
+        .map_err(|_| anyhow!("Invalid date in export: {:?} (expected YYYY-MM-DD)", date))
+}
+
+/// Write an export into the database in one transaction. Records already
+/// here are kept unless `overwrite`; a prayer still pending counts as not
+/// yet recorded, so it is filled in either way. Running the same import
+/// twice changes nothing the second time.
+pub fn import(conn: &Connection, data: &ExportData, overwrite: bool) -> Result<ImportSummary> {
+    let tx = conn.unchecked_transaction()?;
+    let mut summary = ImportSummary::default();
+
+    for day in &data.days {
+        for p in &day.prayers {
+            let marked_at = p.marked_at.map(|t| t.format(DATETIME_FMT).to_string());
+            let changed = tx.execute(
+                "INSERT INTO prayers (prayer_type, date, status, is_qada, note, marked_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?7)
+                 ON CONFLICT(prayer_type, date, is_qada) DO UPDATE
+                 SET status = excluded.status, note = excluded.note, marked_at = excluded.marked_at
+                 WHERE (?6 OR status = 'pending')
+                   AND (status != excluded.status OR note IS NOT excluded.note
+                        OR marked_at IS NOT excluded.marked_at)",
+                params![
+                    p.prayer_type.as_str(),
+                    day.date,
+                    p.status.as_str(),
+                    p.is_qada,
+                    p.note,
+                    overwrite,
+                    marked_at
+                ],
+            )?;
+            // Pending rows carry nothing worth restoring
+            if p.status != PrayerStatus::Pending || changed > 0 {
+                summary.prayers.count(changed);
+            }
+        }
+    }
+
+    // Logs refer to adhkar by id, which differs between databases; match by name.
+    // A new one comes in deactivated if it was exported that way; an existing
+    // one only takes the file's `active` with `overwrite`.
+    let mut dhikr_ids: HashMap<i64, i64> = HashMap::new();
+    for def in &data.dhikr_definitions {
+        let local = match find_dhikr_id(&tx, &def.name)? {
+            Some(id) => {
+                if overwrite {
+                    tx.execute(
+                        "UPDATE dhikr_definitions SET active = ?2 WHERE id = ?1",
+                        params![id, def.active],
+                    )?;
+                }
+                id
+            }
+            None => {
+                DhikrRepo::add_custom(
+                    &tx,
+                    &def.name,
+                    def.dhikr_type.as_str(),
+                    def.target_count,
+                    def.frequency.as_str(),
+                    def.tag.as_deref(),
+                )?;
+                let id = tx.last_insert_rowid();
+                if !def.active {
+                    DhikrRepo::deactivate(&tx, id)?;
+                }
+                summary.new_dhikr.push(def.name.clone());
+                id
+            }
+        };
+        dhikr_ids.insert(def.id, local);
+    }
+
+    for day in &data.days {
+        for log in &day.dhikr {
+            check_date(&log.date)?;
+            // Older exports could log a dhikr without defining it; keep the rest
+            let Some(&dhikr_id) = dhikr_ids.get(&log.dhikr_id) else {
+                log::warn!("dhikr log on {} refers to unknown dhikr id {}", log.date, log.dhikr_id);
+                summary.orphan_dhikr_logs += 1;
+                continue;
+            };
+            let changed = tx.execute(
+                "INSERT INTO dhikr_log (dhikr_id, date, count, completed)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(dhikr_id, date) DO UPDATE
+                 SET count = excluded.count, completed = excluded.completed
+                 WHERE ?5 AND (count != excluded.count OR completed != excluded.completed)",
+                params![dhikr_id, log.date, log.count, log.completed, overwrite],
+            )?;
+            summary.dhikr.count(changed);
+        }
+
+        if day.quran_pages > 0.0 {
+            let changed = tx.execute(
+                "INSERT INTO quran_log (date, pages) VALUES (?1, ?2)
+                 ON CONFLICT(date) DO UPDATE SET pages = excluded.pages
+                 WHERE ?3 AND pages != excluded.pages",
+                params![day.date, day.quran_pages, overwrite],
+            )?;
+            summary.quran.count(changed);
+        }
+    }
+
+    for entry in &data.qada_queue {
+        let existing: Option<i64> = tx
+            .query_row(
+                "SELECT id FROM qada_queue WHERE prayer_type = ?1 AND original_date = ?2
+                 ORDER BY completed LIMIT 1",
+                params![entry.prayer_type.as_str(), entry.original_date],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let changed = match existing {
+            None => tx.execute(
+                "INSERT INTO qada_queue (prayer_type, original_date, completed, completed_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    entry.prayer_type.as_str(),
+                    entry.original_date,
+                    entry.completed,
+                    entry.completed_at
+                ],
+            )?,
+            Some(id) if overwrite => tx.execute(
+                "UPDATE qada_queue SET completed = ?2, completed_at = ?3
+                 WHERE id = ?1 AND (completed != ?2 OR completed_at IS NOT ?3)",
+                params![id, entry.completed, entry.completed_at],
+            )?,
+            Some(_) => 0,
+        };
+        summary.qada.count(changed);
+    }
+
+    tx.commit()?;
+    Ok(summary)
+}
+
+/// Any dhikr with this name, including deactivated ones.
+fn find_dhikr_id(conn: &Connection, name: &str) -> Result<Option<i64>> {
+    Ok(conn
+        .query_row(
+            "SELECT id FROM dhikr_definitions WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::export::{collect, to_json};
+    use crate::config::AppConfig;
+    use crate::db::migrations::run_migrations;
+    use crate::db::repository::PrayerRepo;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn deactivated_dhikr_survives_a_round_trip() {
+        let source = test_db();
+        let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        DhikrRepo::add_custom(&source, "Travel dua", "checkbox", 1, "daily", None).unwrap();
+        let id = source.last_insert_rowid();
+        DhikrRepo::upsert_log(&source, id, "2026-10-16", 1, true).unwrap();
+        DhikrRepo::deactivate(&source, id).unwrap();
+        PrayerRepo::ensure_rows_for_date(&source, "2026-10-16").unwrap();
+        let marked = day.and_hms_opt(5, 40, 0).unwrap();
+        PrayerRepo::mark_status(&source, "fajr", "2026-10-16", "done", Some(marked)).unwrap();
+
+        let json = to_json(&collect(&source, &AppConfig::default(), day, 1).unwrap()).unwrap();
+        let target = test_db();
+        let summary = import(&target, &parse(&json).unwrap(), false).unwrap();
+        assert_eq!(summary.new_dhikr, ["Travel dua"]);
+        assert_eq!((summary.dhikr.written, summary.orphan_dhikr_logs), (1, 0));
+
+        let id = find_dhikr_id(&target, "Travel dua").unwrap().unwrap();
+        let active: bool = target
+            .query_row("SELECT active FROM dhikr_definitions WHERE id = ?1", [id], |r| r.get(0))
+            .unwrap();
+        assert!(!active);
+        let logs = DhikrRepo::get_log_for_date(&target, "2026-10-16").unwrap();
+        assert!(logs.iter().any(|l| l.dhikr_id == id && l.completed));
+        let fajr = &PrayerRepo::get_by_date(&target, "2026-10-16").unwrap()[0];
+        assert_eq!(fajr.marked_at, Some(marked));
+    }
+
+    #[test]
+    fn a_log_without_its_definition_is_left_out() {
+        let source = test_db();
+        let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        DhikrRepo::add_custom(&source, "Travel dua", "checkbox", 1, "daily", None).unwrap();
+        DhikrRepo::upsert_log(&source, source.last_insert_rowid(), "2026-10-16", 1, true).unwrap();
+        let mut data = collect(&source, &AppConfig::default(), day, 1).unwrap();
+        data.dhikr_definitions.retain(|d| d.name != "Travel dua");
+
+        let target = test_db();
+        let summary = import(&target, &data, false).unwrap();
+        assert_eq!(summary.orphan_dhikr_logs, 1);
+        assert!(find_dhikr_id(&target, "Travel dua").unwrap().is_none());
+    }
+}
@@ -1,3 +1,9 @@
 pub mod args;
+pub mod catchup;
+pub mod daemon;
+pub mod export;
+pub mod geolocate;
 pub mod handlers;
+pub mod import;
+pub mod serve;
 pub mod setup_tui;
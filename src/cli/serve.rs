@@ -0,0 +1,214 @@
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::config::AppConfig;
+
+/// Paths `sujood serve` answers, listed in its 404 replies.
+#[cfg(feature = "serve")]
+const ENDPOINTS: [&str; 4] = ["/times", "/next", "/stats", "/today"];
+
+/// Answer read-only JSON requests on `bind:port` until Ctrl-C, one at a
+/// time. Only compiled in with the `serve` feature.
+#[cfg(feature = "serve")]
+pub fn run(conn: &Connection, config: &AppConfig, bind: &str, port: u16) -> Result<()> {
+    use anyhow::Context;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    let listener = TcpListener::bind((bind, port))
+        .with_context(|| format!("Listening on {}:{}", bind, port))?;
+    println!("Serving {} on http://{}:{} — Ctrl-C stops it", ENDPOINTS.join(" "), bind, port);
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::warn!("accepting a connection: {}", e);
+                continue;
+            }
+        };
+        let _ = stream.set_read_timeout(Some(TIMEOUT));
+        let _ = stream.set_write_timeout(Some(TIMEOUT));
+
+        // Only the request line matters, but the headers are read too so the
+        // reply isn't cut short by closing on unread input
+        let mut reader = BufReader::new(&stream);
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).is_err() {
+            continue;
+        }
+        let mut header = String::new();
+        while reader.read_line(&mut header).is_ok_and(|n| n > 0) && header.trim() != "" {
+            header.clear();
+        }
+
+        let (status, body) = respond(conn, config, &request_line);
+        log::info!("{} → {}", request_line.trim(), status);
+        let reply = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json; charset=utf-8\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        if let Err(e) = stream.write_all(reply.as_bytes()) {
+            log::warn!("replying to {}: {}", request_line.trim(), e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "serve"))]
+pub fn run(_conn: &Connection, _config: &AppConfig, _bind: &str, _port: u16) -> Result<()> {
+    Err(anyhow::anyhow!(
+        "this build has no HTTP server — reinstall with `--features serve`"
+    ))
+}
+
+/// The status line and JSON body for one request line, e.g. `GET /next HTTP/1.1`.
+#[cfg(feature = "serve")]
+fn respond(conn: &Connection, config: &AppConfig, request_line: &str) -> (&'static str, String) {
+    use serde_json::json;
+
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return ("400 Bad Request", json!({ "error": "malformed request" }).to_string());
+    };
+    if method != "GET" {
+        let error = format!("{} not allowed — every endpoint is read-only GET", method);
+        return ("405 Method Not Allowed", json!({ "error": error }).to_string());
+    }
+
+    let path = target.split('?').next().unwrap_or(target);
+    let value = match path {
+        "/times" => times(conn, config),
+        "/next" => crate::cli::handlers::next_prayer_json(conn, config),
+        "/stats" => stats(conn, config),
+        "/today" => today(conn, config),
+        _ => {
+            let body = json!({ "error": format!("no endpoint {}", path), "endpoints": ENDPOINTS });
+            return ("404 Not Found", body.to_string());
+        }
+    };
+    match value {
+        Ok(value) => ("200 OK", value.to_string()),
+        Err(e) => {
+            let body = json!({ "error": format!("{:#}", e) });
+            ("500 Internal Server Error", body.to_string())
+        }
+    }
+}
+
+/// Today's prayer times by the calendar day, as HH:MM.
+#[cfg(feature = "serve")]
+fn times(conn: &Connection, config: &AppConfig) -> Result<serde_json::Value> {
+    use crate::prayer_times::PrayerCalculator;
+    use crate::utils::date::{date_str, now_local};
+
+    let day = now_local(config).date();
+    let times = PrayerCalculator::from_config(&config.salah)?.get_cached_or_compute(conn, day)?;
+    let hm = |t: chrono::NaiveTime| t.format("%H:%M").to_string();
+    Ok(serde_json::json!({
+        "date": date_str(day),
+        "location": config.salah.location_name,
+        "imsak": hm(times.imsak),
+        "fajr": hm(times.fajr),
+        "sunrise": hm(times.sunrise),
+        "zuhr": hm(times.zuhr),
+        "asr": hm(times.asr),
+        "maghrib": hm(times.maghrib),
+        "isha": hm(times.isha),
+    }))
+}
+
+/// The figures `sujood stats` leads with.
+#[cfg(feature = "serve")]
+fn stats(conn: &Connection, config: &AppConfig) -> Result<serde_json::Value> {
+    use crate::db::repository::{QadaRepo, QuranRepo, StatsRepo};
+    use crate::models::StatsRange;
+    use crate::utils::date::{date_str, today_date};
+
+    let today = today_date(config);
+    let today_str = date_str(today);
+    let month_ago = date_str(today - chrono::Duration::days(29));
+    let week_ago = date_str(today - chrono::Duration::days(6));
+
+    let goal = |range, target: Option<u32>| -> Result<serde_json::Value> {
+        Ok(match target {
+            Some(target) => {
+                let goal = StatsRepo::goal(conn, range, target, today)?;
+                serde_json::json!({ "done": goal.done, "target": goal.target, "met": goal.met() })
+            }
+            None => serde_json::Value::Null,
+        })
+    };
+
+    Ok(serde_json::json!({
+        "streak": StatsRepo::calculate_streak(conn, &config.streak, today)?,
+        "on_time_percent": StatsRepo::on_time(conn, &month_ago, &today_str)?.percent(),
+        "qada_owed": QadaRepo::count_pending(conn)?,
+        "quran_pages_week": QuranRepo::get_weekly_total(conn, &week_ago, &today_str)?,
+        "weekly_goal": goal(StatsRange::Week, config.goals.weekly_prayers)?,
+        "monthly_goal": goal(StatsRange::Month, config.goals.monthly_prayers)?,
+    }))
+}
+
+/// The tracking day's prayers with their status, what's left, and Quran read.
+/// Nothing is written: a prayer without a row yet is reported as pending.
+#[cfg(feature = "serve")]
+fn today(conn: &Connection, config: &AppConfig) -> Result<serde_json::Value> {
+    use crate::db::repository::{PrayerRepo, QuranRepo};
+    use crate::models::{PrayerStatus, PrayerType};
+    use crate::utils::date::{date_str, today_date};
+
+    let today = date_str(today_date(config));
+    let rows = PrayerRepo::get_by_date(conn, &today)?;
+    let statuses: Vec<(PrayerType, PrayerStatus)> = PrayerType::all()
+        .into_iter()
+        .map(|prayer| {
+            let status = rows
+                .iter()
+                .find(|p| p.prayer_type == prayer)
+                .map_or(PrayerStatus::Pending, |p| p.status.clone());
+            (prayer, status)
+        })
+        .collect();
+    let remaining: Vec<&str> = statuses
+        .iter()
+        .filter(|(_, status)| *status == PrayerStatus::Pending)
+        .map(|(prayer, _)| prayer.as_str())
+        .collect();
+    let prayers: Vec<serde_json::Value> = statuses
+        .iter()
+        .map(|(prayer, status)| {
+            serde_json::json!({ "prayer": prayer.as_str(), "status": status.as_str() })
+        })
+        .collect();
+    Ok(serde_json::json!({
+        "date": today,
+        "prayers": prayers,
+        "remaining": remaining,
+        "quran_pages": QuranRepo::get_today(conn, &today)?,
+    }))
+}
+
+#[cfg(all(test, feature = "serve"))]
+mod tests {
+    use super::*;
+    use crate::db::migrations::run_migrations;
+
+    #[test]
+    fn today_reports_an_untracked_day_as_pending_without_writing() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let json = today(&conn, &AppConfig::default()).unwrap();
+        assert_eq!(json["remaining"].as_array().unwrap().len(), 5);
+        assert!(json["prayers"].as_array().unwrap().iter().all(|p| p["status"] == "pending"));
+        let rows: i64 = conn.query_row("SELECT COUNT(*) FROM prayers", [], |r| r.get(0)).unwrap();
+        assert_eq!(rows, 0);
+    }
+}
@@ -9,11 +9,20 @@ use ratatui::{
 };
 use rusqlite::Connection;
 
-use crate::config::AppConfig;
-use crate::db::repository::CacheRepo;
-use crate::prayer_times::calculator::{PrayerCalculator, CALC_METHODS};
+use crate::config::{AppConfig, PrayerAdjustments};
+use crate::prayer_times::calculator::{
+    method_fajr_angle, suggested_method_for_country, PrayerCalculator, CALC_METHODS,
+    HIGH_LATITUDE_RULES, LATER_FAJR_ANGLE,
+};
 use crate::tui::theme;
 use crate::tui::events::{Event, EventHandler};
+use crate::tui::terminal;
+use crate::utils::cities::{self, City};
+use crate::utils::date::now_local;
+use crate::utils::format::{format_coord, round_decimals};
+
+/// `hijri_offset` for each choice on the Hijri date step, in display order.
+const HIJRI_OFFSETS: [i32; 3] = [0, -1, 1];
 
 // ─── Wizard steps ────────────────────────────────────────────────────────────
 
@@ -21,12 +30,20 @@ use crate::tui::events::{Event, EventHandler};
 enum Step {
     Welcome,
     LocationName,
+    CityMatch,
+    Country,
     Latitude,
     Longitude,
+    Elevation,
     CalcMethod,
+    FajrTiming,
+    FajrAngle,
+    IshaAngle,
     Madhab,
+    HighLatitude,
     TimezoneOffset,
     HijriOffset,
+    Adjustments,
     Confirm,
 }
 
@@ -37,15 +54,27 @@ struct SetupWizard {
     input: String,
     error: Option<String>,
     list_state: ListState,
+    // Bundled cities matching the typed name; the list ends with a manual entry option
+    city_matches: Vec<&'static City>,
+    city_state: ListState,
 
     // Collected values
     location_name: String,
+    country: Option<String>, // ISO code; pre-selects the usual method
     latitude: f64,
     longitude: f64,
+    coord_precision: usize, // decimals kept for latitude/longitude
+    elevation_m: f64,
     method_idx: usize,
+    // Left empty until entered — "Other" has no sensible default
+    fajr_angle: Option<f64>,
+    isha_angle: Option<f64>,
+    later_fajr_idx: usize, // 0 = method default, 1 = later Fajr
     madhab_idx: usize, // 0 = Hanafi, 1 = Shafi
+    high_latitude_idx: usize, // index into HIGH_LATITUDE_RULES
     tz_minutes: i32,
-    hijri_idx: usize, // 0 = 0 days, 1 = -1 day
+    hijri_idx: usize, // index into HIJRI_OFFSETS: 0 = 0 days, 1 = -1 day, 2 = +1 day
+    adjustments: PrayerAdjustments,
 
     should_quit: bool,
     confirmed: bool,
@@ -58,7 +87,15 @@ impl SetupWizard {
             .position(|m| *m == existing.salah.calc_method)
             .unwrap_or(0);
         let madhab_idx = if existing.salah.madhab == "Shafi" { 1 } else { 0 };
-        let hijri_idx = if existing.salah.hijri_offset < 0 { 1 } else { 0 };
+        let hijri_idx = match existing.salah.hijri_offset.signum() {
+            -1 => 1,
+            1 => 2,
+            _ => 0,
+        };
+        let high_latitude_idx = HIGH_LATITUDE_RULES
+            .iter()
+            .position(|r| *r == existing.salah.high_latitude_rule)
+            .unwrap_or(0);
 
         let mut list_state = ListState::default();
         list_state.select(Some(method_idx));
@@ -68,14 +105,24 @@ impl SetupWizard {
             input: String::new(),
             error: None,
             list_state,
+            city_matches: Vec::new(),
+            city_state: ListState::default(),
 
             location_name: existing.salah.location_name.clone(),
-            latitude: existing.salah.latitude,
-            longitude: existing.salah.longitude,
+            country: existing.salah.country.clone(),
+            latitude: round_decimals(existing.salah.latitude, existing.salah.coord_precision),
+            longitude: round_decimals(existing.salah.longitude, existing.salah.coord_precision),
+            coord_precision: existing.salah.coord_precision,
+            elevation_m: existing.salah.elevation_m,
             method_idx,
+            fajr_angle: existing.salah.fajr_angle,
+            isha_angle: existing.salah.isha_angle,
+            later_fajr_idx: existing.salah.later_fajr as usize,
             madhab_idx,
+            high_latitude_idx,
             tz_minutes: existing.salah.timezone_offset,
             hijri_idx,
+            adjustments: existing.salah.adjustments.clone(),
 
             should_quit: false,
             confirmed: false,
@@ -85,30 +132,47 @@ impl SetupWizard {
     fn step_number(&self) -> usize {
         match self.step {
             Step::Welcome => 0,
-            Step::LocationName => 1,
-            Step::Latitude => 2,
-            Step::Longitude => 3,
-            Step::CalcMethod => 4,
-            Step::Madhab => 5,
-            Step::TimezoneOffset => 6,
-            Step::HijriOffset => 7,
-            Step::Confirm => 8,
+            Step::LocationName | Step::CityMatch => 1,
+            Step::Country => 2,
+            Step::Latitude => 3,
+            Step::Longitude => 4,
+            Step::Elevation => 5,
+            // Fajr sub-steps depend on the method, so they share its dot
+            Step::CalcMethod | Step::FajrTiming | Step::FajrAngle | Step::IshaAngle => 6,
+            Step::Madhab => 7,
+            Step::HighLatitude => 8,
+            Step::TimezoneOffset => 9,
+            Step::HijriOffset => 10,
+            Step::Adjustments => 11,
+            Step::Confirm => 12,
         }
     }
 
-    const TOTAL_STEPS: usize = 8;
+    const TOTAL_STEPS: usize = 12;
 
     fn advance(&mut self) {
         self.error = None;
         self.step = match self.step {
             Step::Welcome => Step::LocationName,
-            Step::LocationName => Step::Latitude,
+            Step::LocationName if !self.city_matches.is_empty() => Step::CityMatch,
+            Step::LocationName => Step::Country,
+            // A picked city already filled in the country and coordinates
+            Step::CityMatch if self.city_idx() < self.city_matches.len() => Step::Elevation,
+            Step::CityMatch => Step::Country,
+            Step::Country => Step::Latitude,
             Step::Latitude => Step::Longitude,
-            Step::Longitude => Step::CalcMethod,
-            Step::CalcMethod => Step::Madhab,
-            Step::Madhab => Step::TimezoneOffset,
+            Step::Longitude => Step::Elevation,
+            Step::Elevation => Step::CalcMethod,
+            Step::CalcMethod if self.uses_custom_angles() => Step::FajrAngle,
+            Step::CalcMethod => Step::FajrTiming,
+            Step::FajrTiming => Step::Madhab,
+            Step::FajrAngle => Step::IshaAngle,
+            Step::IshaAngle => Step::Madhab,
+            Step::Madhab => Step::HighLatitude,
+            Step::HighLatitude => Step::TimezoneOffset,
             Step::TimezoneOffset => Step::HijriOffset,
-            Step::HijriOffset => Step::Confirm,
+            Step::HijriOffset => Step::Adjustments,
+            Step::Adjustments => Step::Confirm,
             Step::Confirm => {
                 self.confirmed = true;
                 Step::Confirm
@@ -117,9 +181,14 @@ impl SetupWizard {
         // Pre-fill input with current value when entering a text step
         self.input = match self.step {
             Step::LocationName => self.location_name.clone(),
-            Step::Latitude => format!("{}", self.latitude),
-            Step::Longitude => format!("{}", self.longitude),
+            Step::Country => self.country.clone().unwrap_or_default(),
+            Step::Latitude => format_coord(self.latitude, self.coord_precision),
+            Step::Longitude => format_coord(self.longitude, self.coord_precision),
+            Step::Elevation => self.elevation_m.to_string(),
+            Step::FajrAngle => self.fajr_angle.map(|a| a.to_string()).unwrap_or_default(),
+            Step::IshaAngle => self.isha_angle.map(|a| a.to_string()).unwrap_or_default(),
             Step::TimezoneOffset => format_tz(self.tz_minutes),
+            Step::Adjustments => format_adjustments(&self.adjustments),
             _ => String::new(),
         };
     }
@@ -132,19 +201,36 @@ impl SetupWizard {
                 Step::Welcome
             }
             Step::LocationName => Step::Welcome,
-            Step::Latitude => Step::LocationName,
+            Step::CityMatch => Step::LocationName,
+            Step::Country if !self.city_matches.is_empty() => Step::CityMatch,
+            Step::Country => Step::LocationName,
+            Step::Latitude => Step::Country,
             Step::Longitude => Step::Latitude,
-            Step::CalcMethod => Step::Longitude,
-            Step::Madhab => Step::CalcMethod,
-            Step::TimezoneOffset => Step::Madhab,
+            // A picked city skipped the coordinate steps
+            Step::Elevation if self.city_idx() < self.city_matches.len() => Step::CityMatch,
+            Step::Elevation => Step::Longitude,
+            Step::CalcMethod => Step::Elevation,
+            Step::FajrTiming => Step::CalcMethod,
+            Step::FajrAngle => Step::CalcMethod,
+            Step::IshaAngle => Step::FajrAngle,
+            Step::Madhab if self.uses_custom_angles() => Step::IshaAngle,
+            Step::Madhab => Step::FajrTiming,
+            Step::HighLatitude => Step::Madhab,
+            Step::TimezoneOffset => Step::HighLatitude,
             Step::HijriOffset => Step::TimezoneOffset,
-            Step::Confirm => Step::HijriOffset,
+            Step::Adjustments => Step::HijriOffset,
+            Step::Confirm => Step::Adjustments,
         };
         self.input = match self.step {
             Step::LocationName => self.location_name.clone(),
-            Step::Latitude => format!("{}", self.latitude),
-            Step::Longitude => format!("{}", self.longitude),
+            Step::Country => self.country.clone().unwrap_or_default(),
+            Step::Latitude => format_coord(self.latitude, self.coord_precision),
+            Step::Longitude => format_coord(self.longitude, self.coord_precision),
+            Step::Elevation => self.elevation_m.to_string(),
+            Step::FajrAngle => self.fajr_angle.map(|a| a.to_string()).unwrap_or_default(),
+            Step::IshaAngle => self.isha_angle.map(|a| a.to_string()).unwrap_or_default(),
             Step::TimezoneOffset => format_tz(self.tz_minutes),
+            Step::Adjustments => format_adjustments(&self.adjustments),
             _ => String::new(),
         };
     }
@@ -171,6 +257,14 @@ impl SetupWizard {
                 }
             }),
 
+            Step::Country => self.handle_text_input(key, |s| {
+                if s.is_empty() || (s.len() == 2 && s.chars().all(|c| c.is_ascii_alphabetic())) {
+                    Ok(())
+                } else {
+                    Err("Enter a two-letter country code (e.g. PK), or leave empty".to_string())
+                }
+            }),
+
             Step::Latitude => self.handle_text_input(key, |s| {
                 s.parse::<f64>()
                     .map_err(|_| "Enter a valid latitude (e.g. 19.0748)".to_string())
@@ -195,6 +289,37 @@ impl SetupWizard {
                     })
             }),
 
+            Step::Elevation => self.handle_text_input(key, |s| {
+                s.parse::<f64>()
+                    .map_err(|_| "Enter a height in metres (e.g. 350)".to_string())
+                    .and_then(|v| {
+                        if !(0.0..=9000.0).contains(&v) {
+                            Err("Elevation must be between 0 and 9000 metres".to_string())
+                        } else {
+                            Ok(())
+                        }
+                    })
+            }),
+
+            Step::CityMatch => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    let idx = self.city_idx().saturating_sub(1);
+                    self.city_state.select(Some(idx));
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    let idx = (self.city_idx() + 1).min(self.city_matches.len());
+                    self.city_state.select(Some(idx));
+                }
+                KeyCode::Enter => {
+                    if let Some(city) = self.city_matches.get(self.city_idx()).copied() {
+                        self.pick_city(city);
+                    }
+                    self.advance();
+                }
+                KeyCode::Esc => self.go_back(),
+                _ => {}
+            },
+
             Step::CalcMethod => match key.code {
                 KeyCode::Up | KeyCode::Char('k') => {
                     if self.method_idx > 0 {
@@ -213,6 +338,20 @@ impl SetupWizard {
                 _ => {}
             },
 
+            Step::FajrAngle | Step::IshaAngle => self.handle_text_input(key, validate_angle),
+
+            Step::FajrTiming => match key.code {
+                KeyCode::Left | KeyCode::Char('1') | KeyCode::Char('h') => {
+                    self.later_fajr_idx = 0;
+                }
+                KeyCode::Right | KeyCode::Char('2') | KeyCode::Char('l') => {
+                    self.later_fajr_idx = 1;
+                }
+                KeyCode::Enter => self.advance(),
+                KeyCode::Esc => self.go_back(),
+                _ => {}
+            },
+
             Step::Madhab => match key.code {
                 KeyCode::Left | KeyCode::Char('1') | KeyCode::Char('h') => {
                     self.madhab_idx = 0;
@@ -225,6 +364,22 @@ impl SetupWizard {
                 _ => {}
             },
 
+            Step::HighLatitude => match key.code {
+                KeyCode::Left | KeyCode::Up | KeyCode::Char('h') | KeyCode::Char('k') => {
+                    self.high_latitude_idx = self.high_latitude_idx.saturating_sub(1);
+                }
+                KeyCode::Right | KeyCode::Down | KeyCode::Char('l') | KeyCode::Char('j') => {
+                    self.high_latitude_idx =
+                        (self.high_latitude_idx + 1).min(HIGH_LATITUDE_RULES.len() - 1);
+                }
+                KeyCode::Char(c @ '1'..='4') => {
+                    self.high_latitude_idx = c as usize - '1' as usize;
+                }
+                KeyCode::Enter => self.advance(),
+                KeyCode::Esc => self.go_back(),
+                _ => {}
+            },
+
             Step::TimezoneOffset => self.handle_text_input(key, |s| {
                 parse_tz(s).map(|_| ()).map_err(|_| {
                     "Use format like +5:30, -3, or +5.5".to_string()
@@ -232,17 +387,24 @@ impl SetupWizard {
             }),
 
             Step::HijriOffset => match key.code {
-                KeyCode::Left | KeyCode::Char('1') | KeyCode::Char('h') => {
-                    self.hijri_idx = 0;
+                KeyCode::Left | KeyCode::Up | KeyCode::Char('h') | KeyCode::Char('k') => {
+                    self.hijri_idx = self.hijri_idx.saturating_sub(1);
                 }
-                KeyCode::Right | KeyCode::Char('2') | KeyCode::Char('l') => {
-                    self.hijri_idx = 1;
+                KeyCode::Right | KeyCode::Down | KeyCode::Char('l') | KeyCode::Char('j') => {
+                    self.hijri_idx = (self.hijri_idx + 1).min(HIJRI_OFFSETS.len() - 1);
+                }
+                KeyCode::Char(c @ '1'..='3') => {
+                    self.hijri_idx = c as usize - '1' as usize;
                 }
                 KeyCode::Enter => self.advance(),
                 KeyCode::Esc => self.go_back(),
                 _ => {}
             },
 
+            Step::Adjustments => self.handle_text_input(key, |s| {
+                parse_adjustments(s).map(|_| ())
+            }),
+
             Step::Confirm => match key.code {
                 KeyCode::Enter | KeyCode::Char('y') => {
                     self.confirmed = true;
@@ -279,9 +441,14 @@ impl SetupWizard {
                 // Reset to default value for this step
                 self.input = match self.step {
                     Step::LocationName => "Mumbai".to_string(),
+                    Step::Country => String::new(),
                     Step::Latitude => "19.0748".to_string(),
                     Step::Longitude => "72.8856".to_string(),
+                    Step::Elevation => "0".to_string(),
+                    Step::FajrAngle => "18".to_string(),
+                    Step::IshaAngle => "17".to_string(),
                     Step::TimezoneOffset => "+5:30".to_string(),
+                    Step::Adjustments => "0 0 0 0 0".to_string(),
                     _ => self.input.clone(),
                 };
                 self.error = None;
@@ -298,33 +465,101 @@ impl SetupWizard {
         match self.step {
             Step::LocationName => {
                 self.location_name = val.to_string();
+                self.city_matches = cities::search(val);
+                self.city_state.select(Some(0));
+            }
+            Step::Country => {
+                self.set_country(Some(val.to_uppercase()).filter(|c| !c.is_empty()));
             }
             Step::Latitude => {
-                self.latitude = val.parse().unwrap_or(self.latitude);
+                self.latitude = val
+                    .parse()
+                    .map(|v| round_decimals(v, self.coord_precision))
+                    .unwrap_or(self.latitude);
             }
             Step::Longitude => {
-                self.longitude = val.parse().unwrap_or(self.longitude);
+                self.longitude = val
+                    .parse()
+                    .map(|v| round_decimals(v, self.coord_precision))
+                    .unwrap_or(self.longitude);
+            }
+            Step::Elevation => {
+                self.elevation_m = val.parse().unwrap_or(self.elevation_m);
+            }
+            Step::FajrAngle => {
+                self.fajr_angle = val.parse().ok().or(self.fajr_angle);
+            }
+            Step::IshaAngle => {
+                self.isha_angle = val.parse().ok().or(self.isha_angle);
             }
             Step::TimezoneOffset => {
                 self.tz_minutes = parse_tz(val).unwrap_or(self.tz_minutes);
             }
+            Step::Adjustments => {
+                if let Ok(adj) = parse_adjustments(val) {
+                    self.adjustments = adj;
+                }
+            }
             _ => {}
         }
     }
 
+    fn set_country(&mut self, country: Option<String>) {
+        // Only a newly entered country re-selects the method, so an
+        // earlier manual choice survives re-running setup
+        if country != self.country
+            && let Some(code) = &country
+        {
+            let suggested = suggested_method_for_country(code);
+            if let Some(idx) = CALC_METHODS.iter().position(|m| *m == suggested) {
+                self.method_idx = idx;
+                self.list_state.select(Some(idx));
+            }
+        }
+        self.country = country;
+    }
+
+    fn city_idx(&self) -> usize {
+        self.city_state.selected().unwrap_or(0)
+    }
+
+    fn pick_city(&mut self, city: &City) {
+        self.location_name = city.name.to_string();
+        self.set_country(Some(city.country.to_string()));
+        self.latitude = round_decimals(city.latitude, self.coord_precision);
+        self.longitude = round_decimals(city.longitude, self.coord_precision);
+    }
+
+    fn uses_custom_angles(&self) -> bool {
+        CALC_METHODS[self.method_idx] == "Other"
+    }
+
     fn build_config(&self, existing: &AppConfig) -> AppConfig {
         let mut config = existing.clone();
         config.salah.location_name = self.location_name.clone();
+        config.salah.country = self.country.clone();
         config.salah.latitude = self.latitude;
         config.salah.longitude = self.longitude;
+        config.salah.elevation_m = self.elevation_m;
         config.salah.calc_method = CALC_METHODS[self.method_idx].to_string();
+        if self.uses_custom_angles() {
+            config.salah.fajr_angle = self.fajr_angle;
+            config.salah.isha_angle = self.isha_angle;
+            config.salah.later_fajr = false;
+        } else {
+            config.salah.fajr_angle = None;
+            config.salah.isha_angle = None;
+            config.salah.later_fajr = self.later_fajr_idx == 1;
+        }
         config.salah.madhab = if self.madhab_idx == 0 {
             "Hanafi".to_string()
         } else {
             "Shafi".to_string()
         };
+        config.salah.high_latitude_rule = HIGH_LATITUDE_RULES[self.high_latitude_idx].to_string();
         config.salah.timezone_offset = self.tz_minutes;
-        config.salah.hijri_offset = if self.hijri_idx == 0 { 0 } else { -1 };
+        config.salah.hijri_offset = HIJRI_OFFSETS[self.hijri_idx];
+        config.salah.adjustments = self.adjustments.clone();
         config
     }
 }
@@ -362,7 +597,7 @@ fn draw(frame: &mut Frame, wizard: &mut SetupWizard) {
     let outer_block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(theme::gold())
+        .border_style(theme::border(true))
         .style(theme::surface())
         .title(Span::styled(
             "  سُجُود  sujood  —  Setup  ",
@@ -382,7 +617,26 @@ fn draw(frame: &mut Frame, wizard: &mut SetupWizard) {
 
     match wizard.step {
         Step::Welcome => draw_welcome(frame, inner),
+        Step::CityMatch => draw_city_list(frame, inner, wizard),
         Step::CalcMethod => draw_method_list(frame, inner, wizard),
+        Step::FajrTiming => {
+            let method = CALC_METHODS[wizard.method_idx];
+            let default = method_fajr_angle(method).unwrap_or(LATER_FAJR_ANGLE);
+            let options = [
+                format!("{} default  ({}°)", method, default),
+                format!("Later Fajr  ({}°)", LATER_FAJR_ANGLE),
+            ];
+            draw_choice(
+                frame,
+                inner,
+                5,
+                "Fajr Timing",
+                "Some scholars hold Fajr begins later, at a shallower 15° angle",
+                &[options[0].as_str(), options[1].as_str()],
+                wizard.later_fajr_idx,
+                &wizard.error,
+            )
+        }
         Step::Madhab => draw_choice(
             frame,
             inner,
@@ -393,15 +647,31 @@ fn draw(frame: &mut Frame, wizard: &mut SetupWizard) {
             wizard.madhab_idx,
             &wizard.error,
         ),
+        Step::HighLatitude => draw_choice(
+            frame,
+            inner,
+            6,
+            "High-Latitude Rule",
+            "Bounds Fajr and Isha when twilight lasts all night (polar summers)",
+            &[
+                "Middle of the night  (default)",
+                "One-seventh of the night",
+                "Twilight angle  (angle ÷ 60 of the night)",
+                "Recommended  (one-seventh above 48°)",
+            ],
+            wizard.high_latitude_idx,
+            &wizard.error,
+        ),
         Step::HijriOffset => draw_choice(
             frame,
             inner,
-            7,
+            8,
             "Hijri Date",
             "When does your region start each Islamic month?",
             &[
                 "Same day as astronomical calculation",
                 "One day after (local moon sighting — common in South Asia)",
+                "One day ahead (moon sighted a day earlier)",
             ],
             wizard.hijri_idx,
             &wizard.error,
@@ -496,9 +766,14 @@ fn draw_text_step(frame: &mut Frame, area: Rect, wizard: &SetupWizard) {
     let (title, subtitle, hint) = match wizard.step {
         Step::LocationName => (
             "City Name",
-            "Where are you located? (used for display only)",
+            "Where are you located? Known cities fill in their coordinates",
             "e.g.  Mumbai,  Karachi,  London",
         ),
+        Step::Country => (
+            "Country",
+            "Two-letter country code — pre-selects the usual calculation method",
+            "e.g.  PK,  EG,  GB  ·  leave empty to skip",
+        ),
         Step::Latitude => (
             "Latitude",
             "Your city's latitude — north/south position",
@@ -509,11 +784,31 @@ fn draw_text_step(frame: &mut Frame, area: Rect, wizard: &SetupWizard) {
             "Your city's longitude — east/west position",
             "e.g.  72.8856  for Mumbai  ·  [Tab] to reset",
         ),
+        Step::Elevation => (
+            "Elevation",
+            "Metres above sea level — the sun rises earlier and sets later up high",
+            "0  at sea level  ·  1000 m  moves them 4–6 minutes  ·  [Tab] to reset",
+        ),
+        Step::FajrAngle => (
+            "Fajr Angle",
+            "Sun depression below the horizon for Fajr, in degrees",
+            "e.g.  18  (MWL)  ·  15  (ISNA)  ·  [Tab] to reset",
+        ),
+        Step::IshaAngle => (
+            "Isha Angle",
+            "Sun depression below the horizon for Isha, in degrees",
+            "e.g.  17  (MWL)  ·  15  (ISNA)  ·  [Tab] to reset",
+        ),
         Step::TimezoneOffset => (
             "UTC Offset",
             "Your timezone offset from UTC",
             "e.g.  +5:30  for IST  ·  +3  for AST  ·  -5  for EST",
         ),
+        Step::Adjustments => (
+            "Time Adjustments",
+            "Minutes to add to Fajr Zuhr Asr Maghrib Isha (negative = earlier)",
+            "e.g.  -2 0 0 0 12  to match your masjid  ·  [Tab] to reset",
+        ),
         _ => ("", "", ""),
     };
 
@@ -582,7 +877,13 @@ fn draw_method_list(frame: &mut Frame, area: Rect, wizard: &mut SetupWizard) {
             "Choose the authority for prayer time calculation",
             theme::dim(),
         )),
-        Line::from(""),
+        match &wizard.country {
+            Some(code) => Line::from(Span::styled(
+                format!("Usual for {}: {}", code, suggested_method_for_country(code)),
+                theme::amber(),
+            )),
+            None => Line::from(""),
+        },
     ];
 
     let header_para = Paragraph::new(header_lines).alignment(Alignment::Center);
@@ -640,6 +941,85 @@ fn draw_method_list(frame: &mut Frame, area: Rect, wizard: &mut SetupWizard) {
     frame.render_widget(footer, footer_area);
 }
 
+fn draw_city_list(frame: &mut Frame, area: Rect, wizard: &mut SetupWizard) {
+    let header_lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Pick Your City",
+            theme::gold().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Matches for \"{}\" — fills in country and coordinates", wizard.location_name),
+            theme::dim(),
+        )),
+    ];
+
+    let header_para = Paragraph::new(header_lines).alignment(Alignment::Center);
+    let header_area = Rect {
+        x: area.x,
+        y: area.y + 2,
+        width: area.width,
+        height: 4,
+    };
+    frame.render_widget(header_para, header_area);
+
+    let list_area = Rect {
+        x: area.x + 2,
+        y: area.y + 7,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(11),
+    };
+
+    let selected_idx = wizard.city_idx();
+    let labels = wizard
+        .city_matches
+        .iter()
+        .map(|c| {
+            format!(
+                "{}, {}   {} {}",
+                c.name,
+                c.country,
+                format_coord(c.latitude, wizard.coord_precision),
+                format_coord(c.longitude, wizard.coord_precision),
+            )
+        })
+        .chain(std::iter::once("None of these — enter coordinates myself".to_string()));
+    let items: Vec<ListItem> = labels
+        .enumerate()
+        .map(|(i, label)| {
+            let line = if i == selected_idx {
+                Line::from(vec![
+                    Span::styled("  ◉  ", theme::gold()),
+                    Span::styled(label, theme::gold().add_modifier(Modifier::BOLD)),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::styled("  ○  ", theme::dim()),
+                    Span::styled(label, theme::dim()),
+                ])
+            };
+            ListItem::new(line)
+        })
+        .collect();
+
+    let list = List::new(items).style(theme::surface());
+    frame.render_stateful_widget(list, list_area, &mut wizard.city_state);
+
+    let footer = Paragraph::new(Line::from(Span::styled(
+        "↑↓  navigate   ·   Enter  select   ·   Esc  back",
+        theme::dim(),
+    )))
+    .alignment(Alignment::Center);
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height.saturating_sub(3),
+        width: area.width,
+        height: 1,
+    };
+    frame.render_widget(footer, footer_area);
+}
+
 fn draw_choice(
     frame: &mut Frame,
     area: Rect,
@@ -679,10 +1059,15 @@ fn draw_choice(
         lines.push(Line::from(Span::styled(format!("  ✗  {}", err), theme::red())));
     }
     lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        "←→  or  1 2  choose   ·   Enter  confirm   ·   Esc  back",
-        theme::dim(),
-    )));
+    let hint = if options.len() > 2 {
+        format!(
+            "↑↓  or  1–{}  choose   ·   Enter  confirm   ·   Esc  back",
+            options.len()
+        )
+    } else {
+        "←→  or  1 2  choose   ·   Enter  confirm   ·   Esc  back".to_string()
+    };
+    lines.push(Line::from(Span::styled(hint, theme::dim())));
 
     let para = Paragraph::new(lines).alignment(Alignment::Center);
     let content_area = Rect {
@@ -696,10 +1081,22 @@ fn draw_choice(
 
 fn draw_confirm(frame: &mut Frame, area: Rect, wizard: &SetupWizard) {
     let madhab = if wizard.madhab_idx == 0 { "Hanafi" } else { "Shafi" };
-    let hijri = if wizard.hijri_idx == 0 {
-        "Astronomical (default)"
+    let method = if wizard.uses_custom_angles() {
+        let angle = |a: Option<f64>| a.map(|v| format!("{}°", v)).unwrap_or_else(|| "—".into());
+        format!(
+            "Other  (Fajr {}, Isha {})",
+            angle(wizard.fajr_angle),
+            angle(wizard.isha_angle)
+        )
+    } else if wizard.later_fajr_idx == 1 {
+        format!("{}  (later Fajr {}°)", CALC_METHODS[wizard.method_idx], LATER_FAJR_ANGLE)
     } else {
-        "Local moon sighting (−1 day)"
+        CALC_METHODS[wizard.method_idx].to_string()
+    };
+    let hijri = match HIJRI_OFFSETS[wizard.hijri_idx] {
+        0 => "Astronomical (default)",
+        -1 => "Local moon sighting (−1 day)",
+        _ => "Local moon sighting (+1 day)",
     };
 
     let lines = vec![
@@ -710,23 +1107,41 @@ fn draw_confirm(frame: &mut Frame, area: Rect, wizard: &SetupWizard) {
         Line::from(""),
         Line::from(vec![
             Span::styled("  Location    ", theme::dim()),
-            Span::styled(&wizard.location_name, theme::bold()),
+            Span::styled(
+                match &wizard.country {
+                    Some(code) => format!("{} ({})", wizard.location_name, code),
+                    None => wizard.location_name.clone(),
+                },
+                theme::bold(),
+            ),
         ]),
         Line::from(vec![
             Span::styled("  Coordinates ", theme::dim()),
             Span::styled(
-                format!("{:.4},  {:.4}", wizard.latitude, wizard.longitude),
+                format!(
+                    "{},  {}",
+                    format_coord(wizard.latitude, wizard.coord_precision),
+                    format_coord(wizard.longitude, wizard.coord_precision)
+                ),
                 theme::bold(),
             ),
         ]),
+        Line::from(vec![
+            Span::styled("  Elevation   ", theme::dim()),
+            Span::styled(format!("{} m", wizard.elevation_m), theme::bold()),
+        ]),
         Line::from(vec![
             Span::styled("  Method      ", theme::dim()),
-            Span::styled(CALC_METHODS[wizard.method_idx], theme::bold()),
+            Span::styled(method, theme::bold()),
         ]),
         Line::from(vec![
             Span::styled("  Madhab      ", theme::dim()),
             Span::styled(madhab, theme::bold()),
         ]),
+        Line::from(vec![
+            Span::styled("  High Lat.   ", theme::dim()),
+            Span::styled(HIGH_LATITUDE_RULES[wizard.high_latitude_idx], theme::bold()),
+        ]),
         Line::from(vec![
             Span::styled("  UTC Offset  ", theme::dim()),
             Span::styled(format_tz(wizard.tz_minutes), theme::bold()),
@@ -735,6 +1150,10 @@ fn draw_confirm(frame: &mut Frame, area: Rect, wizard: &SetupWizard) {
             Span::styled("  Hijri Date  ", theme::dim()),
             Span::styled(hijri, theme::bold()),
         ]),
+        Line::from(vec![
+            Span::styled("  Adjustments ", theme::dim()),
+            Span::styled(format_adjustments(&wizard.adjustments), theme::bold()),
+        ]),
         Line::from(""),
         Line::from(""),
         Line::from(Span::styled(
@@ -787,51 +1206,46 @@ fn draw_caching(frame: &mut Frame) {
 
 pub fn run_setup_tui(conn: &Connection, config: &mut AppConfig) -> Result<()> {
     let mut wizard = SetupWizard::new(config);
-    let mut terminal = ratatui::init();
-    let events = EventHandler::new(100);
-
-    loop {
-        terminal.draw(|frame| draw(frame, &mut wizard))?;
-
-        match events.next()? {
-            Event::Key(key) => {
-                wizard.handle_key(key);
-                if wizard.should_quit {
-                    break;
-                }
-                if wizard.confirmed {
-                    // Show caching screen
-                    terminal.draw(|frame| draw_caching(frame))?;
-
-                    // Build and save config
-                    let new_config = wizard.build_config(config);
-                    *config = new_config;
-                    config.save()?;
-
-                    // Clear stale cache and recompute
-                    CacheRepo::clear_all(conn)?;
-                    let calc = PrayerCalculator::new(
-                        config.salah.latitude,
-                        config.salah.longitude,
-                        &config.salah.calc_method,
-                        &config.salah.madhab,
-                        config.salah.timezone_offset,
-                    )?;
-                    calc.ensure_cached(conn, 90)?;
-
-                    // Mark setup done
-                    use crate::db::repository::MetaRepo;
-                    MetaRepo::set(conn, "setup_done", "1")?;
-
-                    break;
+    terminal::with_terminal(|terminal| {
+        // Nothing here changes on its own; ticks only keep the loop honest
+        let events = EventHandler::new(1000);
+
+        loop {
+            terminal.draw(|frame| draw(frame, &mut wizard))?;
+
+            match events.next()? {
+                Event::Key(key) => {
+                    wizard.handle_key(key);
+                    if wizard.should_quit {
+                        break;
+                    }
+                    if wizard.confirmed {
+                        // Show caching screen
+                        terminal.draw(|frame| draw_caching(frame))?;
+
+                        // Build and save config
+                        let new_config = wizard.build_config(config);
+                        *config = new_config;
+                        config.save()?;
+
+                        // Clear stale cache and recompute
+                        crate::cli::handlers::sync_cache(conn, config)?;
+                        let calc = PrayerCalculator::from_config(&config.salah)?;
+                        let days = config.salah.cache_days_ahead;
+                        calc.ensure_cached(conn, now_local(config).date(), days)?;
+
+                        // Mark setup done
+                        use crate::db::repository::MetaRepo;
+                        MetaRepo::set(conn, "setup_done", "1")?;
+
+                        break;
+                    }
                 }
+                Event::Mouse(_) | Event::Resize | Event::Tick => {}
             }
-            Event::Tick => {}
         }
-    }
-
-    ratatui::restore();
-    Ok(())
+        Ok(())
+    })
 }
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
@@ -848,6 +1262,53 @@ fn format_tz(minutes: i32) -> String {
     }
 }
 
+fn validate_angle(s: &str) -> std::result::Result<(), String> {
+    if s.is_empty() {
+        return Err("The Other method needs this angle — enter it in degrees (e.g. 18)".to_string());
+    }
+    s.parse::<f64>()
+        .map_err(|_| "Enter an angle in degrees (e.g. 18)".to_string())
+        .and_then(|v| {
+            if v <= 0.0 || v > 25.0 {
+                Err("Angle must be between 0 and 25 degrees".to_string())
+            } else {
+                Ok(())
+            }
+        })
+}
+
+fn format_adjustments(adj: &PrayerAdjustments) -> String {
+    format!(
+        "{} {} {} {} {}",
+        adj.fajr_adj, adj.zuhr_adj, adj.asr_adj, adj.maghrib_adj, adj.isha_adj
+    )
+}
+
+/// Parse five whitespace- or comma-separated minute offsets (Fajr → Isha).
+fn parse_adjustments(s: &str) -> std::result::Result<PrayerAdjustments, String> {
+    let values = s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|p| !p.is_empty())
+        .map(|p| p.trim_start_matches('+').parse::<i32>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| "Enter five whole numbers, e.g. -2 0 0 0 12".to_string())?;
+
+    if values.len() != 5 {
+        return Err("Enter exactly five values: Fajr Zuhr Asr Maghrib Isha".to_string());
+    }
+    if values.iter().any(|v| v.abs() > 120) {
+        return Err("Adjustments must be within ±120 minutes".to_string());
+    }
+
+    Ok(PrayerAdjustments {
+        fajr_adj: values[0],
+        zuhr_adj: values[1],
+        asr_adj: values[2],
+        maghrib_adj: values[3],
+        isha_adj: values[4],
+    })
+}
+
 fn parse_tz(s: &str) -> Result<i32> {
     let s = s.trim().trim_start_matches('+');
     let negative = s.starts_with('-');
@@ -869,3 +1330,37 @@ fn parse_tz(s: &str) -> Result<i32> {
 
     Ok(sign * minutes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinates_survive_the_wizard_without_extra_decimals() {
+        let mut existing = AppConfig::default();
+        existing.salah.latitude = 21.422_487_123_456;
+        existing.salah.longitude = -0.1 - 0.2; // -0.30000000000000004
+
+        let mut wizard = SetupWizard::new(&existing);
+        wizard.step = Step::Country;
+        wizard.advance();
+        assert_eq!(wizard.step, Step::Latitude);
+        assert_eq!(wizard.input, "21.42249");
+        let input = wizard.input.clone();
+        wizard.commit_text_input(&input);
+        wizard.advance();
+        assert_eq!(wizard.input, "-0.3");
+        let input = wizard.input.clone();
+        wizard.commit_text_input(&input);
+
+        let saved = toml::to_string_pretty(&wizard.build_config(&existing)).unwrap();
+        assert!(saved.contains("latitude = 21.42249\n"), "{saved}");
+        assert!(saved.contains("longitude = -0.3\n"), "{saved}");
+
+        // Running setup again over the saved config changes nothing
+        let reloaded: AppConfig = toml::from_str(&saved).unwrap();
+        let again = SetupWizard::new(&reloaded).build_config(&reloaded);
+        assert_eq!(again.salah.latitude, 21.42249);
+        assert_eq!(again.salah.longitude, -0.3);
+    }
+}
@@ -1,8 +1,16 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::NaiveDate;
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
 use std::path::PathBuf;
 
+use crate::models::{Nafl, PrayerType};
+
+/// Environment variable naming the database file, e.g. one in a synced folder.
+pub const DB_ENV_VAR: &str = "SUJOOD_DB";
+
 fn default_latitude() -> f64 {
     33.6938
 }
@@ -24,9 +32,27 @@ fn default_timezone_offset() -> i32 {
 fn default_hijri_offset() -> i32 {
     0
 }
+pub fn default_imsak_minutes() -> u32 {
+    10
+}
+fn default_cache_days_ahead() -> u32 {
+    90
+}
+fn default_input_history_size() -> usize {
+    5
+}
+fn default_coord_precision() -> usize {
+    5
+}
+fn default_high_latitude_rule() -> String {
+    "MiddleOfTheNight".to_string()
+}
 fn default_daily_target() -> f64 {
     2.0
 }
+fn default_mushaf_pages() -> u32 {
+    604
+}
 fn default_true() -> bool {
     true
 }
@@ -35,10 +61,16 @@ fn default_true() -> bool {
 pub struct SalahConfig {
     #[serde(default = "default_location_name")]
     pub location_name: String,
+    /// ISO 3166-1 alpha-2 country code; used to suggest a calculation method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
     #[serde(default = "default_latitude")]
     pub latitude: f64,
     #[serde(default = "default_longitude")]
     pub longitude: f64,
+    /// Decimal places coordinates are stored and shown with (5 ≈ 1 m, max 8)
+    #[serde(default = "default_coord_precision")]
+    pub coord_precision: usize,
     #[serde(default = "default_calc_method")]
     pub calc_method: String,
     #[serde(default = "default_madhab")]
@@ -49,22 +81,144 @@ pub struct SalahConfig {
     /// 0 = default (Saudi), -1 = one day behind (e.g. some Indian regions), +1 = one day ahead
     #[serde(default = "default_hijri_offset")]
     pub hijri_offset: i32,
+    /// Twilight angles (degrees) used when `calc_method = "Other"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fajr_angle: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isha_angle: Option<f64>,
+    /// How Fajr/Isha are bounded when twilight never fully ends (high latitudes).
+    /// MiddleOfTheNight | SeventhOfTheNight | TwilightAngle | Recommended
+    #[serde(default = "default_high_latitude_rule")]
+    pub high_latitude_rule: String,
+    /// Use a later Fajr (15°) instead of the method's own angle.
+    /// Ignored with calc_method = "Other", whose angles are set directly.
+    #[serde(default)]
+    pub later_fajr: bool,
+    /// Height above sea level in metres. Moves sunrise earlier and Maghrib
+    /// later by the dip of the horizon (a few minutes at 1000 m).
+    #[serde(default, alias = "elevation_meters")]
+    pub elevation_m: f64,
+    /// Imsak, when eating stops before a fast, this many minutes before Fajr
+    #[serde(default = "default_imsak_minutes")]
+    pub imsak_minutes: u32,
+    /// Days of prayer times computed and cached past today (1–365). More means
+    /// fewer recalculations; fewer means less work after a settings change.
+    #[serde(default = "default_cache_days_ahead")]
+    pub cache_days_ahead: u32,
+    /// Fixed khutbah time ("HH:MM") shown in place of Zuhr on Fridays.
+    /// Only the displayed time changes; it's still tracked as Zuhr.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jummah_time: Option<String>,
+    /// Manual per-prayer corrections applied after calculation
+    #[serde(default)]
+    pub adjustments: PrayerAdjustments,
+    /// Where prayer times come from: "calculated" (default) or "file" for a
+    /// timetable loaded with `sujood times import`, which is never recalculated
+    #[serde(default)]
+    pub source: TimesSource,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimesSource {
+    #[default]
+    Calculated,
+    File,
+}
+
+/// Minutes added to each calculated prayer time (negative = earlier).
+/// Useful for matching a local masjid's published timetable.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PrayerAdjustments {
+    #[serde(default)]
+    pub fajr_adj: i32,
+    #[serde(default)]
+    pub zuhr_adj: i32,
+    #[serde(default)]
+    pub asr_adj: i32,
+    #[serde(default)]
+    pub maghrib_adj: i32,
+    #[serde(default)]
+    pub isha_adj: i32,
 }
 
 impl Default for SalahConfig {
     fn default() -> Self {
         Self {
             location_name: default_location_name(),
+            country: None,
             latitude: default_latitude(),
             longitude: default_longitude(),
+            coord_precision: default_coord_precision(),
             calc_method: default_calc_method(),
             madhab: default_madhab(),
             timezone_offset: default_timezone_offset(),
             hijri_offset: default_hijri_offset(),
+            fajr_angle: None,
+            isha_angle: None,
+            high_latitude_rule: default_high_latitude_rule(),
+            later_fajr: false,
+            elevation_m: 0.0,
+            imsak_minutes: default_imsak_minutes(),
+            cache_days_ahead: default_cache_days_ahead(),
+            jummah_time: None,
+            adjustments: PrayerAdjustments::default(),
+            source: TimesSource::default(),
         }
     }
 }
 
+impl SalahConfig {
+    /// Reject settings no calculation could use; `section` names the table
+    /// they came from, `[salah]` or a profile.
+    pub fn validate(&self, section: &str) -> Result<()> {
+        if !(1..=365).contains(&self.cache_days_ahead) {
+            bail!(
+                "{} cache_days_ahead is {} — pick 1–365 days of times to keep ahead",
+                section,
+                self.cache_days_ahead
+            );
+        }
+        Ok(())
+    }
+
+    /// Both custom twilight angles, if configured.
+    pub fn custom_angles(&self) -> Option<(f64, f64)> {
+        self.fajr_angle.zip(self.isha_angle)
+    }
+
+    /// Hash of every setting that goes into the cached prayer times, so a
+    /// hand edit to config.toml can be noticed. Names, the Hijri offset, the
+    /// Jummah time and the cache length are left out since nothing cached
+    /// depends on them.
+    /// FNV-1a rather than `DefaultHasher`, whose output may change between
+    /// Rust releases.
+    pub fn fingerprint(&self) -> String {
+        let fields = format!(
+            "{:?} {:?}",
+            (
+                self.latitude,
+                self.longitude,
+                &self.calc_method,
+                &self.madhab,
+                self.timezone_offset,
+                self.fajr_angle,
+                self.isha_angle,
+                &self.high_latitude_rule,
+                self.later_fajr,
+                self.elevation_m,
+                self.imsak_minutes,
+                &self.adjustments,
+            ),
+            self.source,
+        );
+        let hash = fields.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        format!("{:016x}", hash)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CustomDhikr {
     pub name: String,
@@ -99,6 +253,16 @@ pub struct QuranConfig {
     pub enabled: bool,
     #[serde(default = "default_daily_target")]
     pub daily_target: f64,
+    /// How many recent page counts to remember for quick re-entry (0 = off)
+    #[serde(default = "default_input_history_size")]
+    pub input_history_size: usize,
+    /// Pages in a full mushaf, for khatm progress (604 in the Madani print)
+    #[serde(default = "default_mushaf_pages")]
+    pub mushaf_pages: u32,
+    /// Finish the current khatm by this day ("YYYY-MM-DD"). While it runs,
+    /// the daily target becomes whatever is left spread over the days left.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub khatm_deadline: Option<NaiveDate>,
 }
 
 impl Default for QuranConfig {
@@ -106,18 +270,402 @@ impl Default for QuranConfig {
         Self {
             enabled: true,
             daily_target: 2.0,
+            input_history_size: default_input_history_size(),
+            mushaf_pages: default_mushaf_pages(),
+            khatm_deadline: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EidConfig {
+    /// Offer the Eid prayer tracker on Eid al-Fitr and Eid al-Adha
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+impl Default for EidConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FastingConfig {
+    /// Remind about sunnah fasts (white days, Mondays, Thursdays, Arafah,
+    /// Ashura) and offer the fasting tracker
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JummahConfig {
+    /// Offer the Jumu'ah attendance toggle on Fridays
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Minutes before Zuhr on Fridays to remind you to leave. 0 disables it.
+    #[serde(default)]
+    pub leave_early_minutes: u32,
+}
+
+impl Default for JummahConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            leave_early_minutes: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreakConfig {
+    /// Treat days with no prayer rows at all (app never opened) as neutral gaps
+    /// that neither extend nor break a streak. Days that were tracked but left
+    /// incomplete still break it.
+    #[serde(default)]
+    pub untracked_days_neutral: bool,
+    /// Let a day with one of `forgivable` missed and the other four prayed
+    /// still extend the streak. Off keeps the strict 5/5 rule.
+    #[serde(default)]
+    pub forgiving: bool,
+    /// Prayers whose miss `forgiving` overlooks — at most one per day
+    #[serde(default = "default_forgivable")]
+    pub forgivable: Vec<PrayerType>,
+    /// Incomplete days a streak may contain before one more ends it. Grace
+    /// days keep the streak going but aren't counted in its length.
+    #[serde(default)]
+    pub grace_days: u32,
+    /// Count a missed prayer as prayed for the streak once its qada is made up
+    #[serde(default)]
+    pub qada_heals: bool,
+}
+
+fn default_forgivable() -> Vec<PrayerType> {
+    vec![PrayerType::Fajr]
+}
+
+impl Default for StreakConfig {
+    fn default() -> Self {
+        Self {
+            untracked_days_neutral: false,
+            forgiving: false,
+            forgivable: default_forgivable(),
+            grace_days: 0,
+            qada_heals: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GoalsConfig {
+    /// Fard prayers to pray each week (Saturday–Friday), out of 35
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekly_prayers: Option<u32>,
+    /// Fard prayers to pray each calendar month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_prayers: Option<u32>,
+}
+
+impl GoalsConfig {
+    /// Reject targets that could never be met.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(n) = self.weekly_prayers.filter(|n| !(1..=35).contains(n)) {
+            bail!("[goals] weekly_prayers is {}, but a week has 35 prayers — pick 1–35", n);
+        }
+        if let Some(n) = self.monthly_prayers.filter(|n| !(1..=155).contains(n)) {
+            bail!(
+                "[goals] monthly_prayers is {}, but a month has at most 155 prayers — pick 1–155",
+                n
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DayConfig {
+    /// Create tomorrow's pending prayer rows once today's last prayer time has
+    /// passed, so the first action after midnight needs no setup.
+    #[serde(default)]
+    pub precreate_tomorrow_rows: bool,
+    /// Hour (0–23) at which a new tracking day begins. With 3, a late Isha
+    /// marked at 01:30 still counts toward the previous day.
+    #[serde(default)]
+    pub rollover_hour: u32,
+    /// When to offer the end-of-day summary: "isha+60" (minutes after Isha)
+    /// or a fixed "HH:MM". Unset disables it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_at: Option<String>,
+    /// Store each day's computed prayer times on its rows when they are
+    /// created, so history keeps the times that applied even after the
+    /// cache is rebuilt under a new config.
+    #[serde(default)]
+    pub snapshot_times: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Played in the dashboard as each prayer time enters: "off", "bell"
+    /// for the terminal bell, or a path to an audio file.
+    #[serde(default = "default_sound")]
+    pub sound: String,
+    /// Minutes before each prayer that `sujood daemon` sends a desktop
+    /// notification; 0 is the moment the prayer enters.
+    #[serde(default = "default_lead_minutes")]
+    pub lead_minutes: Vec<u32>,
+}
+
+fn default_sound() -> String {
+    "off".to_string()
+}
+
+fn default_lead_minutes() -> Vec<u32> {
+    vec![10, 0]
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            sound: default_sound(),
+            lead_minutes: default_lead_minutes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    /// Color scheme for the dashboard and setup wizard: "dark" or "light"
+    #[serde(default = "default_theme")]
+    pub theme: String,
+    /// How often the dashboard wakes to refresh its countdown, in
+    /// milliseconds. Ticks land on whole seconds, so the default 1000 turns
+    /// the countdown over exactly on the minute.
+    #[serde(default = "default_tick_ms")]
+    pub tick_ms: u64,
+    /// Clock for displayed times: "24h" (13:05) or "12h" (1:05 PM)
+    #[serde(default)]
+    pub time_format: TimeFormat,
+    /// Click prayers, adhkar and status bar hints in the dashboard. While
+    /// it's on, most terminals need Shift held to select text.
+    #[serde(default = "default_true")]
+    pub mouse: bool,
+    /// Ask for a second Esc before the dashboard quits
+    #[serde(default = "default_true")]
+    pub confirm_quit: bool,
+}
+
+/// How prayer times are shown. Stored and cached times are always `%H:%M`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum TimeFormat {
+    #[default]
+    #[serde(rename = "24h")]
+    H24,
+    #[serde(rename = "12h")]
+    H12,
+}
+
+fn default_tick_ms() -> u64 {
+    1000
+}
+
+fn default_theme() -> String {
+    "dark".to_string()
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            theme: default_theme(),
+            tick_ms: default_tick_ms(),
+            time_format: TimeFormat::default(),
+            mouse: true,
+            confirm_quit: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    /// Show sunrise under Fajr in the dashboard's prayer list
+    #[serde(default = "default_true")]
+    pub show_sunrise: bool,
+    /// Warn in the next-prayer card during the makruh times around sunrise,
+    /// the sun's zenith and sunset
+    #[serde(default = "default_true")]
+    pub forbidden_windows: bool,
+    /// Also show Asr by the other madhab, dimmed beside the configured one
+    #[serde(default)]
+    pub both_asr: bool,
+}
+
+/// Dashboard keys for the common actions, one character each. Tab, the
+/// arrows, Enter, Esc and the other letters stay fixed; a key bound here
+/// takes precedence over a fixed one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeybindingsConfig {
+    #[serde(default = "default_mark_key")]
+    pub mark: char,
+    #[serde(default = "default_missed_key")]
+    pub missed: char,
+    #[serde(default = "default_dhikr_key")]
+    pub dhikr: char,
+    #[serde(default = "default_quran_key")]
+    pub quran: char,
+    #[serde(default = "default_stats_key")]
+    pub stats: char,
+    #[serde(default = "default_help_key")]
+    pub help: char,
+    #[serde(default = "default_qada_key")]
+    pub qada: char,
+    /// Esc always quits; this adds a key that does too
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quit: Option<char>,
+    /// Keys that move the focus beside the arrows, e.g. "k" and "j"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub up: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub down: Option<char>,
+}
+
+/// Keys the stats views keep for themselves (month range, prayer filter), so
+/// `stats`, the one bound key they also read, may not take them.
+pub const STATS_VIEW_KEYS: &[char] = &['m', 'p'];
+
+fn default_mark_key() -> char {
+    'm'
+}
+
+fn default_missed_key() -> char {
+    'M'
+}
+
+fn default_dhikr_key() -> char {
+    'd'
+}
+
+fn default_quran_key() -> char {
+    'r'
+}
+
+fn default_stats_key() -> char {
+    's'
+}
+
+fn default_help_key() -> char {
+    '?'
+}
+
+fn default_qada_key() -> char {
+    'q'
+}
+
+impl Default for KeybindingsConfig {
+    fn default() -> Self {
+        Self {
+            mark: default_mark_key(),
+            missed: default_missed_key(),
+            dhikr: default_dhikr_key(),
+            quran: default_quran_key(),
+            stats: default_stats_key(),
+            help: default_help_key(),
+            qada: default_qada_key(),
+            quit: None,
+            up: None,
+            down: None,
+        }
+    }
+}
+
+impl KeybindingsConfig {
+    /// Every bound action with its key, named as in config.toml.
+    pub fn bindings(&self) -> Vec<(&'static str, char)> {
+        let bound = [
+            ("mark", self.mark),
+            ("missed", self.missed),
+            ("dhikr", self.dhikr),
+            ("quran", self.quran),
+            ("stats", self.stats),
+            ("help", self.help),
+            ("qada", self.qada),
+        ];
+        let optional = [("quit", self.quit), ("up", self.up), ("down", self.down)];
+        bound
+            .into_iter()
+            .chain(optional.into_iter().filter_map(|(name, key)| key.map(|k| (name, k))))
+            .collect()
+    }
+
+    /// Refuse a key bound to two actions, naming both, or a stats key the
+    /// stats views would read as something else.
+    pub fn validate(&self) -> Result<()> {
+        if STATS_VIEW_KEYS.contains(&self.stats) {
+            bail!(
+                "[keybindings] '{}' for stats is already a key in the stats view — pick another",
+                self.stats
+            );
+        }
+        let bindings = self.bindings();
+        for (i, (name, key)) in bindings.iter().enumerate() {
+            if let Some((other, _)) = bindings[..i].iter().find(|(_, k)| k == key) {
+                bail!(
+                    "[keybindings] '{}' is bound to both {} and {} — give one of them another key",
+                    key,
+                    other,
+                    name
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            show_sunrise: true,
+            forbidden_windows: true,
+            both_asr: false,
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
+    /// The entry in `profiles` that `[salah]` currently holds, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Voluntary prayers to track beside the fard, e.g. ["duha", "witr"]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nafl: Vec<Nafl>,
     #[serde(default)]
     pub salah: SalahConfig,
     #[serde(default)]
     pub dhikr: DhikrConfig,
     #[serde(default)]
     pub quran: QuranConfig,
+    #[serde(default)]
+    pub streak: StreakConfig,
+    #[serde(default)]
+    pub goals: GoalsConfig,
+    #[serde(default)]
+    pub day: DayConfig,
+    #[serde(default)]
+    pub eid: EidConfig,
+    #[serde(default)]
+    pub jummah: JummahConfig,
+    #[serde(default)]
+    pub fasting: FastingConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub ui: UiConfig,
+    #[serde(default)]
+    pub keybindings: KeybindingsConfig,
+    /// Saved locations by name (`[profiles.<name>]`), each a whole `[salah]` section
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, SalahConfig>,
 }
 
 impl AppConfig {
@@ -148,6 +696,12 @@ impl AppConfig {
         let content =
             std::fs::read_to_string(&path).with_context(|| format!("Reading {:?}", path))?;
         let config: AppConfig = toml::from_str(&content).context("Parsing config.toml")?;
+        config.keybindings.validate()?;
+        config.goals.validate()?;
+        config.salah.validate("[salah]")?;
+        for (name, salah) in &config.profiles {
+            salah.validate(&format!("[profiles.{}]", name))?;
+        }
         Ok(config)
     }
 
@@ -161,9 +715,86 @@ impl AppConfig {
         Ok(())
     }
 
-    pub fn ensure_data_dir() -> Result<PathBuf> {
-        let dir = Self::data_dir()?;
-        std::fs::create_dir_all(&dir)?;
-        Ok(dir)
+    /// Load profile `name` into `[salah]` and make it the active one. Changes
+    /// made to `[salah]` since the last switch are kept in the profile left.
+    pub fn switch_profile(&mut self, name: &str) -> Result<()> {
+        let salah = self.profiles.get(name).cloned().ok_or_else(|| {
+            if self.profiles.is_empty() {
+                anyhow!("No profile '{}' — save one first with `sujood profile add <name>`", name)
+            } else {
+                let names: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+                anyhow!("No profile '{}'. Profiles: {}", name, names.join(", "))
+            }
+        })?;
+        if let Some(current) = self.profile.take() {
+            self.profiles.insert(current, self.salah.clone());
+        }
+        self.salah = salah;
+        self.profile = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Where to open the database: `--db`, else `SUJOOD_DB`, else the default
+    /// under the data directory. Parent directories are created as needed.
+    pub fn resolve_db_path(flag: Option<PathBuf>) -> Result<PathBuf> {
+        Self::resolve_db_path_from(flag, std::env::var_os(DB_ENV_VAR))
+    }
+
+    fn resolve_db_path_from(flag: Option<PathBuf>, env: Option<OsString>) -> Result<PathBuf> {
+        let env = env.filter(|v| !v.is_empty());
+        let path = match flag.or(env.map(PathBuf::from)) {
+            Some(path) => path,
+            None => Self::db_path()?,
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Creating {}", parent.display()))?;
+        }
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::run_migrations;
+    use rusqlite::Connection;
+
+    #[test]
+    fn db_path_prefers_the_flag_then_the_env_var() {
+        let dir = tempfile::tempdir().unwrap();
+        let flag = dir.path().join("flag/nested/sujood.db");
+        let env = dir.path().join("env/sujood.db");
+
+        let path =
+            AppConfig::resolve_db_path_from(Some(flag.clone()), Some(env.clone().into())).unwrap();
+        assert_eq!(path, flag);
+        assert!(flag.parent().unwrap().is_dir());
+
+        let path = AppConfig::resolve_db_path_from(None, Some(env.clone().into())).unwrap();
+        assert_eq!(path, env);
+
+        // Migrations run against the custom file like the default one
+        let conn = Connection::open(&env).unwrap();
+        run_migrations(&conn).unwrap();
+        let tables: i64 = conn
+            .query_row("SELECT COUNT(*) FROM sqlite_master WHERE name = 'prayers'", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(tables, 1);
+    }
+
+    #[test]
+    fn vim_keys_shadow_fixed_ones_but_stats_keeps_clear_of_its_view() {
+        let vim = KeybindingsConfig { up: Some('k'), down: Some('j'), ..Default::default() };
+        assert!(vim.validate().is_ok());
+        assert!(KeybindingsConfig::default().validate().is_ok());
+
+        for key in ['m', 'p'] {
+            let stats = KeybindingsConfig { stats: key, mark: 'o', ..Default::default() };
+            let err = stats.validate().unwrap_err().to_string();
+            assert!(err.contains(&format!("'{key}' for stats")), "{err}");
+        }
     }
 }
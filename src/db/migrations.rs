@@ -1,7 +1,78 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use rusqlite::Connection;
 
+use crate::db::repository::MetaRepo;
+
+/// `app_meta` key holding how many of `MIGRATIONS` the database has had.
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Schema changes in the order they were made. A database at version N has
+/// had the first N applied; new steps go at the end and are never reordered.
+/// Steps see only the database, never the config: data fixes that depend on a
+/// setting, like `QadaRepo::localize_legacy_completions`, run after it loads.
+const MIGRATIONS: &[fn(&Connection) -> Result<()>] = &[
+    base_schema,
+    add_dhikr_tag,
+    add_scheduled_time,
+    add_marked_at,
+    add_cached_imsak,
+    add_imported_flag,
+    add_date_indexes,
+];
+
+/// Bring the database up to the latest schema. Each pending step runs in its
+/// own transaction together with the version bump, so an upgrade that fails
+/// part way leaves the database at the last complete version.
 pub fn run_migrations(conn: &Connection) -> Result<()> {
+    let mut version = schema_version(conn)?;
+    if version > MIGRATIONS.len() {
+        bail!(
+            "The database is at schema version {}, newer than this sujood knows ({}). \
+             Upgrade sujood to open it.",
+            version,
+            MIGRATIONS.len()
+        );
+    }
+
+    for migration in &MIGRATIONS[version..] {
+        let tx = conn.unchecked_transaction()?;
+        migration(&tx)?;
+        version += 1;
+        MetaRepo::set(&tx, SCHEMA_VERSION_KEY, &version.to_string())?;
+        tx.commit()?;
+    }
+
+    seed_builtins(conn)?;
+    Ok(())
+}
+
+/// The recorded schema version. A database from before versioning counts as
+/// version 1, after creating any base table it predates; an empty one is 0.
+fn schema_version(conn: &Connection) -> Result<usize> {
+    if table_exists(conn, "app_meta")?
+        && let Some(value) = MetaRepo::get(conn, SCHEMA_VERSION_KEY)?
+    {
+        return value
+            .parse()
+            .map_err(|_| anyhow!("Invalid schema_version '{}' in app_meta", value));
+    }
+    if table_exists(conn, "prayers")? {
+        base_schema(conn)?;
+        return Ok(1);
+    }
+    Ok(0)
+}
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get(0),
+    )?)
+}
+
+/// Version 1: every table, as created before versioning.
+fn base_schema(conn: &Connection) -> Result<()> {
     conn.execute_batch("
         CREATE TABLE IF NOT EXISTS prayers (
             id           INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -60,30 +131,186 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
             note  TEXT
         );
 
+        CREATE TABLE IF NOT EXISTS quran_reading_log (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            date        TEXT NOT NULL,
+            surah       INTEGER NOT NULL CHECK(surah BETWEEN 1 AND 114),
+            ayah_start  INTEGER NOT NULL,
+            ayah_end    INTEGER NOT NULL,
+            pages       REAL NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS qiyam_log (
+            night_date     TEXT PRIMARY KEY,
+            prayed_at      TEXT NOT NULL,
+            in_last_third  INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS nafl_log (
+            name       TEXT NOT NULL,
+            date       TEXT NOT NULL,
+            prayed_at  TEXT NOT NULL,
+            PRIMARY KEY (name, date)
+        );
+
+        CREATE TABLE IF NOT EXISTS fasting_log (
+            date         TEXT PRIMARY KEY,
+            recorded_at  TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS eid_log (
+            hijri_year  INTEGER NOT NULL,
+            eid         TEXT NOT NULL CHECK(eid IN ('fitr','adha')),
+            date        TEXT NOT NULL,
+            attended    INTEGER NOT NULL,
+            PRIMARY KEY (hijri_year, eid)
+        );
+
+        CREATE TABLE IF NOT EXISTS jummah_log (
+            date      TEXT PRIMARY KEY,
+            attended  INTEGER NOT NULL
+        );
+
         CREATE TABLE IF NOT EXISTS app_meta (
             key   TEXT PRIMARY KEY,
             value TEXT
         );
     ")?;
+    Ok(())
+}
 
-    seed_builtins(conn)?;
+/// Free-text category for grouping adhkar (e.g. "morning", "travel").
+/// `category` keeps recording whether a dhikr is builtin or custom.
+fn add_dhikr_tag(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "dhikr_definitions", "tag", "TEXT")
+}
+
+/// Prayer time as computed when the row was created (`[day] snapshot_times`).
+/// Written once and never updated, unlike prayer_times_cache.
+fn add_scheduled_time(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "prayers", "scheduled_time", "TEXT")
+}
+
+/// Local time a prayer was marked done, for on-time stats. Left NULL when
+/// the mark was a backfill rather than made on the day.
+fn add_marked_at(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "prayers", "marked_at", "TEXT")
+}
+
+/// Imsak, cached beside the prayer times. Rows cached before it existed
+/// get Fajr minus the default `imsak_minutes`.
+fn add_cached_imsak(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "prayer_times_cache", "imsak", "TEXT")?;
+    conn.execute(
+        "UPDATE prayer_times_cache SET imsak = strftime('%H:%M', fajr, '-10 minutes')
+         WHERE imsak IS NULL",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Days loaded from a published timetable by `sujood times import`,
+/// which a settings change must not throw away under `source = "file"`.
+fn add_imported_flag(conn: &Connection) -> Result<()> {
+    add_column_if_missing(conn, "prayer_times_cache", "imported", "INTEGER NOT NULL DEFAULT 0")
+}
+
+/// Indexes for the date-range reads behind stats, streaks and history. The
+/// unique keys on prayers and dhikr_log lead with another column, so they
+/// can't serve a range on date; quran_log's unique date already can.
+fn add_date_indexes(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_prayers_date ON prayers(date);
+         CREATE INDEX IF NOT EXISTS idx_dhikr_log_date ON dhikr_log(date);
+         CREATE INDEX IF NOT EXISTS idx_qada_queue_original_date ON qada_queue(original_date, completed);",
+    )?;
+    Ok(())
+}
+
+/// Steps that add a column check for it first: a database from before
+/// versioning may already have some of them.
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let exists: bool = conn.query_row(
+        &format!("SELECT COUNT(*) > 0 FROM pragma_table_info('{}') WHERE name = ?1", table),
+        [column],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {};", table, column, decl))?;
+    }
     Ok(())
 }
 
 fn seed_builtins(conn: &Connection) -> Result<()> {
     let builtins = [
-        ("Morning Adhkar", "checkbox", "daily", 1, 0),
-        ("Evening Adhkar", "checkbox", "daily", 1, 1),
-        ("Post-Salah Tasbih", "counter", "daily", 99, 2),
+        ("Morning Adhkar", "checkbox", "daily", 1, 0, "morning"),
+        ("Evening Adhkar", "checkbox", "daily", 1, 1, "evening"),
+        ("Post-Salah Tasbih", "counter", "daily", 99, 2, "after-salah"),
     ];
 
-    for (name, dhikr_type, freq, target, order) in &builtins {
+    for (name, dhikr_type, freq, target, order, tag) in &builtins {
         conn.execute(
             "INSERT OR IGNORE INTO dhikr_definitions
-                (name, dhikr_type, frequency, target_count, category, sort_order, active)
-             VALUES (?1, ?2, ?3, ?4, 'builtin', ?5, 1)",
-            rusqlite::params![name, dhikr_type, freq, target, order],
+                (name, dhikr_type, frequency, target_count, category, sort_order, active, tag)
+             VALUES (?1, ?2, ?3, ?4, 'builtin', ?5, 1, ?6)",
+            rusqlite::params![name, dhikr_type, freq, target, order, tag],
+        )?;
+        // Backfill tags on databases seeded before tags existed
+        conn.execute(
+            "UPDATE dhikr_definitions SET tag = ?2
+             WHERE name = ?1 AND category = 'builtin' AND tag IS NULL",
+            rusqlite::params![name, tag],
         )?;
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rusqlite::params;
+
+    /// What SQLite plans for `sql`, one detail line per step.
+    fn query_plan(conn: &Connection, sql: &str) -> String {
+        let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", sql)).unwrap();
+        let details = stmt
+            .query_map(["2026-01-01", "2026-01-31"], |row| row.get::<_, String>(3))
+            .unwrap()
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .unwrap();
+        details.join("\n")
+    }
+
+    #[test]
+    fn date_range_reads_use_the_date_indexes() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let tx = conn.unchecked_transaction().unwrap();
+        let first = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        for day in first.iter_days().take(2500) {
+            let date = day.format("%Y-%m-%d").to_string();
+            for prayer in ["fajr", "zuhr", "asr", "maghrib", "isha"] {
+                tx.execute(
+                    "INSERT INTO prayers (prayer_type, date, status) VALUES (?1, ?2, 'done')",
+                    params![prayer, date],
+                )
+                .unwrap();
+            }
+        }
+        tx.commit().unwrap();
+        conn.execute_batch("ANALYZE").unwrap();
+
+        let prayers = query_plan(
+            &conn,
+            "SELECT date, COUNT(*) FROM prayers WHERE date >= ?1 AND date <= ?2 AND is_qada = 0
+             GROUP BY date",
+        );
+        assert!(prayers.contains("idx_prayers_date"), "{prayers}");
+
+        let dhikr =
+            query_plan(&conn, "SELECT * FROM dhikr_log WHERE date >= ?1 AND date <= ?2");
+        assert!(dhikr.contains("idx_dhikr_log_date"), "{dhikr}");
+    }
+}
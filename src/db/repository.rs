@@ -1,17 +1,23 @@
 use anyhow::{anyhow, Result};
-use chrono::NaiveTime;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
 use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{BTreeMap, BTreeSet};
 use std::str::FromStr;
 
+use crate::config::StreakConfig;
+use crate::utils::date::{date_str, last_friday, week_start, DATETIME_FMT, DATE_FMT};
 use crate::models::{
-    DailyStats, DhikrCategory, DhikrDef, DhikrFrequency, DhikrLog, DhikrType, Prayer,
-    PrayerStatus, PrayerType, QadaEntry, Streak,
+    DailyStats, DhikrCategory, DhikrDef, DhikrFrequency, DhikrLog, DhikrType, Eid, EidEntry, Goal,
+    JummahEntry, Khatm, Nafl, OnTime, Prayer, PrayerStatus, PrayerType, QadaEntry, QadaPace, QiyamEntry,
+    StatsRange,
+    StatsView, Streak, YearReview,
 };
 
 // ─── Cached prayer times ────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
 pub struct CachedTimes {
+    pub imsak: NaiveTime,
     pub fajr: NaiveTime,
     pub sunrise: NaiveTime,
     pub zuhr: NaiveTime,
@@ -24,13 +30,17 @@ fn parse_time(s: &str) -> Result<NaiveTime> {
     NaiveTime::parse_from_str(s, "%H:%M").map_err(|e| anyhow!("Bad time '{}': {}", s, e))
 }
 
+fn parse_datetime(s: &str) -> Result<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, DATETIME_FMT).map_err(|e| anyhow!("Bad time '{}': {}", s, e))
+}
+
 pub struct CacheRepo;
 
 impl CacheRepo {
     pub fn get_times_for_date(conn: &Connection, date: &str) -> Result<Option<CachedTimes>> {
         let row = conn
             .query_row(
-                "SELECT fajr, sunrise, zuhr, asr, maghrib, isha FROM prayer_times_cache WHERE date = ?1",
+                "SELECT imsak, fajr, sunrise, zuhr, asr, maghrib, isha FROM prayer_times_cache WHERE date = ?1",
                 params![date],
                 |row| {
                     Ok((
@@ -40,6 +50,7 @@ impl CacheRepo {
                         row.get::<_, String>(3)?,
                         row.get::<_, String>(4)?,
                         row.get::<_, String>(5)?,
+                        row.get::<_, String>(6)?,
                     ))
                 },
             )
@@ -47,7 +58,8 @@ impl CacheRepo {
 
         match row {
             None => Ok(None),
-            Some((fajr, sunrise, zuhr, asr, maghrib, isha)) => Ok(Some(CachedTimes {
+            Some((imsak, fajr, sunrise, zuhr, asr, maghrib, isha)) => Ok(Some(CachedTimes {
+                imsak: parse_time(&imsak)?,
                 fajr: parse_time(&fajr)?,
                 sunrise: parse_time(&sunrise)?,
                 zuhr: parse_time(&zuhr)?,
@@ -63,10 +75,35 @@ impl CacheRepo {
         Ok(())
     }
 
+    /// Drop calculated days but keep an imported timetable.
+    pub fn clear_computed(conn: &Connection) -> Result<()> {
+        conn.execute("DELETE FROM prayer_times_cache WHERE imported = 0", [])?;
+        Ok(())
+    }
+
+    /// Drop calculated days before `before`, returning how many went. Imported
+    /// timetable days are kept since they can't be recalculated.
+    pub fn prune_computed_before(conn: &Connection, before: &str) -> Result<usize> {
+        Ok(conn.execute(
+            "DELETE FROM prayer_times_cache WHERE imported = 0 AND date < ?1",
+            params![before],
+        )?)
+    }
+
     pub fn store_times(conn: &Connection, date: &str, times: &CachedTimes) -> Result<()> {
+        Self::store(conn, date, times, false)
+    }
+
+    /// Store a day from a published timetable, replacing any calculated times.
+    pub fn store_imported(conn: &Connection, date: &str, times: &CachedTimes) -> Result<()> {
+        Self::store(conn, date, times, true)
+    }
+
+    fn store(conn: &Connection, date: &str, times: &CachedTimes, imported: bool) -> Result<()> {
         conn.execute(
-            "INSERT OR REPLACE INTO prayer_times_cache (date, fajr, sunrise, zuhr, asr, maghrib, isha)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT OR REPLACE INTO prayer_times_cache
+                (date, fajr, sunrise, zuhr, asr, maghrib, isha, imsak, imported)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![
                 date,
                 times.fajr.format("%H:%M").to_string(),
@@ -75,6 +112,8 @@ impl CacheRepo {
                 times.asr.format("%H:%M").to_string(),
                 times.maghrib.format("%H:%M").to_string(),
                 times.isha.format("%H:%M").to_string(),
+                times.imsak.format("%H:%M").to_string(),
+                imported,
             ],
         )?;
         Ok(())
@@ -87,7 +126,7 @@ pub struct PrayerRepo;
 
 impl PrayerRepo {
     /// Ensure a row exists for each prayer type for the given date (status='pending')
-    pub fn ensure_today_rows(conn: &Connection, date: &str) -> Result<()> {
+    pub fn ensure_rows_for_date(conn: &Connection, date: &str) -> Result<()> {
         for pt in PrayerType::all() {
             conn.execute(
                 "INSERT OR IGNORE INTO prayers (prayer_type, date, status, is_qada)
@@ -100,7 +139,7 @@ impl PrayerRepo {
 
     pub fn get_by_date(conn: &Connection, date: &str) -> Result<Vec<Prayer>> {
         let mut stmt = conn.prepare(
-            "SELECT id, prayer_type, date, status, is_qada, note
+            "SELECT id, prayer_type, date, status, is_qada, note, scheduled_time, marked_at
              FROM prayers WHERE date = ?1 AND is_qada = 0
              ORDER BY CASE prayer_type
                WHEN 'fajr' THEN 1 WHEN 'zuhr' THEN 2 WHEN 'asr' THEN 3
@@ -115,12 +154,14 @@ impl PrayerRepo {
                 row.get::<_, String>(3)?,
                 row.get::<_, i32>(4)?,
                 row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+                row.get::<_, Option<String>>(7)?,
             ))
         })?;
 
         let mut result = Vec::new();
         for p in prayers {
-            let (id, prayer_type, date, status, is_qada, note) = p?;
+            let (id, prayer_type, date, status, is_qada, note, scheduled, marked) = p?;
             result.push(Prayer {
                 id: Some(id),
                 prayer_type: PrayerType::from_str(&prayer_type)
@@ -131,27 +172,66 @@ impl PrayerRepo {
                 is_qada: is_qada != 0,
                 note,
                 time: None,
+                scheduled_time: scheduled.as_deref().map(parse_time).transpose()?,
+                marked_at: marked.as_deref().map(parse_datetime).transpose()?,
             });
         }
         Ok(result)
     }
 
+    /// Record the computed time on `date`'s rows. Rows that already carry a
+    /// snapshot keep it, so later config changes don't rewrite history.
+    pub fn snapshot_times(
+        conn: &Connection,
+        date: &str,
+        times: &[(PrayerType, NaiveTime)],
+    ) -> Result<()> {
+        for (pt, time) in times {
+            conn.execute(
+                "UPDATE prayers SET scheduled_time = ?1
+                 WHERE prayer_type = ?2 AND date = ?3 AND is_qada = 0 AND scheduled_time IS NULL",
+                params![time.format("%H:%M").to_string(), pt.as_str(), date],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Set a prayer's status. `marked_at` is the local time of a live mark,
+    /// kept only for `done` so on-time stats can judge it; pass None when
+    /// backfilling so a late entry isn't counted as a late prayer. Marking an
+    /// already-done prayer again keeps its first time.
     pub fn mark_status(
         conn: &Connection,
         prayer_type: &str,
         date: &str,
         status: &str,
+        marked_at: Option<NaiveDateTime>,
     ) -> Result<()> {
+        let marked_at = marked_at
+            .filter(|_| status == "done")
+            .map(|t| t.format(DATETIME_FMT).to_string());
         conn.execute(
-            "UPDATE prayers SET status = ?1 WHERE prayer_type = ?2 AND date = ?3 AND is_qada = 0",
-            params![status, prayer_type, date],
+            "UPDATE prayers
+             SET status = ?1,
+                 marked_at = CASE WHEN ?1 = 'done' AND status = 'done' THEN marked_at ELSE ?4 END
+             WHERE prayer_type = ?2 AND date = ?3 AND is_qada = 0",
+            params![status, prayer_type, date, marked_at],
+        )?;
+        Ok(())
+    }
+
+    /// Attach a short note to a prayer, or clear it with `None`.
+    pub fn set_note(conn: &Connection, prayer_type: &str, date: &str, note: Option<&str>) -> Result<()> {
+        conn.execute(
+            "UPDATE prayers SET note = ?1 WHERE prayer_type = ?2 AND date = ?3 AND is_qada = 0",
+            params![note, prayer_type, date],
         )?;
         Ok(())
     }
 
     pub fn get_date_range(conn: &Connection, start: &str, end: &str) -> Result<Vec<Prayer>> {
         let mut stmt = conn.prepare(
-            "SELECT id, prayer_type, date, status, is_qada, note
+            "SELECT id, prayer_type, date, status, is_qada, note, scheduled_time, marked_at
              FROM prayers WHERE date >= ?1 AND date <= ?2 AND is_qada = 0
              ORDER BY date, CASE prayer_type
                WHEN 'fajr' THEN 1 WHEN 'zuhr' THEN 2 WHEN 'asr' THEN 3
@@ -166,12 +246,14 @@ impl PrayerRepo {
                 row.get::<_, String>(3)?,
                 row.get::<_, i32>(4)?,
                 row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+                row.get::<_, Option<String>>(7)?,
             ))
         })?;
 
         let mut result = Vec::new();
         for r in rows {
-            let (id, prayer_type, date, status, is_qada, note) = r?;
+            let (id, prayer_type, date, status, is_qada, note, scheduled, marked) = r?;
             result.push(Prayer {
                 id: Some(id),
                 prayer_type: PrayerType::from_str(&prayer_type)
@@ -182,6 +264,8 @@ impl PrayerRepo {
                 is_qada: is_qada != 0,
                 note,
                 time: None,
+                scheduled_time: scheduled.as_deref().map(parse_time).transpose()?,
+                marked_at: marked.as_deref().map(parse_datetime).transpose()?,
             });
         }
         Ok(result)
@@ -194,10 +278,20 @@ pub struct DhikrRepo;
 
 impl DhikrRepo {
     pub fn get_active_definitions(conn: &Connection) -> Result<Vec<DhikrDef>> {
-        let mut stmt = conn.prepare(
-            "SELECT id, name, dhikr_type, frequency, target_count, category, sort_order
-             FROM dhikr_definitions WHERE active = 1 ORDER BY sort_order, id",
-        )?;
+        Self::query_definitions(conn, "WHERE active = 1")
+    }
+
+    /// Every dhikr, deactivated ones included.
+    pub fn get_all_definitions(conn: &Connection) -> Result<Vec<DhikrDef>> {
+        Self::query_definitions(conn, "")
+    }
+
+    fn query_definitions(conn: &Connection, filter: &str) -> Result<Vec<DhikrDef>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, name, dhikr_type, frequency, target_count, category, sort_order, tag, active
+             FROM dhikr_definitions {} ORDER BY sort_order, id",
+            filter
+        ))?;
 
         let rows = stmt.query_map([], |row| {
             Ok((
@@ -208,12 +302,15 @@ impl DhikrRepo {
                 row.get::<_, i32>(4)?,
                 row.get::<_, String>(5)?,
                 row.get::<_, i32>(6)?,
+                row.get::<_, Option<String>>(7)?,
+                row.get::<_, bool>(8)?,
             ))
         })?;
 
         let mut result = Vec::new();
         for r in rows {
-            let (id, name, dhikr_type, frequency, target_count, category, sort_order) = r?;
+            let (id, name, dhikr_type, frequency, target_count, category, sort_order, tag, active) =
+                r?;
             let dhikr_type = match dhikr_type.as_str() {
                 "checkbox" => DhikrType::Checkbox,
                 _ => DhikrType::Counter,
@@ -234,18 +331,29 @@ impl DhikrRepo {
                 target_count,
                 category,
                 sort_order,
-                active: true,
+                active,
+                tag,
             });
         }
         Ok(result)
     }
 
+    /// Each dhikr's log entry in effect on `date`. Daily adhkar use that day's
+    /// row; weekly ones use any row in the containing week, preferring the
+    /// week-start row that `DhikrDef::log_date` writes to.
     pub fn get_log_for_date(conn: &Connection, date: &str) -> Result<Vec<DhikrLog>> {
+        let start = week_start(NaiveDate::parse_from_str(date, DATE_FMT)?);
+        let week_from = date_str(start);
+        let week_to = date_str(start + chrono::Duration::days(6));
         let mut stmt = conn.prepare(
-            "SELECT id, dhikr_id, date, count, completed FROM dhikr_log WHERE date = ?1",
+            "SELECT l.id, l.dhikr_id, l.date, l.count, l.completed
+             FROM dhikr_log l JOIN dhikr_definitions d ON d.id = l.dhikr_id
+             WHERE (d.frequency = 'weekly' AND l.date BETWEEN ?2 AND ?3)
+                OR (d.frequency <> 'weekly' AND l.date = ?1)
+             ORDER BY l.dhikr_id, l.date = ?2 DESC, l.date DESC",
         )?;
 
-        let rows = stmt.query_map(params![date], |row| {
+        let rows = stmt.query_map(params![date, week_from, week_to], |row| {
             Ok(DhikrLog {
                 id: Some(row.get::<_, i64>(0)?),
                 dhikr_id: row.get::<_, i64>(1)?,
@@ -255,6 +363,37 @@ impl DhikrRepo {
             })
         })?;
 
+        let mut logs: Vec<DhikrLog> = Vec::new();
+        for log in rows {
+            let log = log?;
+            if logs.last().is_none_or(|l| l.dhikr_id != log.dhikr_id) {
+                logs.push(log);
+            }
+        }
+        Ok(logs)
+    }
+
+    /// One dhikr's log rows from `start` to `end` inclusive, oldest first.
+    pub fn log_range(
+        conn: &Connection,
+        dhikr_id: i64,
+        start: &str,
+        end: &str,
+    ) -> Result<Vec<DhikrLog>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, dhikr_id, date, count, completed FROM dhikr_log
+             WHERE dhikr_id = ?1 AND date >= ?2 AND date <= ?3
+             ORDER BY date",
+        )?;
+        let rows = stmt.query_map(params![dhikr_id, start, end], |row| {
+            Ok(DhikrLog {
+                id: Some(row.get::<_, i64>(0)?),
+                dhikr_id: row.get::<_, i64>(1)?,
+                date: row.get::<_, String>(2)?,
+                count: row.get::<_, i32>(3)?,
+                completed: row.get::<_, i32>(4)? != 0,
+            })
+        })?;
         rows.collect::<rusqlite::Result<Vec<_>>>()
             .map_err(anyhow::Error::from)
     }
@@ -275,12 +414,45 @@ impl DhikrRepo {
         Ok(())
     }
 
+    /// Mark `def` complete on every day from `from` to `to` inclusive, returning
+    /// how many log entries were written. Counters are raised to their target;
+    /// weekly adhkar get one entry per week touched.
+    pub fn upsert_log_range(
+        conn: &Connection,
+        def: &DhikrDef,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<usize> {
+        let count = match def.dhikr_type {
+            DhikrType::Checkbox => 1,
+            DhikrType::Counter => def.target_count,
+        };
+        let dates: BTreeSet<String> = from
+            .iter_days()
+            .take_while(|d| *d <= to)
+            .map(|d| def.log_date(&date_str(d)))
+            .collect();
+
+        let tx = conn.unchecked_transaction()?;
+        for date in &dates {
+            tx.execute(
+                "INSERT INTO dhikr_log (dhikr_id, date, count, completed)
+                 VALUES (?1, ?2, ?3, 1)
+                 ON CONFLICT(dhikr_id, date) DO UPDATE SET count = MAX(count, ?3), completed = 1",
+                params![def.id, date, count],
+            )?;
+        }
+        tx.commit()?;
+        Ok(dates.len())
+    }
+
     pub fn add_custom(
         conn: &Connection,
         name: &str,
         dhikr_type: &str,
         target: i32,
         frequency: &str,
+        tag: Option<&str>,
     ) -> Result<()> {
         // Get max sort_order for custom
         let max_order: i32 = conn
@@ -292,13 +464,92 @@ impl DhikrRepo {
             .unwrap_or(100);
 
         conn.execute(
-            "INSERT INTO dhikr_definitions (name, dhikr_type, frequency, target_count, category, sort_order, active)
-             VALUES (?1, ?2, ?3, ?4, 'custom', ?5, 1)",
-            params![name, dhikr_type, frequency, target, max_order + 1],
+            "INSERT INTO dhikr_definitions (name, dhikr_type, frequency, target_count, category, sort_order, active, tag)
+             VALUES (?1, ?2, ?3, ?4, 'custom', ?5, 1, ?6)",
+            params![name, dhikr_type, frequency, target, max_order + 1, tag],
         )?;
         Ok(())
     }
 
+    /// Change a dhikr in place, keeping its log history. The category never
+    /// changes. Built-ins are seeded by name on every start, so renaming one
+    /// would bring the original back beside it; their name is kept.
+    pub fn update_definition(
+        conn: &Connection,
+        id: i64,
+        name: &str,
+        dhikr_type: &DhikrType,
+        target: i32,
+        frequency: &DhikrFrequency,
+    ) -> Result<()> {
+        let current: Option<(String, String)> = conn
+            .query_row(
+                "SELECT name, category FROM dhikr_definitions WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((old_name, category)) = current else {
+            return Err(anyhow!("No dhikr with id {}", id));
+        };
+        if category == "builtin" && name != old_name {
+            return Err(anyhow!(
+                "Built-in adhkar keep their name — sujood adds '{}' back by that name on start",
+                old_name
+            ));
+        }
+
+        let taken: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM dhikr_definitions WHERE name = ?1 COLLATE NOCASE AND id <> ?2",
+            params![name, id],
+            |row| row.get(0),
+        )?;
+        if taken {
+            return Err(anyhow!("A dhikr named '{}' already exists", name));
+        }
+
+        conn.execute(
+            "UPDATE dhikr_definitions
+             SET name = ?2, dhikr_type = ?3, frequency = ?4, target_count = ?5
+             WHERE id = ?1",
+            params![id, name, dhikr_type.as_str(), frequency.as_str(), target],
+        )?;
+        Ok(())
+    }
+
+    /// Hide a dhikr from lists while keeping its log history.
+    pub fn deactivate(conn: &Connection, id: i64) -> Result<()> {
+        conn.execute(
+            "UPDATE dhikr_definitions SET active = 0 WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(())
+    }
+
+    /// Permanently delete a custom dhikr and its log rows. Built-ins are refused.
+    pub fn delete_custom(conn: &Connection, id: i64) -> Result<()> {
+        let category: Option<String> = conn
+            .query_row(
+                "SELECT category FROM dhikr_definitions WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match category.as_deref() {
+            None => return Err(anyhow!("No dhikr with id {}", id)),
+            Some("custom") => {}
+            Some(_) => {
+                return Err(anyhow!("Built-in adhkar can't be deleted — use --deactivate to hide it"))
+            }
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM dhikr_log WHERE dhikr_id = ?1", params![id])?;
+        tx.execute("DELETE FROM dhikr_definitions WHERE id = ?1", params![id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn find_by_name(conn: &Connection, name: &str) -> Result<Option<DhikrDef>> {
         let defs = Self::get_active_definitions(conn)?;
         Ok(defs.into_iter().find(|d| d.name.to_lowercase() == name.to_lowercase()))
@@ -309,6 +560,11 @@ impl DhikrRepo {
 
 pub struct QadaRepo;
 
+/// app_meta flag set once legacy UTC completion times have been converted.
+const QADA_LOCAL_TIME_KEY: &str = "qada_completed_at_local";
+/// Days of made-up qada the time-to-clear estimate averages over.
+const QADA_PACE_DAYS: i64 = 30;
+
 impl QadaRepo {
     pub fn get_queue(conn: &Connection) -> Result<Vec<QadaEntry>> {
         let mut stmt = conn.prepare(
@@ -342,6 +598,34 @@ impl QadaRepo {
         Ok(result)
     }
 
+    pub fn get(conn: &Connection, id: i64) -> Result<Option<QadaEntry>> {
+        conn.query_row(
+            "SELECT id, prayer_type, original_date, completed, completed_at
+             FROM qada_queue WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i32>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            },
+        )
+        .optional()?
+        .map(|(id, prayer_type, original_date, completed, completed_at)| {
+            Ok(QadaEntry {
+                id,
+                prayer_type: PrayerType::from_str(&prayer_type)?,
+                original_date,
+                completed: completed != 0,
+                completed_at,
+            })
+        })
+        .transpose()
+    }
+
     pub fn add_entry(conn: &Connection, prayer_type: &str, original_date: &str) -> Result<()> {
         conn.execute(
             "INSERT INTO qada_queue (prayer_type, original_date, completed) VALUES (?1, ?2, 0)",
@@ -350,7 +634,41 @@ impl QadaRepo {
         Ok(())
     }
 
-    pub fn complete_oldest(conn: &Connection) -> Result<bool> {
+    /// Queue each of `prayers` on every day from `from` to `to` inclusive,
+    /// returning how many entries were added. Prayers marked done that day,
+    /// or already queued for it (pending or made up), are skipped.
+    pub fn add_range(
+        conn: &Connection,
+        from: NaiveDate,
+        to: NaiveDate,
+        prayers: &[PrayerType],
+    ) -> Result<usize> {
+        let tx = conn.unchecked_transaction()?;
+        let mut added = 0;
+        for day in from.iter_days().take_while(|d| *d <= to) {
+            let date = date_str(day);
+            for pt in prayers {
+                added += tx.execute(
+                    "INSERT INTO qada_queue (prayer_type, original_date, completed)
+                     SELECT ?1, ?2, 0
+                     WHERE NOT EXISTS (
+                         SELECT 1 FROM prayers
+                         WHERE prayer_type = ?1 AND date = ?2 AND is_qada = 0 AND status = 'done'
+                     )
+                     AND NOT EXISTS (
+                         SELECT 1 FROM qada_queue WHERE prayer_type = ?1 AND original_date = ?2
+                     )",
+                    params![pt.as_str(), date],
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(added)
+    }
+
+    /// Mark the oldest pending entry done, returning its id. `completed_at` is
+    /// a local `DATETIME_FMT` timestamp, matching every other stored time.
+    pub fn complete_oldest(conn: &Connection, completed_at: &str) -> Result<Option<i64>> {
         let oldest_id: Option<i64> = conn
             .query_row(
                 "SELECT id FROM qada_queue WHERE completed = 0 ORDER BY original_date, id LIMIT 1",
@@ -360,17 +678,120 @@ impl QadaRepo {
             .optional()?;
 
         match oldest_id {
-            None => Ok(false),
+            None => Ok(None),
             Some(id) => {
-                conn.execute(
-                    "UPDATE qada_queue SET completed = 1, completed_at = datetime('now') WHERE id = ?1",
-                    params![id],
-                )?;
-                Ok(true)
+                Self::complete_by_id(conn, id, completed_at)?;
+                Ok(Some(id))
             }
         }
     }
 
+    /// Mark a specific pending entry done. Returns false when the id doesn't
+    /// exist or was already completed.
+    pub fn complete_by_id(conn: &Connection, id: i64, completed_at: &str) -> Result<bool> {
+        let updated = conn.execute(
+            "UPDATE qada_queue SET completed = 1, completed_at = ?1 WHERE id = ?2 AND completed = 0",
+            params![completed_at, id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Drop the still-owed entries for `prayer_type` on `original_date`, as
+    /// when a prayer marked missed turns out to have been prayed. Returns
+    /// how many were removed; made-up entries are left alone.
+    pub fn remove_for(conn: &Connection, prayer_type: &str, original_date: &str) -> Result<usize> {
+        Ok(conn.execute(
+            "DELETE FROM qada_queue
+             WHERE prayer_type = ?1 AND original_date = ?2 AND completed = 0",
+            params![prayer_type, original_date],
+        )?)
+    }
+
+    /// Delete an entry outright. Returns false when no such id exists.
+    pub fn remove_entry(conn: &Connection, id: i64) -> Result<bool> {
+        let removed = conn.execute("DELETE FROM qada_queue WHERE id = ?1", params![id])?;
+        Ok(removed > 0)
+    }
+
+    /// Entries completed between `start` and `end` (inclusive `YYYY-MM-DD`),
+    /// oldest completion first.
+    pub fn completed_in_range(conn: &Connection, start: &str, end: &str) -> Result<Vec<QadaEntry>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, prayer_type, original_date, completed_at
+             FROM qada_queue
+             WHERE completed = 1 AND substr(completed_at, 1, 10) BETWEEN ?1 AND ?2
+             ORDER BY completed_at, id",
+        )?;
+
+        let rows = stmt.query_map(params![start, end], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut result = Vec::new();
+        for r in rows {
+            let (id, prayer_type, original_date, completed_at) = r?;
+            result.push(QadaEntry {
+                id,
+                prayer_type: PrayerType::from_str(&prayer_type)?,
+                original_date,
+                completed: true,
+                completed_at: Some(completed_at),
+            });
+        }
+        Ok(result)
+    }
+
+    /// The most recently completed entry, if any.
+    pub fn last_completed(conn: &Connection) -> Result<Option<QadaEntry>> {
+        conn.query_row(
+            "SELECT id, prayer_type, original_date, completed_at
+             FROM qada_queue WHERE completed = 1 AND completed_at IS NOT NULL
+             ORDER BY completed_at DESC, id DESC LIMIT 1",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            },
+        )
+        .optional()?
+        .map(|(id, prayer_type, original_date, completed_at)| {
+            Ok(QadaEntry {
+                id,
+                prayer_type: PrayerType::from_str(&prayer_type)?,
+                original_date,
+                completed: true,
+                completed_at: Some(completed_at),
+            })
+        })
+        .transpose()
+    }
+
+    /// Completions were once stamped with SQLite's `datetime('now')`, which is
+    /// UTC. Shift those to local time once so all timestamps share a zone.
+    /// This is not a migration step: the shift is the timezone offset of the
+    /// profile that opens the database first, which a step can't know, so it
+    /// runs once the config is loaded and records itself in `app_meta`.
+    pub fn localize_legacy_completions(conn: &Connection, offset_minutes: i32) -> Result<()> {
+        if MetaRepo::get(conn, QADA_LOCAL_TIME_KEY)?.is_some() {
+            return Ok(());
+        }
+        conn.execute(
+            "UPDATE qada_queue SET completed_at = datetime(completed_at, ?1 || ' minutes')
+             WHERE completed_at IS NOT NULL",
+            params![format!("{:+}", offset_minutes)],
+        )?;
+        MetaRepo::set(conn, QADA_LOCAL_TIME_KEY, "1")
+    }
+
     pub fn count_pending(conn: &Connection) -> Result<i64> {
         conn.query_row(
             "SELECT COUNT(*) FROM qada_queue WHERE completed = 0",
@@ -379,6 +800,279 @@ impl QadaRepo {
         )
         .map_err(anyhow::Error::from)
     }
+
+    /// What's owed and the rate qada was made up at over the
+    /// `QADA_PACE_DAYS` ending `today`.
+    pub fn pace(conn: &Connection, today: NaiveDate) -> Result<QadaPace> {
+        let window_start = today - chrono::Duration::days(QADA_PACE_DAYS - 1);
+        let made_up = Self::completed_in_range(conn, &date_str(window_start), &date_str(today))?.len();
+        Ok(QadaPace {
+            owed: Self::count_pending(conn)?,
+            daily_rate: (made_up > 0).then(|| made_up as f64 / QADA_PACE_DAYS as f64),
+            window_days: QADA_PACE_DAYS as u32,
+        })
+    }
+}
+
+// ─── Eid repo ────────────────────────────────────────────────────────────────
+
+pub struct EidRepo;
+
+impl EidRepo {
+    pub fn get(conn: &Connection, hijri_year: i64, eid: Eid) -> Result<Option<EidEntry>> {
+        conn.query_row(
+            "SELECT hijri_year, eid, date, attended FROM eid_log
+             WHERE hijri_year = ?1 AND eid = ?2",
+            params![hijri_year, eid.as_str()],
+            eid_row,
+        )
+        .optional()?
+        .map(eid_entry)
+        .transpose()
+    }
+
+    pub fn record(conn: &Connection, entry: &EidEntry) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO eid_log (hijri_year, eid, date, attended)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![entry.hijri_year, entry.eid.as_str(), entry.date, entry.attended as i32],
+        )?;
+        Ok(())
+    }
+
+    /// Every recorded Eid, most recent first.
+    pub fn history(conn: &Connection) -> Result<Vec<EidEntry>> {
+        let mut stmt = conn.prepare(
+            "SELECT hijri_year, eid, date, attended FROM eid_log ORDER BY date DESC",
+        )?;
+        let rows = stmt.query_map([], eid_row)?;
+
+        let mut result = Vec::new();
+        for r in rows {
+            result.push(eid_entry(r?)?);
+        }
+        Ok(result)
+    }
+}
+
+type EidRow = (i64, String, String, i32);
+
+fn eid_row(row: &rusqlite::Row) -> rusqlite::Result<EidRow> {
+    Ok((
+        row.get::<_, i64>(0)?,
+        row.get::<_, String>(1)?,
+        row.get::<_, String>(2)?,
+        row.get::<_, i32>(3)?,
+    ))
+}
+
+fn eid_entry((hijri_year, eid, date, attended): EidRow) -> Result<EidEntry> {
+    Ok(EidEntry {
+        hijri_year,
+        eid: Eid::from_str(&eid)?,
+        date,
+        attended: attended != 0,
+    })
+}
+
+// ─── Jummah repo ─────────────────────────────────────────────────────────────
+
+pub struct JummahRepo;
+
+impl JummahRepo {
+    pub fn get(conn: &Connection, date: &str) -> Result<Option<JummahEntry>> {
+        conn.query_row(
+            "SELECT date, attended FROM jummah_log WHERE date = ?1",
+            params![date],
+            |row| {
+                Ok(JummahEntry {
+                    date: row.get(0)?,
+                    attended: row.get::<_, i32>(1)? != 0,
+                })
+            },
+        )
+        .optional()
+        .map_err(anyhow::Error::from)
+    }
+
+    pub fn record(conn: &Connection, entry: &JummahEntry) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO jummah_log (date, attended) VALUES (?1, ?2)",
+            params![entry.date, entry.attended as i32],
+        )?;
+        Ok(())
+    }
+
+    /// Consecutive Fridays attended, ending at the last Friday on or before
+    /// `today`. A Friday recorded as missed, or never recorded, breaks it —
+    /// except today's, which may still be attended.
+    pub fn streak(conn: &Connection, today: NaiveDate) -> Result<Streak> {
+        let step = chrono::Duration::days(7);
+        let mut stmt = conn.prepare("SELECT date FROM jummah_log WHERE attended = 1")?;
+        let attended: BTreeSet<NaiveDate> = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter_map(|d| NaiveDate::parse_from_str(&d, DATE_FMT).ok())
+            .map(last_friday)
+            .collect();
+
+        let mut check = last_friday(today);
+        if check == today && !attended.contains(&check) && Self::get(conn, &date_str(today))?.is_none() {
+            check -= step;
+        }
+        let mut current = 0u32;
+        while attended.contains(&check) {
+            current += 1;
+            check -= step;
+        }
+
+        let mut best = 0u32;
+        let mut run = 0u32;
+        let mut prev: Option<NaiveDate> = None;
+        for &date in &attended {
+            run = if prev.is_some_and(|p| p + step == date) { run + 1 } else { 1 };
+            best = best.max(run);
+            prev = Some(date);
+        }
+
+        Ok(Streak { current, best })
+    }
+}
+
+// ─── Nafl repo ───────────────────────────────────────────────────────────────
+
+/// Voluntary prayers by day, kept out of the `prayers` table so they never
+/// reach completion counts or streaks. Tahajjud lives in `qiyam_log` instead.
+pub struct NaflRepo;
+
+impl NaflRepo {
+    /// The nafl recorded on `date`.
+    pub fn prayed_on(conn: &Connection, date: &str) -> Result<Vec<Nafl>> {
+        let mut stmt = conn.prepare("SELECT name FROM nafl_log WHERE date = ?1")?;
+        let names = stmt
+            .query_map(params![date], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(names.iter().filter_map(|n| Nafl::from_str(n).ok()).collect())
+    }
+
+    pub fn mark(conn: &Connection, nafl: Nafl, date: &str, prayed_at: &str) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO nafl_log (name, date, prayed_at) VALUES (?1, ?2, ?3)",
+            params![nafl.as_str(), date, prayed_at],
+        )?;
+        Ok(())
+    }
+
+    pub fn unmark(conn: &Connection, nafl: Nafl, date: &str) -> Result<()> {
+        conn.execute(
+            "DELETE FROM nafl_log WHERE name = ?1 AND date = ?2",
+            params![nafl.as_str(), date],
+        )?;
+        Ok(())
+    }
+}
+
+// ─── Fasting repo ────────────────────────────────────────────────────────────
+
+/// Voluntary fasts kept, one row per day fasted. Independent of prayer stats.
+pub struct FastingRepo;
+
+impl FastingRepo {
+    pub fn fasted_on(conn: &Connection, date: &str) -> Result<bool> {
+        Ok(conn
+            .query_row("SELECT 1 FROM fasting_log WHERE date = ?1", params![date], |_| Ok(()))
+            .optional()?
+            .is_some())
+    }
+
+    pub fn mark(conn: &Connection, date: &str, recorded_at: &str) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO fasting_log (date, recorded_at) VALUES (?1, ?2)",
+            params![date, recorded_at],
+        )?;
+        Ok(())
+    }
+
+    pub fn unmark(conn: &Connection, date: &str) -> Result<()> {
+        conn.execute("DELETE FROM fasting_log WHERE date = ?1", params![date])?;
+        Ok(())
+    }
+
+    /// Days fasted from `from` through `to`, inclusive.
+    pub fn count_between(conn: &Connection, from: &str, to: &str) -> Result<u32> {
+        Ok(conn.query_row(
+            "SELECT COUNT(*) FROM fasting_log WHERE date >= ?1 AND date <= ?2",
+            params![from, to],
+            |row| row.get(0),
+        )?)
+    }
+}
+
+// ─── Qiyam repo ──────────────────────────────────────────────────────────────
+
+pub struct QiyamRepo;
+
+impl QiyamRepo {
+    pub fn get(conn: &Connection, night_date: &str) -> Result<Option<QiyamEntry>> {
+        conn.query_row(
+            "SELECT night_date, prayed_at, in_last_third FROM qiyam_log WHERE night_date = ?1",
+            params![night_date],
+            |row| {
+                Ok(QiyamEntry {
+                    night_date: row.get(0)?,
+                    prayed_at: row.get(1)?,
+                    in_last_third: row.get::<_, i32>(2)? != 0,
+                })
+            },
+        )
+        .optional()
+        .map_err(anyhow::Error::from)
+    }
+
+    pub fn mark(conn: &Connection, entry: &QiyamEntry) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO qiyam_log (night_date, prayed_at, in_last_third)
+             VALUES (?1, ?2, ?3)",
+            params![entry.night_date, entry.prayed_at, entry.in_last_third as i32],
+        )?;
+        Ok(())
+    }
+
+    pub fn unmark(conn: &Connection, night_date: &str) -> Result<()> {
+        conn.execute("DELETE FROM qiyam_log WHERE night_date = ?1", params![night_date])?;
+        Ok(())
+    }
+
+    /// Consecutive nights with qiyam ending at `night`.
+    /// An unmarked `night` doesn't break the streak yet — it may still be prayed.
+    pub fn current_streak(conn: &Connection, night: NaiveDate) -> Result<u32> {
+        let mut stmt = conn.prepare("SELECT night_date FROM qiyam_log")?;
+        let nights: BTreeSet<NaiveDate> = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter_map(|d| NaiveDate::parse_from_str(&d, DATE_FMT).ok())
+            .collect();
+
+        let mut check = if nights.contains(&night) {
+            night
+        } else {
+            match night.pred_opt() {
+                Some(d) => d,
+                None => return Ok(0),
+            }
+        };
+        let mut streak = 0u32;
+        while nights.contains(&check) {
+            streak += 1;
+            check = match check.pred_opt() {
+                Some(d) => d,
+                None => break,
+            };
+        }
+        Ok(streak)
+    }
 }
 
 // ─── Quran repo ──────────────────────────────────────────────────────────────
@@ -387,6 +1081,11 @@ pub struct QuranRepo;
 
 impl QuranRepo {
     pub fn log_pages(conn: &Connection, date: &str, pages: f64) -> Result<()> {
+        Self::remember_undo(conn, date, None)?;
+        Self::add_pages(conn, date, pages)
+    }
+
+    fn add_pages(conn: &Connection, date: &str, pages: f64) -> Result<()> {
         conn.execute(
             "INSERT INTO quran_log (date, pages) VALUES (?1, ?2)
              ON CONFLICT(date) DO UPDATE SET pages = pages + ?2",
@@ -395,6 +1094,76 @@ impl QuranRepo {
         Ok(())
     }
 
+    /// Overwrite the day's total instead of adding to it.
+    pub fn set_pages(conn: &Connection, date: &str, pages: f64) -> Result<()> {
+        Self::remember_undo(conn, date, None)?;
+        conn.execute(
+            "INSERT INTO quran_log (date, pages) VALUES (?1, ?2)
+             ON CONFLICT(date) DO UPDATE SET pages = ?2",
+            params![date, pages],
+        )?;
+        Ok(())
+    }
+
+    /// Revert the last add or set, returning the day and the total it went
+    /// back to. Only one step is kept, so a second undo returns None.
+    pub fn undo_last(conn: &Connection) -> Result<Option<(String, f64)>> {
+        let Some(raw) = MetaRepo::get(conn, QURAN_UNDO_KEY)? else {
+            return Ok(None);
+        };
+        let mut parts = raw.split(',');
+        let (Some(date), Some(previous)) = (parts.next(), parts.next()) else {
+            return Ok(None);
+        };
+        let previous: f64 = previous.parse()?;
+        let reading_id: Option<i64> = parts.next().and_then(|id| id.parse().ok());
+
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE quran_log SET pages = ?2 WHERE date = ?1",
+            params![date, previous],
+        )?;
+        if let Some(id) = reading_id {
+            tx.execute("DELETE FROM quran_reading_log WHERE id = ?1", params![id])?;
+        }
+        tx.execute("DELETE FROM app_meta WHERE key = ?1", params![QURAN_UNDO_KEY])?;
+        tx.commit()?;
+        Ok(Some((date.to_string(), previous)))
+    }
+
+    /// Save `date`'s current total (and the reading row about to be added,
+    /// if any) so `undo_last` can restore it.
+    fn remember_undo(conn: &Connection, date: &str, reading_id: Option<i64>) -> Result<()> {
+        let previous = Self::get_today(conn, date)?;
+        let mut raw = format!("{},{}", date, previous);
+        if let Some(id) = reading_id {
+            raw.push_str(&format!(",{}", id));
+        }
+        MetaRepo::set(conn, QURAN_UNDO_KEY, &raw)
+    }
+
+    /// Record reading ayahs `start..=end` of `surah`, adding `pages` (its
+    /// approximate page count) to the day's total so page stats include it.
+    pub fn log_range(
+        conn: &Connection,
+        date: &str,
+        surah: usize,
+        start: u16,
+        end: u16,
+        pages: f64,
+    ) -> Result<()> {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO quran_reading_log (date, surah, ayah_start, ayah_end, pages)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![date, surah as i64, start, end, pages],
+        )?;
+        Self::remember_undo(&tx, date, Some(tx.last_insert_rowid()))?;
+        Self::add_pages(&tx, date, pages)?;
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn get_today(conn: &Connection, date: &str) -> Result<f64> {
         conn.query_row(
             "SELECT COALESCE(pages, 0) FROM quran_log WHERE date = ?1",
@@ -406,6 +1175,19 @@ impl QuranRepo {
         .map_err(anyhow::Error::from)
     }
 
+    /// Recently entered page counts, most recent first.
+    pub fn get_input_history(conn: &Connection) -> Result<Vec<f64>> {
+        let raw = MetaRepo::get(conn, QURAN_HISTORY_KEY)?.unwrap_or_default();
+        Ok(raw.split(',').filter_map(|v| v.trim().parse().ok()).collect())
+    }
+
+    /// Move `pages` to the front of the input history, keeping at most `limit` entries.
+    pub fn remember_input(conn: &Connection, pages: f64, limit: usize) -> Result<()> {
+        let history = push_recent(Self::get_input_history(conn)?, pages, limit);
+        let raw: Vec<String> = history.iter().map(|p| p.to_string()).collect();
+        MetaRepo::set(conn, QURAN_HISTORY_KEY, &raw.join(","))
+    }
+
     pub fn get_weekly_total(conn: &Connection, start_date: &str, end_date: &str) -> Result<f64> {
         conn.query_row(
             "SELECT COALESCE(SUM(pages), 0) FROM quran_log WHERE date >= ?1 AND date <= ?2",
@@ -414,17 +1196,103 @@ impl QuranRepo {
         )
         .map_err(anyhow::Error::from)
     }
+
+    pub fn total_pages_since(conn: &Connection, start_date: &str) -> Result<f64> {
+        conn.query_row(
+            "SELECT COALESCE(SUM(pages), 0) FROM quran_log WHERE date >= ?1",
+            params![start_date],
+            |row| row.get(0),
+        )
+        .map_err(anyhow::Error::from)
+    }
+
+    /// Progress through the current khatm as of `today`. Until the first
+    /// khatm completes, it counts from the earliest logged day.
+    pub fn khatm(conn: &Connection, today: NaiveDate, mushaf_pages: u32) -> Result<Khatm> {
+        let started = match MetaRepo::get(conn, KHATM_START_KEY)? {
+            Some(start) => Some(start),
+            None => conn.query_row("SELECT MIN(date) FROM quran_log", [], |row| row.get(0))?,
+        };
+        let pages = match &started {
+            Some(start) => {
+                let carried: f64 = MetaRepo::get(conn, KHATM_CARRIED_KEY)?
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0.0);
+                (Self::total_pages_since(conn, start)? - carried).max(0.0)
+            }
+            None => 0.0,
+        };
+        let completed = MetaRepo::get(conn, KHATM_COUNT_KEY)?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+
+        let window_start = today - chrono::Duration::days(KHATM_AVERAGE_DAYS - 1);
+        let recent = Self::get_weekly_total(conn, &date_str(window_start), &date_str(today))?;
+
+        Ok(Khatm {
+            pages,
+            mushaf_pages,
+            started,
+            completed,
+            daily_average: recent / KHATM_AVERAGE_DAYS as f64,
+        })
+    }
+
+    /// Call after logging pages on `date`. If that finished the khatm, start
+    /// the next one from `date` — keeping any pages past the last one — and
+    /// return true.
+    pub fn advance_khatm(conn: &Connection, date: NaiveDate, mushaf_pages: u32) -> Result<bool> {
+        let khatm = Self::khatm(conn, date, mushaf_pages)?;
+        if mushaf_pages == 0 || khatm.pages < mushaf_pages as f64 {
+            return Ok(false);
+        }
+        let date = date_str(date);
+        let overflow = khatm.pages - mushaf_pages as f64;
+        let carried = Self::total_pages_since(conn, &date)? - overflow;
+
+        let tx = conn.unchecked_transaction()?;
+        MetaRepo::set(&tx, KHATM_START_KEY, &date)?;
+        MetaRepo::set(&tx, KHATM_CARRIED_KEY, &carried.to_string())?;
+        MetaRepo::set(&tx, KHATM_COUNT_KEY, &(khatm.completed + 1).to_string())?;
+        tx.commit()?;
+        Ok(true)
+    }
+}
+
+const QURAN_HISTORY_KEY: &str = "quran_input_history";
+/// app_meta key for the single-step undo: "date,previous_pages[,reading_id]".
+const QURAN_UNDO_KEY: &str = "quran_undo";
+/// app_meta keys for khatm tracking: the day the current khatm began, pages
+/// logged from that day onward that belong to the previous one, and the
+/// number completed.
+const KHATM_START_KEY: &str = "khatm_start";
+const KHATM_CARRIED_KEY: &str = "khatm_carried_pages";
+const KHATM_COUNT_KEY: &str = "khatm_count";
+/// Days of reading the khatm finish estimate averages over.
+const KHATM_AVERAGE_DAYS: i64 = 14;
+
+/// Most-recent-first list with `value` at the front and no duplicates.
+fn push_recent(mut history: Vec<f64>, value: f64, limit: usize) -> Vec<f64> {
+    history.retain(|v| *v != value);
+    history.insert(0, value);
+    history.truncate(limit);
+    history
 }
 
 // ─── Stats repo ──────────────────────────────────────────────────────────────
 
+/// app_meta key for the last stats range and prayer filter (`StatsView::encode`).
+const STATS_VIEW_KEY: &str = "stats_view";
+
 pub struct StatsRepo;
 
 impl StatsRepo {
+    /// Prayers done per tracked day, optionally counting just one prayer.
     pub fn get_daily_stats_range(
         conn: &Connection,
         start: &str,
         end: &str,
+        prayer: Option<&PrayerType>,
     ) -> Result<Vec<DailyStats>> {
         let mut stmt = conn.prepare(
             "SELECT date,
@@ -432,11 +1300,13 @@ impl StatsRepo {
                     COUNT(*) as total
              FROM prayers
              WHERE date >= ?1 AND date <= ?2 AND is_qada = 0
+               AND (?3 IS NULL OR prayer_type = ?3)
              GROUP BY date
              ORDER BY date",
         )?;
 
-        let rows = stmt.query_map(params![start, end], |row| {
+        let prayer = prayer.map(|p| p.as_str());
+        let rows = stmt.query_map(params![start, end, prayer], |row| {
             Ok(DailyStats {
                 date: row.get(0)?,
                 prayers_done: row.get::<_, i32>(1)? as u8,
@@ -448,75 +1318,348 @@ impl StatsRepo {
             .map_err(anyhow::Error::from)
     }
 
-    pub fn calculate_streak(conn: &Connection) -> Result<Streak> {
-        // Get all dates with all 5 prayers done, ordered desc
+    pub fn calculate_streak(
+        conn: &Connection,
+        config: &StreakConfig,
+        today: NaiveDate,
+    ) -> Result<Streak> {
+        let days = Self::streak_days(conn, config)?;
+        let current = calculate_current_streak(&days, today, config);
+        let best = calculate_best_streak(&days, config);
+
+        Ok(Streak { current, best })
+    }
+
+    /// Whether each tracked day counts toward a streak under `config`.
+    fn streak_days(conn: &Connection, config: &StreakConfig) -> Result<BTreeMap<NaiveDate, bool>> {
+        // Per-date completion for every tracked day (any row counts as tracked).
+        // A forgiving streak also accepts four done with the fifth forgivable.
+        // With `qada_heals`, a prayer whose qada was completed counts as done.
+        let forgivable = if config.forgiving {
+            let names: Vec<&str> = config.forgivable.iter().map(|p| p.as_str()).collect();
+            format!(",{},", names.join(","))
+        } else {
+            String::new()
+        };
         let mut stmt = conn.prepare(
-            "SELECT date FROM prayers
-             WHERE is_qada = 0
-             GROUP BY date
-             HAVING SUM(CASE WHEN status = 'done' THEN 1 ELSE 0 END) >= 5
-             ORDER BY date DESC",
+            "SELECT date,
+                    SUM(ok) >= 5
+                    OR (SUM(ok) >= 4
+                        AND SUM(ok OR instr(?1, ',' || prayer_type || ',') > 0) >= 5)
+             FROM (
+                SELECT p.date, p.prayer_type,
+                       p.status = 'done' OR (?2 AND EXISTS (
+                           SELECT 1 FROM qada_queue q
+                           WHERE q.completed = 1
+                             AND q.prayer_type = p.prayer_type
+                             AND q.original_date = p.date
+                       )) AS ok
+                FROM prayers p
+                WHERE p.is_qada = 0
+             )
+             GROUP BY date",
         )?;
 
-        let dates: Vec<String> = stmt
-            .query_map([], |row| row.get(0))?
-            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let days: BTreeMap<NaiveDate, bool> = stmt
+            .query_map(params![forgivable, config.qada_heals], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter_map(|(date, complete)| {
+                NaiveDate::parse_from_str(&date, DATE_FMT)
+                    .ok()
+                    .map(|d| (d, complete))
+            })
+            .collect();
+        Ok(days)
+    }
 
-        // Calculate current streak (consecutive days ending at today)
-        let today = chrono::Local::now().date_naive();
-        let mut current = 0u32;
-        let mut check_date = today;
-
-        for date_str in &dates {
-            let d = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                .unwrap_or(chrono::NaiveDate::MIN);
-            if d == check_date || d == today {
-                if d == check_date {
-                    current += 1;
-                    check_date = check_date.pred_opt().unwrap_or(check_date);
-                }
-            } else {
-                break;
+    /// Totals for the calendar year `year`, each from one grouped query.
+    pub fn year_review(conn: &Connection, config: &StreakConfig, year: i32) -> Result<YearReview> {
+        let start = NaiveDate::from_ymd_opt(year, 1, 1)
+            .ok_or_else(|| anyhow!("Year {} is out of range", year))?;
+        let end = NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or(start);
+        let (start_str, end_str) = (date_str(start), date_str(end));
+        let mut review = YearReview { year, ..Default::default() };
+
+        let mut stmt = conn.prepare(
+            "SELECT CAST(substr(date, 6, 2) AS INTEGER),
+                    SUM(status = 'done'),
+                    COUNT(*)
+             FROM prayers
+             WHERE is_qada = 0 AND date >= ?1 AND date <= ?2
+             GROUP BY 1",
+        )?;
+        let months = stmt.query_map(params![start_str, end_str], |row| {
+            Ok((row.get::<_, usize>(0)?, row.get::<_, u32>(1)?, row.get::<_, u32>(2)?))
+        })?;
+        for month in months {
+            let (month, done, total) = month?;
+            if let Some(slot) = review.months.get_mut(month.wrapping_sub(1)) {
+                *slot = (done, total);
             }
         }
 
-        // Calculate best streak from all dates
-        let best = calculate_best_streak(&dates);
+        let days: BTreeMap<NaiveDate, bool> = Self::streak_days(conn, config)?
+            .range(start..=end)
+            .map(|(date, complete)| (*date, *complete))
+            .collect();
+        review.best_streak = calculate_best_streak(&days, config);
 
-        Ok(Streak { current, best })
+        review.quran_pages = QuranRepo::get_weekly_total(conn, &start_str, &end_str)?;
+
+        let mut stmt = conn.prepare(
+            "SELECT d.name, COUNT(*)
+             FROM dhikr_log l
+             JOIN dhikr_definitions d ON d.id = l.dhikr_id
+             WHERE l.completed = 1 AND l.date >= ?1 AND l.date <= ?2
+             GROUP BY l.dhikr_id
+             ORDER BY 2 DESC, d.name",
+        )?;
+        review.dhikr = stmt
+            .query_map(params![start_str, end_str], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(review)
+    }
+
+    /// How many prayers in the range were marked done within their own day's
+    /// window — before the next prayer's cached time (sunrise for Fajr, the
+    /// following Fajr for Isha). Days with no cached times are left out.
+    pub fn on_time(conn: &Connection, start: &str, end: &str) -> Result<OnTime> {
+        let (on_time, timed) = conn.query_row(
+            "SELECT COALESCE(SUM(p.marked_at < CASE p.prayer_type
+                        WHEN 'fajr'    THEN p.date || ' ' || c.sunrise
+                        WHEN 'zuhr'    THEN p.date || ' ' || c.asr
+                        WHEN 'asr'     THEN p.date || ' ' || c.maghrib
+                        WHEN 'maghrib' THEN
+                            CASE WHEN c.isha < c.maghrib THEN date(p.date, '+1 day') ELSE p.date END
+                            || ' ' || c.isha
+                        ELSE COALESCE(n.date || ' ' || n.fajr, date(p.date, '+1 day'))
+                    END), 0),
+                    COUNT(*)
+             FROM prayers p
+             JOIN prayer_times_cache c ON c.date = p.date
+             LEFT JOIN prayer_times_cache n ON n.date = date(p.date, '+1 day')
+             WHERE p.is_qada = 0 AND p.status = 'done' AND p.marked_at IS NOT NULL
+               AND p.date >= ?1 AND p.date <= ?2",
+            params![start, end],
+            |row| Ok((row.get::<_, u32>(0)?, row.get::<_, u32>(1)?)),
+        )?;
+        Ok(OnTime { on_time, timed })
+    }
+
+    pub fn load_view(conn: &Connection) -> Result<StatsView> {
+        Ok(MetaRepo::get(conn, STATS_VIEW_KEY)?
+            .map(|raw| StatsView::decode(&raw))
+            .unwrap_or_default())
     }
 
+    pub fn save_view(conn: &Connection, view: &StatsView) -> Result<()> {
+        MetaRepo::set(conn, STATS_VIEW_KEY, &view.encode())
+    }
+
+    pub fn reset_view(conn: &Connection) -> Result<()> {
+        conn.execute("DELETE FROM app_meta WHERE key = ?1", params![STATS_VIEW_KEY])?;
+        Ok(())
+    }
+
+    /// One entry per day from `start` to `end`, so index `i` is always
+    /// `start + i`; days without any rows come back as 0/0.
     pub fn get_weekly_grid(conn: &Connection, start: &str, end: &str) -> Result<Vec<DailyStats>> {
-        Self::get_daily_stats_range(conn, start, end)
+        let mut tracked: BTreeMap<String, DailyStats> =
+            Self::get_daily_stats_range(conn, start, end, None)?
+                .into_iter()
+                .map(|d| (d.date.clone(), d))
+                .collect();
+
+        let end = NaiveDate::parse_from_str(end, DATE_FMT)?;
+        let mut day = NaiveDate::parse_from_str(start, DATE_FMT)?;
+        let mut grid = Vec::new();
+        while day <= end {
+            let date = date_str(day);
+            grid.push(tracked.remove(&date).unwrap_or(DailyStats {
+                date,
+                prayers_done: 0,
+                prayers_total: 0,
+            }));
+            day += chrono::Duration::days(1);
+        }
+        Ok(grid)
+    }
+
+    /// Prayers done so far this week (Saturday–Friday) or this calendar month,
+    /// measured against `target`.
+    pub fn goal(
+        conn: &Connection,
+        range: StatsRange,
+        target: u32,
+        today: NaiveDate,
+    ) -> Result<Goal> {
+        let start = match range {
+            StatsRange::Week => week_start(today),
+            StatsRange::Month => today.with_day(1).unwrap_or(today),
+        };
+        let done = Self::get_daily_stats_range(conn, &date_str(start), &date_str(today), None)?
+            .iter()
+            .map(|d| d.prayers_done as u32)
+            .sum();
+        Ok(Goal { range, done, target })
+    }
+
+    /// Consecutive completed days for one dhikr — or weeks, for a weekly one.
+    /// A period not yet completed doesn't break the streak while it's still
+    /// running; counting then starts from the one before.
+    pub fn dhikr_streak(conn: &Connection, dhikr_id: i64, today: NaiveDate) -> Result<Streak> {
+        let weekly: bool = conn.query_row(
+            "SELECT frequency = 'weekly' FROM dhikr_definitions WHERE id = ?1",
+            params![dhikr_id],
+            |row| row.get(0),
+        )?;
+        let period = |d: NaiveDate| if weekly { week_start(d) } else { d };
+        let step = chrono::Duration::days(if weekly { 7 } else { 1 });
+
+        let mut stmt =
+            conn.prepare("SELECT date FROM dhikr_log WHERE dhikr_id = ?1 AND completed = 1")?;
+        let done: BTreeSet<NaiveDate> = stmt
+            .query_map(params![dhikr_id], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter_map(|date| NaiveDate::parse_from_str(&date, DATE_FMT).ok())
+            .map(period)
+            .collect();
+
+        let mut check = period(today);
+        if !done.contains(&check) {
+            check -= step;
+        }
+        let mut current = 0u32;
+        while done.contains(&check) {
+            current += 1;
+            check -= step;
+        }
+
+        let mut best = 0u32;
+        let mut run = 0u32;
+        let mut prev: Option<NaiveDate> = None;
+        for &date in &done {
+            run = if prev.is_some_and(|p| p + step == date) { run + 1 } else { 1 };
+            best = best.max(run);
+            prev = Some(date);
+        }
+
+        Ok(Streak { current, best })
     }
 }
 
-fn calculate_best_streak(dates: &[String]) -> u32 {
-    if dates.is_empty() {
+/// Count consecutive complete days ending at `today`, or at yesterday while
+/// today is still in progress: an incomplete today neither extends the
+/// streak nor breaks it, and doesn't use up a grace day.
+/// With `untracked_days_neutral`, days without any rows are skipped rather
+/// than ending the streak (bounded by the first tracked day). Up to
+/// `grace_days` incomplete days are passed over before one ends it.
+fn calculate_current_streak(
+    days: &BTreeMap<NaiveDate, bool>,
+    today: NaiveDate,
+    config: &StreakConfig,
+) -> u32 {
+    let Some(first) = days.keys().next().copied() else {
         return 0;
+    };
+
+    let mut current = 0u32;
+    let mut grace_left = config.grace_days;
+    let mut check_date = match (days.get(&today), today.pred_opt()) {
+        (Some(true), _) | (_, None) => today,
+        (_, Some(yesterday)) => yesterday,
+    };
+
+    while check_date >= first {
+        match days.get(&check_date) {
+            Some(true) => current += 1,
+            None if config.untracked_days_neutral => {}
+            Some(false) | None if grace_left > 0 => grace_left -= 1,
+            Some(false) | None => break,
+        }
+        check_date = match check_date.pred_opt() {
+            Some(d) => d,
+            None => break,
+        };
     }
+    current
+}
 
-    // dates is sorted DESC - reverse for processing
-    let mut sorted: Vec<chrono::NaiveDate> = dates
-        .iter()
-        .filter_map(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
-        .collect();
-    sorted.sort();
+/// Longest run of complete days, holding at most `grace_days` incomplete
+/// ones. With grace, the run slides forward past its oldest incomplete day
+/// instead of starting over.
+fn calculate_best_streak(days: &BTreeMap<NaiveDate, bool>, config: &StreakConfig) -> u32 {
+    let (Some(first), Some(last)) = (days.keys().next(), days.keys().next_back()) else {
+        return 0;
+    };
 
     let mut best = 0u32;
-    let mut current = 1u32;
+    // Completed-day count before each incomplete day still inside the run,
+    // oldest first; dropping one drops the days before it too
+    let mut misses: std::collections::VecDeque<u32> = Default::default();
+    let mut completed = 0u32;
+    let mut run_start = 0u32;
+
+    for date in first.iter_days().take_while(|d| d <= last) {
+        match days.get(&date) {
+            Some(true) => {
+                completed += 1;
+                best = best.max(completed - run_start);
+            }
+            None if config.untracked_days_neutral => {}
+            Some(false) | None => {
+                misses.push_back(completed);
+                if misses.len() as u32 > config.grace_days {
+                    run_start = misses.pop_front().unwrap_or(completed);
+                }
+            }
+        }
+    }
+    best
+}
 
-    for i in 1..sorted.len() {
-        let prev = sorted[i - 1];
-        let curr = sorted[i];
-        if curr == prev.succ_opt().unwrap_or(curr) {
-            current += 1;
-        } else {
-            current = 1;
+// ─── History ─────────────────────────────────────────────────────────────────
+
+/// Tables of recorded activity and the column each is dated by.
+const HISTORY_TABLES: [(&str, &str); 9] = [
+    ("prayers", "date"),
+    ("dhikr_log", "date"),
+    ("quran_log", "date"),
+    ("quran_reading_log", "date"),
+    ("qiyam_log", "night_date"),
+    ("nafl_log", "date"),
+    ("fasting_log", "date"),
+    ("jummah_log", "date"),
+    ("eid_log", "date"),
+];
+
+pub struct HistoryRepo;
+
+impl HistoryRepo {
+    /// Delete every record dated before `before`, along with qada made up
+    /// before it, in one transaction. Qada still owed is kept. Returns the
+    /// number of rows deleted.
+    pub fn prune_before(conn: &Connection, before: &str) -> Result<usize> {
+        let tx = conn.unchecked_transaction()?;
+        let mut deleted = 0;
+        for (table, column) in HISTORY_TABLES {
+            deleted += tx.execute(
+                &format!("DELETE FROM {} WHERE {} < ?1", table, column),
+                params![before],
+            )?;
         }
-        best = best.max(current);
+        deleted += tx.execute(
+            "DELETE FROM qada_queue WHERE completed = 1 AND completed_at < ?1",
+            params![before],
+        )?;
+        tx.commit()?;
+        Ok(deleted)
     }
-    best.max(current)
 }
 
 // ─── App meta ────────────────────────────────────────────────────────────────
@@ -542,4 +1685,365 @@ impl MetaRepo {
         )?;
         Ok(())
     }
+
+    /// SQLite's counter of changes committed by other connections, so a
+    /// long-running view can notice `sujood` commands run beside it.
+    pub fn data_version(conn: &Connection) -> Result<i64> {
+        Ok(conn.query_row("PRAGMA data_version", [], |row| row.get(0))?)
+    }
+
+    /// Rebuild the database file so space freed by deletions is returned.
+    pub fn vacuum(conn: &Connection) -> Result<()> {
+        conn.execute_batch("VACUUM")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::run_migrations;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, DATE_FMT).unwrap()
+    }
+
+    /// Give `day` its five rows, the first `done` of them marked done.
+    fn track_day(conn: &Connection, day: &str, done: usize) {
+        PrayerRepo::ensure_rows_for_date(conn, day).unwrap();
+        for prayer in PrayerType::all().iter().take(done) {
+            PrayerRepo::mark_status(conn, prayer.as_str(), day, "done", None).unwrap();
+        }
+    }
+
+    /// Give `day` its five rows, all done except `missed`, which are marked missed.
+    fn track_day_missing(conn: &Connection, day: &str, missed: &[PrayerType]) {
+        PrayerRepo::ensure_rows_for_date(conn, day).unwrap();
+        for prayer in PrayerType::all() {
+            let status = if missed.contains(&prayer) { "missed" } else { "done" };
+            PrayerRepo::mark_status(conn, prayer.as_str(), day, status, None).unwrap();
+        }
+    }
+
+    #[test]
+    fn untracked_day_is_neutral_only_when_enabled() {
+        let conn = test_db();
+        for day in ["2026-03-01", "2026-03-02", "2026-03-04", "2026-03-05"] {
+            track_day(&conn, day, 5);
+        }
+        let neutral = StreakConfig { untracked_days_neutral: true, ..Default::default() };
+        let today = date("2026-03-05");
+
+        // 03-03 has no rows at all
+        assert_eq!(StatsRepo::calculate_streak(&conn, &neutral, today).unwrap().current, 4);
+        let strict = StreakConfig::default();
+        assert_eq!(StatsRepo::calculate_streak(&conn, &strict, today).unwrap().current, 2);
+
+        // Tracked but left pending still breaks it
+        track_day(&conn, "2026-03-03", 0);
+        assert_eq!(StatsRepo::calculate_streak(&conn, &neutral, today).unwrap().current, 2);
+    }
+
+    #[test]
+    fn category_filter_returns_only_that_tag() {
+        let conn = test_db();
+        DhikrRepo::add_custom(&conn, "Dua for travel", "checkbox", 1, "daily", Some("travel"))
+            .unwrap();
+        DhikrRepo::add_custom(&conn, "Tasbih", "counter", 33, "daily", Some("After-Salah"))
+            .unwrap();
+        let defs = DhikrRepo::get_active_definitions(&conn).unwrap();
+        let names = |filter: &str| -> Vec<String> {
+            defs.iter().filter(|d| d.in_category(filter)).map(|d| d.name.clone()).collect()
+        };
+
+        assert_eq!(names("travel"), ["Dua for travel"]);
+        // A tag gathers custom and builtin dhikr alike, ignoring case
+        assert_eq!(names("after-salah"), ["Post-Salah Tasbih", "Tasbih"]);
+        assert_eq!(names("custom"), ["Dua for travel", "Tasbih"]);
+        assert_eq!(names("builtin"), ["Morning Adhkar", "Evening Adhkar", "Post-Salah Tasbih"]);
+    }
+
+    #[test]
+    fn quran_input_history_is_recent_first_without_duplicates() {
+        let conn = test_db();
+        assert!(QuranRepo::get_input_history(&conn).unwrap().is_empty());
+        for pages in [2.0, 5.0, 2.0, 10.0, 0.5] {
+            QuranRepo::remember_input(&conn, pages, 3).unwrap();
+        }
+        assert_eq!(QuranRepo::get_input_history(&conn).unwrap(), [0.5, 10.0, 2.0]);
+
+        // Entering a remembered value again moves it to the front
+        QuranRepo::remember_input(&conn, 2.0, 3).unwrap();
+        assert_eq!(QuranRepo::get_input_history(&conn).unwrap(), [2.0, 0.5, 10.0]);
+    }
+
+    #[test]
+    fn qada_completion_is_shown_in_local_time() {
+        let conn = test_db();
+        QadaRepo::add_entry(&conn, "fajr", "2026-10-01").unwrap();
+        // Made up at 21:15 local (18:15 UTC at +03:00)
+        let at = NaiveDateTime::parse_from_str("2026-10-16 21:15:00", DATETIME_FMT).unwrap();
+        QadaRepo::complete_oldest(&conn, &at.format(DATETIME_FMT).to_string()).unwrap();
+
+        let last = QadaRepo::last_completed(&conn).unwrap().unwrap();
+        assert_eq!(last.completed_time(), Some(at));
+        let now = at + chrono::Duration::minutes(135);
+        assert_eq!(last.completed_label(now).unwrap(), "2026-10-16 21:15, 2h ago");
+        assert_eq!(last.completed_label(at).unwrap(), "2026-10-16 21:15, just now");
+        // Counted toward the local day it was made up on
+        let that_day = QadaRepo::completed_in_range(&conn, "2026-10-16", "2026-10-16").unwrap();
+        assert_eq!(that_day.len(), 1);
+    }
+
+    #[test]
+    fn legacy_utc_completions_are_moved_to_local_time_once() {
+        let conn = test_db();
+        QadaRepo::add_entry(&conn, "isha", "2026-10-01").unwrap();
+        QadaRepo::complete_oldest(&conn, "2026-10-16 22:30:00").unwrap();
+
+        QadaRepo::localize_legacy_completions(&conn, 180).unwrap();
+        QadaRepo::localize_legacy_completions(&conn, 180).unwrap();
+        let last = QadaRepo::last_completed(&conn).unwrap().unwrap();
+        assert_eq!(last.completed_at.as_deref(), Some("2026-10-17 01:30:00"));
+    }
+
+    #[test]
+    fn bulk_marking_a_week_completes_each_day_and_the_streak() {
+        let conn = test_db();
+        let morning = DhikrRepo::find_by_name(&conn, "Morning Adhkar").unwrap().unwrap();
+        let tasbih = DhikrRepo::find_by_name(&conn, "Post-Salah Tasbih").unwrap().unwrap();
+        let (from, to) = (date("2026-10-10"), date("2026-10-16"));
+
+        assert_eq!(DhikrRepo::upsert_log_range(&conn, &morning, from, to).unwrap(), 7);
+        let logs = DhikrRepo::log_range(&conn, morning.id, "2026-10-10", "2026-10-16").unwrap();
+        assert_eq!(logs.len(), 7);
+        assert!(logs.iter().all(|l| l.completed && l.count == 1));
+        assert!(DhikrRepo::log_range(&conn, morning.id, "2026-10-09", "2026-10-09")
+            .unwrap()
+            .is_empty());
+        assert_eq!(StatsRepo::dhikr_streak(&conn, morning.id, to).unwrap().current, 7);
+
+        // Counters are filled to their target
+        DhikrRepo::upsert_log_range(&conn, &tasbih, from, to).unwrap();
+        let logs = DhikrRepo::log_range(&conn, tasbih.id, "2026-10-10", "2026-10-16").unwrap();
+        assert!(logs.iter().all(|l| l.completed && l.count == tasbih.target_count));
+    }
+
+    #[test]
+    fn detail_strip_reflects_mixed_history() {
+        let conn = test_db();
+        let morning = DhikrRepo::find_by_name(&conn, "Morning Adhkar").unwrap().unwrap();
+        // 10-09 falls before the strip
+        for day in ["2026-10-09", "2026-10-10", "2026-10-11", "2026-10-13", "2026-10-16"] {
+            DhikrRepo::upsert_log_range(&conn, &morning, date(day), date(day)).unwrap();
+        }
+        // Logged but left incomplete
+        conn.execute(
+            "INSERT INTO dhikr_log (dhikr_id, date, count, completed)
+             VALUES (?1, '2026-10-14', 0, 0)",
+            params![morning.id],
+        )
+        .unwrap();
+
+        let logs = DhikrRepo::log_range(&conn, morning.id, "2026-10-10", "2026-10-16").unwrap();
+        let strip: String = morning
+            .recent_completion(&logs, date("2026-10-16"), 7)
+            .iter()
+            .map(|(_, done)| if *done { '●' } else { '○' })
+            .collect();
+        assert_eq!(strip, "●●○●○○●");
+    }
+
+    #[test]
+    fn jummah_streak_counts_fridays_and_breaks_on_a_miss() {
+        let conn = test_db();
+        let record = |day: &str, attended: bool| {
+            JummahRepo::record(&conn, &JummahEntry { date: day.to_string(), attended }).unwrap();
+        };
+        // Fridays: 09-11, 09-18, 09-25, 10-02, 10-09, 10-16
+        record("2026-09-11", true);
+        record("2026-09-18", true);
+        record("2026-09-25", false);
+        for friday in ["2026-10-02", "2026-10-09"] {
+            record(friday, true);
+        }
+
+        // Midweek, the streak runs to the last Friday
+        let streak = JummahRepo::streak(&conn, date("2026-10-14")).unwrap();
+        assert_eq!((streak.current, streak.best), (2, 2));
+        // Today's Friday isn't recorded yet, so it doesn't break anything
+        assert_eq!(JummahRepo::streak(&conn, date("2026-10-16")).unwrap().current, 2);
+        record("2026-10-16", true);
+        let streak = JummahRepo::streak(&conn, date("2026-10-16")).unwrap();
+        assert_eq!((streak.current, streak.best), (3, 3));
+
+        // A Friday never recorded breaks it too
+        assert_eq!(JummahRepo::streak(&conn, date("2026-10-24")).unwrap().current, 0);
+        record("2026-10-16", false);
+        assert_eq!(JummahRepo::streak(&conn, date("2026-10-16")).unwrap().current, 0);
+    }
+
+    #[test]
+    fn forgiving_streak_overlooks_only_a_lone_fajr() {
+        let conn = test_db();
+        let forgiving = StreakConfig { forgiving: true, ..Default::default() };
+        let streak = |config: &StreakConfig, today: &str| {
+            StatsRepo::calculate_streak(&conn, config, date(today)).unwrap().current
+        };
+        track_day(&conn, "2026-10-13", 5);
+        track_day_missing(&conn, "2026-10-14", &[PrayerType::Fajr]);
+        track_day(&conn, "2026-10-15", 5);
+
+        assert_eq!(streak(&forgiving, "2026-10-15"), 3);
+        assert_eq!(streak(&StreakConfig::default(), "2026-10-15"), 1);
+
+        // 3/5 with Fajr among the misses still breaks it
+        track_day_missing(&conn, "2026-10-14", &[PrayerType::Fajr, PrayerType::Asr]);
+        assert_eq!(streak(&forgiving, "2026-10-15"), 1);
+        // So does a single miss of a prayer that isn't forgivable
+        track_day_missing(&conn, "2026-10-14", &[PrayerType::Isha]);
+        assert_eq!(streak(&forgiving, "2026-10-15"), 1);
+    }
+
+    #[test]
+    fn one_grace_day_bridges_a_single_gap() {
+        let conn = test_db();
+        let grace = StreakConfig { grace_days: 1, ..Default::default() };
+        let streak = |config: &StreakConfig| {
+            StatsRepo::calculate_streak(&conn, config, date("2026-10-15")).unwrap()
+        };
+        for day in ["2026-10-10", "2026-10-11", "2026-10-12", "2026-10-14", "2026-10-15"] {
+            track_day(&conn, day, 5);
+        }
+        track_day(&conn, "2026-10-13", 3);
+
+        let strict = streak(&StreakConfig::default());
+        assert_eq!((strict.current, strict.best), (2, 3));
+        // The gap day keeps the streak alive but isn't counted in it
+        let bridged = streak(&grace);
+        assert_eq!((bridged.current, bridged.best), (5, 5));
+
+        // A second gap inside the run is one more than the grace allows
+        track_day_missing(&conn, "2026-10-11", &[PrayerType::Isha]);
+        let broken = streak(&grace);
+        assert_eq!((broken.current, broken.best), (3, 3));
+    }
+
+    #[test]
+    fn completed_qada_heals_its_day_only_when_enabled() {
+        let conn = test_db();
+        let healing = StreakConfig { qada_heals: true, ..Default::default() };
+        let streak = |config: &StreakConfig| {
+            StatsRepo::calculate_streak(&conn, config, date("2026-10-15")).unwrap().current
+        };
+        track_day(&conn, "2026-10-13", 5);
+        track_day_missing(&conn, "2026-10-14", &[PrayerType::Asr]);
+        track_day(&conn, "2026-10-15", 5);
+        QadaRepo::add_entry(&conn, "asr", "2026-10-14").unwrap();
+
+        // A pending qada doesn't heal anything yet
+        assert_eq!(streak(&healing), 1);
+
+        QadaRepo::complete_oldest(&conn, "2026-10-15 21:00:00").unwrap();
+        assert_eq!(streak(&healing), 3);
+        assert_eq!(streak(&StreakConfig::default()), 1);
+    }
+
+    #[test]
+    fn saved_stats_view_is_restored_until_reset() {
+        let conn = test_db();
+        assert_eq!(StatsRepo::load_view(&conn).unwrap(), StatsView::default());
+
+        let view = StatsView { range: StatsRange::Month, prayer: Some(PrayerType::Fajr) };
+        StatsRepo::save_view(&conn, &view).unwrap();
+        assert_eq!(StatsRepo::load_view(&conn).unwrap(), view);
+
+        StatsRepo::reset_view(&conn).unwrap();
+        assert_eq!(StatsRepo::load_view(&conn).unwrap(), StatsView::default());
+    }
+
+    #[test]
+    fn a_partial_today_keeps_yesterdays_streak() {
+        let conn = test_db();
+        let streak = || {
+            StatsRepo::calculate_streak(&conn, &StreakConfig::default(), date("2026-10-16"))
+                .unwrap()
+                .current
+        };
+        track_day(&conn, "2026-10-14", 5);
+        track_day(&conn, "2026-10-15", 5);
+        track_day(&conn, "2026-10-16", 3);
+        assert_eq!(streak(), 2);
+
+        track_day(&conn, "2026-10-16", 5);
+        assert_eq!(streak(), 3);
+    }
+
+    #[test]
+    fn weekly_grid_fills_untracked_days() {
+        let conn = test_db();
+        for day in ["2026-10-10", "2026-10-11", "2026-10-15", "2026-10-16"] {
+            track_day(&conn, day, 5);
+        }
+        track_day(&conn, "2026-10-12", 2);
+
+        let grid = StatsRepo::get_weekly_grid(&conn, "2026-10-10", "2026-10-16").unwrap();
+        let days: Vec<(&str, u8, u8)> = grid
+            .iter()
+            .map(|d| (d.date.as_str(), d.prayers_done, d.prayers_total))
+            .collect();
+        assert_eq!(
+            days,
+            [
+                ("2026-10-10", 5, 5),
+                ("2026-10-11", 5, 5),
+                ("2026-10-12", 2, 5),
+                ("2026-10-13", 0, 0),
+                ("2026-10-14", 0, 0),
+                ("2026-10-15", 5, 5),
+                ("2026-10-16", 5, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn builtin_edits_keep_the_name_and_the_logs() {
+        let conn = test_db();
+        let tasbih = DhikrRepo::find_by_name(&conn, "Post-Salah Tasbih").unwrap().unwrap();
+        DhikrRepo::upsert_log(&conn, tasbih.id, "2026-10-15", 99, true).unwrap();
+        DhikrRepo::upsert_log(&conn, tasbih.id, "2026-10-16", 20, false).unwrap();
+
+        DhikrRepo::update_definition(
+            &conn,
+            tasbih.id,
+            "Post-Salah Tasbih",
+            &DhikrType::Counter,
+            33,
+            &DhikrFrequency::Daily,
+        )
+        .unwrap();
+        let err = DhikrRepo::update_definition(
+            &conn,
+            tasbih.id,
+            "Tasbih",
+            &DhikrType::Counter,
+            33,
+            &DhikrFrequency::Daily,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("keep their name"), "{err}");
+
+        let edited = DhikrRepo::find_by_name(&conn, "Post-Salah Tasbih").unwrap().unwrap();
+        assert_eq!((edited.id, edited.target_count), (tasbih.id, 33));
+        assert_eq!(edited.category, tasbih.category);
+        let logs = DhikrRepo::log_range(&conn, tasbih.id, "2026-10-15", "2026-10-16").unwrap();
+        let counts: Vec<i32> = logs.iter().map(|l| l.count).collect();
+        assert_eq!(counts, [99, 20]);
+    }
 }
@@ -10,22 +10,34 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use rusqlite::Connection;
 
-use cli::args::{Cli, Commands};
+use cli::args::{Cli, Commands, QuranCommands, TimesCommands};
 use cli::handlers;
 use config::AppConfig;
+use models::StatsRange;
 use db::migrations::run_migrations;
-use db::repository::MetaRepo;
+use db::repository::{CacheRepo, MetaRepo, QadaRepo};
 use prayer_times::PrayerCalculator;
+use utils::date::{date_str, now_local};
 
 fn main() -> Result<()> {
-    env_logger::init();
-
     let cli = Cli::parse();
+    let default_filter = if cli.verbose { "sujood=debug" } else { "error" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_filter))
+        .init();
+    handlers::set_color(cli.color.enabled());
+    // Needs no config or database, so packagers can run it in a clean build root
+    if let Some(Commands::Man { command, dir }) = &cli.command {
+        return handlers::handle_man(command, dir.as_deref());
+    }
     let mut config = AppConfig::load().context("Loading config")?;
+    if let Some(name) = &cli.profile {
+        config.switch_profile(name)?;
+    }
+    let theme_name = cli.theme.as_deref().unwrap_or(&config.ui.theme);
+    tui::theme::set(tui::theme::Theme::by_name(theme_name).context("Choosing a theme")?);
 
-    // Ensure data directory exists and open DB
-    AppConfig::ensure_data_dir()?;
-    let db_path = AppConfig::db_path()?;
+    // Ensure the database's directory exists and open it
+    let db_path = AppConfig::resolve_db_path(cli.db.clone())?;
     let conn = Connection::open(&db_path)
         .with_context(|| format!("Opening database at {:?}", db_path))?;
 
@@ -34,54 +46,146 @@ fn main() -> Result<()> {
 
     // Run migrations on every startup
     run_migrations(&conn)?;
+    // Times cached under since-edited [salah] settings would be silently wrong
+    if handlers::sync_cache(&conn, &config)? {
+        match PrayerCalculator::from_config(&config.salah) {
+            Ok(calc) => {
+                let days = config.salah.cache_days_ahead;
+                calc.ensure_cached(&conn, now_local(&config).date(), days)?
+            }
+            // Left for the command to report, so `setup` can still fix it
+            Err(e) => log::warn!("not refilling the prayer times cache: {:#}", e),
+        }
+    }
+    // Past days can always be recalculated; the last 30 stay for on-time stats
+    let keep_from = now_local(&config).date() - chrono::Duration::days(29);
+    CacheRepo::prune_computed_before(&conn, &date_str(keep_from))?;
 
     match cli.command {
         // Setup wizard
-        Some(Commands::Setup { reset }) => {
-            handlers::handle_setup(&conn, &mut config, reset)?;
+        Some(Commands::Setup { reset, high_latitude_rule, auto_location }) => {
+            handlers::handle_setup(&conn, &mut config, reset, high_latitude_rule, auto_location)?;
         }
 
         // Explicit subcommands — check setup first
         Some(cmd) => {
             ensure_setup(&conn, &mut config)?;
             match cmd {
-                Commands::Times => {
-                    handlers::handle_times(&conn, &config)?;
+                Commands::Times { action: Some(TimesCommands::Import { file }), .. } => {
+                    handlers::handle_times_import(&conn, &config, &file)?;
+                }
+                Commands::Times { action: None, both_asr, date, tomorrow } => {
+                    handlers::handle_times(
+                        &conn,
+                        &config,
+                        date.as_deref(),
+                        tomorrow,
+                        both_asr || config.display.both_asr,
+                    )?;
+                }
+                Commands::Mark { prayer, missed, date, note } => {
+                    handlers::handle_mark(
+                        &conn,
+                        &config,
+                        &prayer,
+                        missed,
+                        date.as_deref(),
+                        note.as_deref(),
+                    )?;
+                }
+                Commands::Remaining => {
+                    handlers::handle_remaining(&conn, &config)?;
+                }
+                Commands::Next { format, json } => {
+                    handlers::handle_next(&conn, &config, &format, json)?;
+                }
+                Commands::Profile { action } => {
+                    handlers::handle_profile(&conn, &mut config, &action)?;
+                }
+                Commands::Daemon => {
+                    cli::daemon::run(&conn, &config)?;
+                }
+                Commands::Serve { port, bind } => {
+                    cli::serve::run(&conn, &config, &bind, port)?;
+                }
+                Commands::List { from, to } => {
+                    handlers::handle_list(&conn, &config, from.as_deref(), to.as_deref())?;
                 }
-                Commands::Mark { prayer, missed } => {
-                    handlers::handle_mark(&conn, &prayer, missed)?;
+                Commands::History { date } => {
+                    handlers::handle_history(&conn, &config, &date)?;
+                }
+                Commands::Today { no_color } => {
+                    if no_color {
+                        handlers::set_color(false);
+                    }
+                    handlers::handle_today(&conn, &config)?;
+                }
+                Commands::Qiyam => {
+                    handlers::handle_qiyam(&conn, &config)?;
+                }
+                Commands::Eid { missed, history } => {
+                    handlers::handle_eid(&conn, &config, missed, history)?;
+                }
+                Commands::Jummah { missed, date } => {
+                    handlers::handle_jummah(&conn, &config, missed, date.as_deref())?;
+                }
+                Commands::Fast { date } => {
+                    handlers::handle_fast(&conn, &config, date.as_deref())?;
+                }
+                Commands::Catchup { action } => {
+                    handlers::handle_catchup(&conn, &config, action)?;
                 }
                 Commands::Qada { action } => {
-                    handlers::handle_qada(&conn, &action)?;
+                    handlers::handle_qada(&conn, &config, &action)?;
                 }
                 Commands::Dhikr { action } => {
-                    handlers::handle_dhikr(&conn, &action)?;
+                    handlers::handle_dhikr(&conn, &config, &action)?;
+                }
+                Commands::Quran { action: Some(QuranCommands::Status), .. } => {
+                    handlers::handle_quran_status(&conn, &config)?;
                 }
-                Commands::Quran { pages } => {
-                    handlers::handle_quran(&conn, pages)?;
+                Commands::Quran { action: Some(QuranCommands::Read { surah, start, end }), .. } => {
+                    handlers::handle_quran_read(&conn, &config, &surah, start, end)?;
                 }
-                Commands::Stats { week } => {
-                    handlers::handle_stats(&conn, week)?;
+                Commands::Quran { action: Some(QuranCommands::Set { pages }), .. } => {
+                    handlers::handle_quran_set(&conn, &config, pages)?;
                 }
-                Commands::Export => {
-                    handlers::handle_export(&conn, &config)?;
+                Commands::Quran { action: Some(QuranCommands::Undo), .. } => {
+                    handlers::handle_quran_undo(&conn)?;
                 }
-                Commands::Setup { .. } => unreachable!(),
+                Commands::Quran { action: None, pages } => {
+                    handlers::handle_quran(&conn, &config, pages)?;
+                }
+                Commands::Stats { year: Some(year), .. } => {
+                    handlers::handle_year_review(&conn, &config, year)?;
+                }
+                Commands::Stats { week, range, prayer, reset_view, year: None } => {
+                    let range = if week { Some(StatsRange::Week) } else { range };
+                    handlers::handle_stats(&conn, &config, range, prayer.as_deref(), reset_view)?;
+                }
+                Commands::Export { format, days, out } => {
+                    handlers::handle_export(&conn, &config, format, days, out.as_deref())?;
+                }
+                Commands::Import { file, merge: _, overwrite } => {
+                    handlers::handle_import(&conn, &file, overwrite)?;
+                }
+                Commands::Prune { before, include_history, vacuum } => {
+                    handlers::handle_prune(&conn, &config, &before, include_history, vacuum)?;
+                }
+                Commands::Setup { .. } | Commands::Man { .. } => unreachable!(),
             }
         }
 
-        // No subcommand → launch TUI
+        // No subcommand → launch TUI, or just print the glance
         None => {
             ensure_setup(&conn, &mut config)?;
-            // Ensure prayer times are cached for today+7 days
-            if let Ok(calc) = PrayerCalculator::new(
-                config.salah.latitude,
-                config.salah.longitude,
-                &config.salah.calc_method,
-                &config.salah.madhab,
-                config.salah.timezone_offset,
-            ) {
-                let _ = calc.ensure_cached(&conn, 7);
+            if cli.summary {
+                return handlers::handle_summary(&conn, &config);
+            }
+            // Top up the cache as days pass; only the newly uncovered ones are computed
+            if let Ok(calc) = PrayerCalculator::from_config(&config.salah) {
+                let days = config.salah.cache_days_ahead;
+                let _ = calc.ensure_cached(&conn, now_local(&config).date(), days);
             }
             tui::app::run(conn, config)?;
         }
@@ -96,7 +200,8 @@ fn ensure_setup(conn: &Connection, config: &mut AppConfig) -> Result<()> {
     if done.as_deref() != Some("1") {
         eprintln!("No configuration found. Running setup...");
         eprintln!();
-        handlers::handle_setup(conn, config, false)?;
+        handlers::handle_setup(conn, config, false, None, false)?;
     }
+    QadaRepo::localize_legacy_completions(conn, config.salah.timezone_offset)?;
     Ok(())
 }
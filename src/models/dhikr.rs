@@ -1,5 +1,8 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::date::{date_str, week_start, DATE_FMT};
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DhikrType {
@@ -80,6 +83,82 @@ pub struct DhikrDef {
     pub category: DhikrCategory,
     pub sort_order: i32,
     pub active: bool,
+    /// Free-text grouping such as "morning" or "travel"
+    pub tag: Option<String>,
+}
+
+impl DhikrDef {
+    /// Whether each of the `len` days (weeks, for weekly adhkar) ending at
+    /// `end` was completed, oldest first, from that range's log rows.
+    pub fn recent_completion(
+        &self,
+        logs: &[DhikrLog],
+        end: NaiveDate,
+        len: usize,
+    ) -> Vec<(NaiveDate, bool)> {
+        let (last, step) = match self.frequency {
+            DhikrFrequency::Daily => (end, 1),
+            DhikrFrequency::Weekly => (week_start(end), 7),
+        };
+        let done: Vec<NaiveDate> = logs
+            .iter()
+            .filter(|l| l.completed)
+            .filter_map(|l| NaiveDate::parse_from_str(&l.date, DATE_FMT).ok())
+            .map(|d| match self.frequency {
+                DhikrFrequency::Daily => d,
+                DhikrFrequency::Weekly => week_start(d),
+            })
+            .collect();
+        (0..len)
+            .rev()
+            .map(|i| {
+                let period = last - chrono::Duration::days(i as i64 * step);
+                (period, done.contains(&period))
+            })
+            .collect()
+    }
+
+    /// The date a log entry for `date` is stored under: the day itself, or
+    /// the start of its week for weekly adhkar so one entry covers the week.
+    pub fn log_date(&self, date: &str) -> String {
+        match self.frequency {
+            DhikrFrequency::Daily => date.to_string(),
+            DhikrFrequency::Weekly => NaiveDate::parse_from_str(date, DATE_FMT)
+                .map(|d| date_str(week_start(d)))
+                .unwrap_or_else(|_| date.to_string()),
+        }
+    }
+
+    /// The section this dhikr is listed under: its tag, or builtin/custom when untagged.
+    pub fn group(&self) -> &str {
+        self.tag.as_deref().unwrap_or(self.category.as_str())
+    }
+
+    /// Case-insensitive substring match on the name; an empty query matches all.
+    pub fn name_matches(&self, query: &str) -> bool {
+        self.name.to_lowercase().contains(&query.to_lowercase())
+    }
+
+    /// Whether `filter` names this dhikr's tag or its builtin/custom category.
+    pub fn in_category(&self, filter: &str) -> bool {
+        self.category.as_str().eq_ignore_ascii_case(filter)
+            || self
+                .tag
+                .as_deref()
+                .is_some_and(|t| t.eq_ignore_ascii_case(filter))
+    }
+}
+
+/// Group definitions by `DhikrDef::group`, keeping groups in order of first appearance.
+pub fn group_dhikr(defs: &[DhikrDef]) -> Vec<(String, Vec<&DhikrDef>)> {
+    let mut groups: Vec<(String, Vec<&DhikrDef>)> = Vec::new();
+    for def in defs {
+        match groups.iter_mut().find(|(name, _)| name == def.group()) {
+            Some((_, members)) => members.push(def),
+            None => groups.push((def.group().to_string(), vec![def])),
+        }
+    }
+    groups
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The two annual Eid prayers, tracked apart from the five fard prayers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Eid {
+    Fitr,
+    Adha,
+}
+
+impl Eid {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Eid::Fitr => "fitr",
+            Eid::Adha => "adha",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Eid::Fitr => "Eid al-Fitr",
+            Eid::Adha => "Eid al-Adha",
+        }
+    }
+
+    /// The Eid falling on a Hijri month/day: 1 Shawwal or 10 Dhu al-Hijjah.
+    pub fn on_hijri(month: usize, day: usize) -> Option<Eid> {
+        match (month, day) {
+            (10, 1) => Some(Eid::Fitr),
+            (12, 10) => Some(Eid::Adha),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Eid {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fitr" => Ok(Eid::Fitr),
+            "adha" => Ok(Eid::Adha),
+            _ => Err(anyhow::anyhow!("Unknown eid: {}", s)),
+        }
+    }
+}
+
+/// Whether the Eid prayer was attended in a given Hijri year.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EidEntry {
+    pub hijri_year: i64,
+    pub eid: Eid,
+    /// Gregorian date it fell on
+    pub date: String,
+    pub attended: bool,
+}
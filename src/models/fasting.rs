@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Why a voluntary fast is recommended on a given day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SunnahFast {
+    /// 9 Dhu al-Hijjah
+    Arafah,
+    /// 10 Muharram
+    Ashura,
+    /// Ayyam al-Beed, 13–15 of the Hijri month
+    WhiteDay,
+    Monday,
+    Thursday,
+}
+
+impl SunnahFast {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SunnahFast::Arafah => "Day of Arafah",
+            SunnahFast::Ashura => "Ashura",
+            SunnahFast::WhiteDay => "White day",
+            SunnahFast::Monday => "Monday",
+            SunnahFast::Thursday => "Thursday",
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether Jumu'ah was attended on a given Friday.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JummahEntry {
+    /// The Friday, `%Y-%m-%d`
+    pub date: String,
+    pub attended: bool,
+}
@@ -1,9 +1,19 @@
 pub mod dhikr;
+pub mod eid;
+pub mod fasting;
+pub mod jummah;
+pub mod nafl;
 pub mod prayer;
 pub mod qada;
+pub mod qiyam;
 pub mod stats;
 
-pub use dhikr::{DhikrCategory, DhikrDef, DhikrFrequency, DhikrLog, DhikrType};
-pub use prayer::{Prayer, PrayerStatus, PrayerType};
-pub use qada::QadaEntry;
-pub use stats::{DailyStats, Streak};
+pub use dhikr::{group_dhikr, DhikrCategory, DhikrDef, DhikrFrequency, DhikrLog, DhikrType};
+pub use eid::{Eid, EidEntry};
+pub use fasting::SunnahFast;
+pub use jummah::JummahEntry;
+pub use nafl::Nafl;
+pub use prayer::{remaining_prayers, Prayer, PrayerStatus, PrayerType};
+pub use qada::{QadaEntry, QadaPace};
+pub use qiyam::QiyamEntry;
+pub use stats::{DailyStats, Goal, Khatm, KhatmPlan, OnTime, StatsRange, StatsView, Streak, YearReview};
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// A voluntary prayer tracked beside the five fard, chosen with `nafl` in
+/// config. Nafl never count toward completion, stats or streaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Nafl {
+    Ishraq,
+    Duha,
+    Awwabin,
+    Witr,
+    /// Recorded through the qiyam tracker, which it shares
+    Tahajjud,
+}
+
+impl Nafl {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Nafl::Ishraq => "ishraq",
+            Nafl::Duha => "duha",
+            Nafl::Awwabin => "awwabin",
+            Nafl::Witr => "witr",
+            Nafl::Tahajjud => "tahajjud",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Nafl::Ishraq => "Ishraq",
+            Nafl::Duha => "Duha",
+            Nafl::Awwabin => "Awwabin",
+            Nafl::Witr => "Witr",
+            Nafl::Tahajjud => "Tahajjud",
+        }
+    }
+}
+
+impl FromStr for Nafl {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ishraq" => Ok(Nafl::Ishraq),
+            "duha" | "chasht" => Ok(Nafl::Duha),
+            "awwabin" => Ok(Nafl::Awwabin),
+            "witr" => Ok(Nafl::Witr),
+            "tahajjud" | "qiyam" => Ok(Nafl::Tahajjud),
+            _ => Err(anyhow::anyhow!("Unknown nafl prayer: {}", s)),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(dead_code)]
-use chrono::NaiveTime;
+use chrono::{NaiveDateTime, NaiveTime};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
@@ -107,4 +107,56 @@ pub struct Prayer {
     pub note: Option<String>,
     /// Computed from cache — not stored directly in this struct
     pub time: Option<NaiveTime>,
+    /// Time frozen onto the row when it was created, if `[day] snapshot_times` was on
+    pub scheduled_time: Option<NaiveTime>,
+    /// Local time a live mark set it done; None for backfills and older exports
+    #[serde(default)]
+    pub marked_at: Option<NaiveDateTime>,
+}
+
+/// Prayers from the given set that are still pending, in schedule order.
+pub fn remaining_prayers(prayers: &[Prayer]) -> Vec<PrayerType> {
+    prayers
+        .iter()
+        .filter(|p| p.status == PrayerStatus::Pending)
+        .map(|p| p.prayer_type.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(statuses: [PrayerStatus; 5]) -> Vec<Prayer> {
+        PrayerType::all()
+            .into_iter()
+            .zip(statuses)
+            .map(|(prayer_type, status)| Prayer {
+                id: None,
+                prayer_type,
+                date: "2026-10-16".to_string(),
+                status,
+                is_qada: false,
+                note: None,
+                time: None,
+                scheduled_time: None,
+                marked_at: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn remaining_keeps_only_pending_in_order() {
+        use PrayerStatus::*;
+        let prayers = day([Done, Missed, Pending, Done, Pending]);
+        let remaining = remaining_prayers(&prayers);
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining, vec![PrayerType::Asr, PrayerType::Isha]);
+    }
+
+    #[test]
+    fn remaining_is_empty_once_nothing_is_pending() {
+        use PrayerStatus::*;
+        assert!(remaining_prayers(&day([Done, Missed, Done, Done, Done])).is_empty());
+    }
 }
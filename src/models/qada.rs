@@ -1,6 +1,9 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 
 use crate::models::PrayerType;
+use crate::utils::date::DATETIME_FMT;
+use crate::utils::format::{format_ago, format_days_approx, format_pages};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QadaEntry {
@@ -10,3 +13,74 @@ pub struct QadaEntry {
     pub completed: bool,
     pub completed_at: Option<String>,
 }
+
+impl QadaEntry {
+    /// When it was made up, in the app's local time.
+    pub fn completed_time(&self) -> Option<NaiveDateTime> {
+        let at = self.completed_at.as_deref()?;
+        NaiveDateTime::parse_from_str(at, DATETIME_FMT).ok()
+    }
+
+    /// "2026-03-01 21:15, 2h ago", measured from the local time `now`.
+    pub fn completed_label(&self, now: NaiveDateTime) -> Option<String> {
+        let at = self.completed_time()?;
+        Some(format!(
+            "{}, {}",
+            at.format("%Y-%m-%d %H:%M"),
+            format_ago((now - at).num_seconds())
+        ))
+    }
+}
+
+/// How quickly the qada queue is being cleared, for the time-to-clear estimate.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QadaPace {
+    /// Prayers still owed
+    pub owed: i64,
+    /// Made up per day over the recent window; None when none were
+    pub daily_rate: Option<f64>,
+    /// Days the rate is measured over
+    pub window_days: u32,
+}
+
+impl QadaPace {
+    /// The per-day rate the estimate assumes: the recent one, else one a day.
+    pub fn rate(&self) -> f64 {
+        self.daily_rate.unwrap_or(1.0)
+    }
+
+    pub fn days_to_clear(&self) -> u32 {
+        (self.owed.max(0) as f64 / self.rate()).ceil() as u32
+    }
+
+    pub fn clear_date(&self, today: NaiveDate) -> NaiveDate {
+        today + Duration::days(self.days_to_clear() as i64)
+    }
+
+    /// "~6 weeks to clear, by Nov 30, 2026"
+    pub fn estimate(&self, today: NaiveDate) -> String {
+        format!(
+            "~{} to clear, by {}",
+            format_days_approx(self.days_to_clear()),
+            self.clear_date(today).format("%b %-d, %Y")
+        )
+    }
+
+    /// "at 2.1/day over the last 30 days", or the one-a-day fallback
+    pub fn rate_label(&self) -> String {
+        match self.daily_rate {
+            // A slow pace reads better per week than as a fraction of one
+            Some(rate) if rate < 1.0 => format!(
+                "at {}/week over the last {} days",
+                format_pages((rate * 70.0).round() / 10.0),
+                self.window_days
+            ),
+            Some(rate) => format!(
+                "at {}/day over the last {} days",
+                format_pages((rate * 10.0).round() / 10.0),
+                self.window_days
+            ),
+            None => format!("at 1/day — none made up in the last {} days", self.window_days),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// A night prayer (tahajjud/qiyam), tracked separately from the five fard prayers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QiyamEntry {
+    /// Date of the Maghrib that opened the night
+    pub night_date: String,
+    /// Local time it was marked, `%Y-%m-%d %H:%M:%S`
+    pub prayed_at: String,
+    pub in_last_third: bool,
+}
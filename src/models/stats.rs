@@ -1,4 +1,9 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::models::PrayerType;
+use crate::utils::format::{format_days_approx, format_pages};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyStats {
@@ -15,6 +20,26 @@ impl DailyStats {
             self.prayers_done as f64 / self.prayers_total as f64
         }
     }
+
+    /// Completion on the 0–5 scale of the heatmaps, so a single-prayer
+    /// filter's 1/1 shades like a full day.
+    pub fn level(&self) -> u8 {
+        (self.completion_ratio() * 5.0).round() as u8
+    }
+}
+
+/// Prayers marked done before their window closed, out of those marked on the day.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct OnTime {
+    pub on_time: u32,
+    /// Done prayers with a mark time to judge (backfills are left out)
+    pub timed: u32,
+}
+
+impl OnTime {
+    pub fn percent(&self) -> Option<u32> {
+        (self.timed > 0).then(|| (self.on_time as f64 / self.timed as f64 * 100.0).round() as u32)
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -23,6 +48,240 @@ pub struct Streak {
     pub best: u32,
 }
 
+/// Progress through the current khatm — one full reading of the mushaf.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Khatm {
+    /// Pages read toward the current khatm
+    pub pages: f64,
+    pub mushaf_pages: u32,
+    /// Day the current khatm began (None before any pages are logged)
+    pub started: Option<String>,
+    /// Khatms finished so far
+    pub completed: u32,
+    /// Pages per day over the recent window, used for the finish estimate
+    pub daily_average: f64,
+}
+
+impl Khatm {
+    pub fn percent(&self) -> u32 {
+        if self.mushaf_pages == 0 {
+            return 0;
+        }
+        ((self.pages / self.mushaf_pages as f64) * 100.0).floor().min(100.0) as u32
+    }
+
+    /// Days left at the recent pace, or None with no recent reading.
+    pub fn days_to_finish(&self) -> Option<u32> {
+        if self.daily_average <= 0.0 {
+            return None;
+        }
+        let remaining = (self.mushaf_pages as f64 - self.pages).max(0.0);
+        Some((remaining / self.daily_average).ceil() as u32)
+    }
+
+    /// "Khatm: 245/604 (41%) · ~6 weeks to finish"
+    pub fn summary(&self) -> String {
+        let head = format!(
+            "Khatm: {}/{} ({}%)",
+            format_pages(self.pages),
+            self.mushaf_pages,
+            self.percent()
+        );
+        match self.days_to_finish() {
+            Some(days) => format!("{} · ~{} to finish", head, format_days_approx(days)),
+            None => head,
+        }
+    }
+
+    /// What finishing by `deadline` asks of each day from `today` on. What
+    /// was left when `today` began is spread over the days left, so the
+    /// target holds steady as `read_today` pages are logged.
+    pub fn plan(&self, deadline: NaiveDate, today: NaiveDate, read_today: f64) -> KhatmPlan {
+        let days_left = ((deadline - today).num_days() + 1).max(0) as u32;
+        let remaining = (self.mushaf_pages as f64 - (self.pages - read_today).max(0.0)).max(0.0);
+        let pages_per_day = if days_left > 0 { remaining / days_left as f64 } else { remaining };
+        KhatmPlan {
+            deadline,
+            days_left,
+            pages_per_day,
+            on_pace: self.daily_average >= pages_per_day,
+        }
+    }
+}
+
+/// One calendar year at a glance, for `sujood stats --year`.
+#[derive(Debug, Clone, Default)]
+pub struct YearReview {
+    pub year: i32,
+    /// Fard prayers done and tracked, per month from January
+    pub months: [(u32, u32); 12],
+    /// Longest streak that fits inside the year
+    pub best_streak: u32,
+    pub quran_pages: f64,
+    /// Completed days (or weeks) per dhikr, most completed first
+    pub dhikr: Vec<(String, u32)>,
+}
+
+impl YearReview {
+    pub fn prayers_done(&self) -> u32 {
+        self.months.iter().map(|(done, _)| done).sum()
+    }
+
+    pub fn prayers_tracked(&self) -> u32 {
+        self.months.iter().map(|(_, total)| total).sum()
+    }
+
+    /// Done as a share of tracked, or None with nothing tracked.
+    pub fn percent(&self) -> Option<u32> {
+        let tracked = self.prayers_tracked();
+        (tracked > 0).then(|| (self.prayers_done() as f64 / tracked as f64 * 100.0).round() as u32)
+    }
+
+    /// One bar per month, ▁ to █ by completion; · for a month with nothing tracked.
+    pub fn sparkline(&self) -> String {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        self.months
+            .iter()
+            .map(|&(done, total)| {
+                if total == 0 {
+                    '·'
+                } else {
+                    BARS[(done as usize * (BARS.len() - 1) + total as usize / 2) / total as usize]
+                }
+            })
+            .collect()
+    }
+}
+
+/// A khatm measured against `quran.khatm_deadline`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KhatmPlan {
+    pub deadline: NaiveDate,
+    /// Days left counting today; 0 once the deadline has passed
+    pub days_left: u32,
+    /// Pages a day that finish on the deadline
+    pub pages_per_day: f64,
+    /// Whether the recent daily average keeps up with `pages_per_day`
+    pub on_pace: bool,
+}
+
+impl KhatmPlan {
+    /// Today's page goal: the plan's while the deadline is ahead, else `daily_target`.
+    pub fn target(plan: Option<&KhatmPlan>, daily_target: f64) -> f64 {
+        plan.filter(|p| p.days_left > 0)
+            .map_or(daily_target, |p| p.pages_per_day)
+    }
+
+    /// "12 days left", or "last day" on the deadline itself.
+    pub fn days_left_label(&self) -> String {
+        match self.days_left {
+            1 => "last day".to_string(),
+            n => format!("{} days left", n),
+        }
+    }
+
+    /// "by 19 Mar · 12 days left", or "deadline 19 Mar passed".
+    pub fn summary(&self) -> String {
+        let deadline = self.deadline.format("%-d %b");
+        match self.days_left {
+            0 => format!("deadline {} passed", deadline),
+            _ => format!("by {} · {}", deadline, self.days_left_label()),
+        }
+    }
+
+    /// "▲ ahead" or "▼ behind", by whether the recent pace makes the deadline.
+    pub fn pace_label(&self) -> &'static str {
+        if self.on_pace { "▲ ahead" } else { "▼ behind" }
+    }
+}
+
+/// Span of days the stats heatmap covers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatsRange {
+    /// The last 7 days
+    #[default]
+    Week,
+    /// The current month so far
+    Month,
+}
+
+impl StatsRange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatsRange::Week => "week",
+            StatsRange::Month => "month",
+        }
+    }
+}
+
+impl FromStr for StatsRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "week" => Ok(StatsRange::Week),
+            "month" => Ok(StatsRange::Month),
+            _ => Err(anyhow::anyhow!("Unknown range '{}'. Use: week, month", s)),
+        }
+    }
+}
+
+/// Progress toward a `[goals]` prayer target: the week (Saturday–Friday)
+/// or calendar month so far.
+#[derive(Debug, Clone, Copy)]
+pub struct Goal {
+    pub range: StatsRange,
+    pub done: u32,
+    pub target: u32,
+}
+
+impl Goal {
+    pub fn met(&self) -> bool {
+        self.done >= self.target
+    }
+}
+
+/// The stats range and prayer filter last chosen, restored on the next visit.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsView {
+    pub range: StatsRange,
+    /// Only count this prayer (None = all five)
+    pub prayer: Option<PrayerType>,
+}
+
+impl StatsView {
+    /// Stored form: "month" or "month,fajr".
+    pub fn encode(&self) -> String {
+        match &self.prayer {
+            Some(p) => format!("{},{}", self.range.as_str(), p.as_str()),
+            None => self.range.as_str().to_string(),
+        }
+    }
+
+    /// Falls back to the default view for anything unreadable.
+    pub fn decode(raw: &str) -> Self {
+        let mut parts = raw.split(',');
+        StatsView {
+            range: parts.next().and_then(|r| r.parse().ok()).unwrap_or_default(),
+            prayer: parts.next().and_then(|p| p.parse().ok()),
+        }
+    }
+
+    /// The filter after this one when cycling: all → Fajr → … → Isha → all.
+    pub fn next_prayer(&self) -> Option<PrayerType> {
+        let all = PrayerType::all();
+        match &self.prayer {
+            None => all.first().cloned(),
+            Some(p) => all.iter().skip_while(|q| *q != p).nth(1).cloned(),
+        }
+    }
+
+    /// "Fajr" or "All prayers"
+    pub fn prayer_label(&self) -> &'static str {
+        self.prayer.as_ref().map_or("All prayers", |p| p.display_name())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeeklyGrid {
     pub days: Vec<DailyStats>,
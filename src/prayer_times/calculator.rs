@@ -1,19 +1,138 @@
 use anyhow::{anyhow, Result};
-use chrono::{Duration, FixedOffset, NaiveDate, NaiveTime};
+use chrono::{Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 use rusqlite::Connection;
 use salah::prelude::*;
 
-use crate::db::repository::CacheRepo;
-use crate::models::PrayerType;
+use crate::config::settings::default_imsak_minutes;
+use crate::config::{AppConfig, PrayerAdjustments, SalahConfig, TimesSource};
+use crate::db::repository::{CacheRepo, CachedTimes, PrayerRepo};
+use crate::models::{PrayerType, QiyamEntry};
+use crate::utils::date::{date_str, place_in_order, DATETIME_FMT};
 
 #[derive(Debug, Clone)]
 pub struct PrayerTimesLocal {
+    /// `imsak_minutes` before Fajr
+    pub imsak: NaiveTime,
     pub fajr: NaiveTime,
     pub sunrise: NaiveTime,
     pub zuhr: NaiveTime,
     pub asr: NaiveTime,
     pub maghrib: NaiveTime,
     pub isha: NaiveTime,
+    /// Start of the last third of the night opened by this day's Maghrib,
+    /// usually after midnight
+    pub last_third_start: NaiveTime,
+}
+
+impl PrayerTimesLocal {
+    pub fn get(&self, prayer: &PrayerType) -> NaiveTime {
+        match prayer {
+            PrayerType::Fajr => self.fajr,
+            PrayerType::Zuhr => self.zuhr,
+            PrayerType::Asr => self.asr,
+            PrayerType::Maghrib => self.maghrib,
+            PrayerType::Isha => self.isha,
+        }
+    }
+
+    /// The five prayers of `date` as timestamps, on the next day for any
+    /// that fall after midnight.
+    pub fn prayer_datetimes(&self, date: NaiveDate) -> Vec<(PrayerType, NaiveDateTime)> {
+        let prayers = PrayerType::all();
+        let times: Vec<NaiveTime> = prayers.iter().map(|p| self.get(p)).collect();
+        prayers.into_iter().zip(place_in_order(date, &times)).collect()
+    }
+}
+
+/// Minutes after sunrise, and before true noon and sunset, when no prayer
+/// should be started.
+pub const FORBIDDEN_SUNRISE_MINUTES: i64 = 15;
+pub const FORBIDDEN_ZENITH_MINUTES: i64 = 10;
+pub const FORBIDDEN_SUNSET_MINUTES: i64 = 15;
+
+/// One of the three makruh times of the day, in local time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForbiddenWindow {
+    pub label: &'static str,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl PrayerTimesLocal {
+    /// The sun rising, at its zenith just before Zuhr, and setting before
+    /// Maghrib. Voluntary prayer after praying Fajr or Asr is disliked too,
+    /// but that depends on when you prayed, so it isn't a fixed window.
+    pub fn forbidden_windows(&self) -> [ForbiddenWindow; 3] {
+        [
+            ForbiddenWindow {
+                label: "sunrise",
+                start: self.sunrise,
+                end: self.sunrise + Duration::minutes(FORBIDDEN_SUNRISE_MINUTES),
+            },
+            ForbiddenWindow {
+                label: "zenith",
+                start: self.zuhr - Duration::minutes(FORBIDDEN_ZENITH_MINUTES),
+                end: self.zuhr,
+            },
+            ForbiddenWindow {
+                label: "sunset",
+                start: self.maghrib - Duration::minutes(FORBIDDEN_SUNSET_MINUTES),
+                end: self.maghrib,
+            },
+        ]
+    }
+
+    pub fn forbidden_at(&self, at: NaiveTime) -> Option<ForbiddenWindow> {
+        self.forbidden_windows()
+            .into_iter()
+            .find(|w| at >= w.start && at < w.end)
+    }
+}
+
+/// The night running from one day's Maghrib to the next day's Fajr, in local time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NightWindow {
+    /// Date of the Maghrib that opens the night
+    pub date: NaiveDate,
+    pub maghrib: NaiveDateTime,
+    pub last_third: NaiveDateTime,
+    pub fajr: NaiveDateTime,
+}
+
+impl NightWindow {
+    pub fn in_last_third(&self, at: NaiveDateTime) -> bool {
+        at >= self.last_third && at < self.fajr
+    }
+
+    /// Qiyam for this night prayed at `at`, noting whether it fell in the last third.
+    pub fn qiyam_at(&self, at: NaiveDateTime) -> QiyamEntry {
+        QiyamEntry {
+            night_date: date_str(self.date),
+            prayed_at: at.format(DATETIME_FMT).to_string(),
+            in_last_third: self.in_last_third(at),
+        }
+    }
+}
+
+/// A cached day as local times, with the night running to `next_fajr`.
+fn from_cached(date: NaiveDate, cached: &CachedTimes, next_fajr: NaiveTime) -> PrayerTimesLocal {
+    PrayerTimesLocal {
+        imsak: cached.imsak,
+        fajr: cached.fajr,
+        sunrise: cached.sunrise,
+        zuhr: cached.zuhr,
+        asr: cached.asr,
+        maghrib: cached.maghrib,
+        isha: cached.isha,
+        last_third_start: last_third_start(date, cached.maghrib, next_fajr),
+    }
+}
+
+/// Maghrib plus two thirds of the night until the next day's Fajr.
+fn last_third_start(date: NaiveDate, maghrib: NaiveTime, next_fajr: NaiveTime) -> NaiveTime {
+    let start = date.and_time(maghrib);
+    let end = (date + Duration::days(1)).and_time(next_fajr);
+    (start + (end - start) * 2 / 3).time()
 }
 
 pub struct PrayerCalculator {
@@ -22,6 +141,18 @@ pub struct PrayerCalculator {
     pub method_str: String,
     pub madhab_str: String,
     pub tz_offset_minutes: i32,
+    /// (Fajr, Isha) twilight angles in degrees — only used by the "Other" method
+    pub custom_angles: Option<(f64, f64)>,
+    pub high_latitude_str: String,
+    pub adjustments: PrayerAdjustments,
+    /// Observer height above sea level in metres (see `horizon_dip_minutes`)
+    pub elevation_m: f64,
+    /// Cap the Fajr angle at `LATER_FAJR_ANGLE`
+    pub later_fajr: bool,
+    /// Minutes between Imsak and Fajr
+    pub imsak_minutes: u32,
+    /// With `File`, only imported days are used and nothing is calculated
+    pub source: TimesSource,
 }
 
 impl PrayerCalculator {
@@ -31,64 +162,177 @@ impl PrayerCalculator {
         method: &str,
         madhab: &str,
         tz_offset_minutes: i32,
+        custom_angles: Option<(f64, f64)>,
     ) -> Result<Self> {
         // Validate method + madhab early
-        parse_method(method)?;
+        let parsed = parse_method(method)?;
         parse_madhab(madhab)?;
+        if parsed == Method::Other && custom_angles.is_none() {
+            return Err(anyhow!(
+                "Calculation method 'Other' needs custom angles — set both fajr_angle and \
+                 isha_angle under [salah] in config.toml, or re-run `sujood setup --reset`"
+            ));
+        }
         Ok(Self {
             lat,
             lng,
             method_str: method.to_string(),
             madhab_str: madhab.to_string(),
             tz_offset_minutes,
+            custom_angles,
+            high_latitude_str: "MiddleOfTheNight".to_string(),
+            adjustments: PrayerAdjustments::default(),
+            elevation_m: 0.0,
+            later_fajr: false,
+            imsak_minutes: default_imsak_minutes(),
+            source: TimesSource::Calculated,
         })
     }
 
+    /// Build a calculator from the `[salah]` config section, including the
+    /// high-latitude rule and manual adjustments.
+    pub fn from_config(salah: &SalahConfig) -> Result<Self> {
+        let mut calc = Self::new(
+            salah.latitude,
+            salah.longitude,
+            &salah.calc_method,
+            &salah.madhab,
+            salah.timezone_offset,
+            salah.custom_angles(),
+        )?;
+        HighLatitudeRule::parse(&salah.high_latitude_rule)?;
+        calc.high_latitude_str = salah.high_latitude_rule.clone();
+        calc.adjustments = salah.adjustments.clone();
+        calc.elevation_m = salah.elevation_m.max(0.0);
+        calc.later_fajr = salah.later_fajr;
+        calc.imsak_minutes = salah.imsak_minutes;
+        calc.source = salah.source;
+        Ok(calc)
+    }
+
     fn compute_times(&self, date: NaiveDate) -> Result<PrayerTimesLocal> {
         let coords = Coordinates::new(self.lat, self.lng);
         let method = parse_method(&self.method_str)?;
         let madhab = parse_madhab(&self.madhab_str)?;
-        let params = Configuration::with(method, madhab);
+        let mut params = Configuration::with(method, madhab);
+        // `new` guarantees angles are present for "Other"
+        if let (Method::Other, Some((fajr, isha))) = (method, self.custom_angles) {
+            params.fajr_angle = fajr;
+            params.isha_angle = isha;
+        } else if self.later_fajr {
+            params.fajr_angle = params.fajr_angle.min(LATER_FAJR_ANGLE);
+        }
 
-        let times = PrayerSchedule::new()
-            .on(date)
-            .for_location(coords)
-            .with_configuration(params)
-            .calculate()
-            .map_err(|e| anyhow!("Prayer calculation failed: {}", e))?;
+        // salah panics rather than erroring when the sun never sets or never
+        // reaches the twilight angle, so those days are checked up front.
+        check_sun_rises_and_sets(self.lat, date)?;
+        let day_after = |d: NaiveDate| d.succ_opt().unwrap_or(d);
+        let tomorrow_date = day_after(date);
+
+        let schedule_for = |day: NaiveDate| -> Result<(PrayerTimes, Twilight)> {
+            let (day_params, twilight) = reachable_params(params, self.lat, day);
+            let times = PrayerSchedule::new()
+                .on(day)
+                .for_location(coords)
+                .with_configuration(day_params)
+                .calculate()
+                .map_err(|e| anyhow!("Prayer calculation failed: {}", e))?;
+            Ok((times, twilight))
+        };
+        let (times, twilight) = schedule_for(date)?;
+        let (tomorrow, tomorrow_twilight) = schedule_for(tomorrow_date)?;
+        let (day_after_tomorrow, _) = schedule_for(day_after(tomorrow_date))?;
+
+        let rule = HighLatitudeRule::parse(&self.high_latitude_str)?.resolve(self.lat);
+        log::debug!(
+            "{}: lat {} lng {} elevation {} m, {} ({}), fajr {}° isha {}°, {:?}, {:?}, \
+             UTC offset {} min, imsak {} min, adjustments {:?}",
+            date,
+            self.lat,
+            self.lng,
+            self.elevation_m,
+            self.method_str,
+            self.madhab_str,
+            params.fajr_angle,
+            params.isha_angle,
+            rule,
+            twilight,
+            self.tz_offset_minutes,
+            self.imsak_minutes,
+            self.adjustments
+        );
+        let (fajr_utc, isha_utc) = rule.apply(&params, &times, &tomorrow, twilight);
+        let (next_fajr_utc, _) = rule.apply(&params, &tomorrow, &day_after_tomorrow, tomorrow_twilight);
 
         let offset = FixedOffset::east_opt(self.tz_offset_minutes * 60)
             .ok_or_else(|| anyhow!("Invalid timezone offset: {}", self.tz_offset_minutes))?;
 
-        let to_local = |utc: chrono::DateTime<chrono::Utc>| -> NaiveTime {
-            utc.with_timezone(&offset).time()
+        // Adjustments are applied to the full UTC timestamp so negative offsets
+        // and offsets that cross midnight wrap the clock time correctly.
+        let to_local = |utc: chrono::DateTime<chrono::Utc>, adj_minutes: i32| -> NaiveTime {
+            (utc + Duration::minutes(adj_minutes as i64))
+                .with_timezone(&offset)
+                .time()
         };
 
-        Ok(PrayerTimesLocal {
-            fajr: to_local(times.time(Prayer::Fajr)),
-            sunrise: to_local(times.time(Prayer::Sunrise)),
-            zuhr: to_local(times.time(Prayer::Dhuhr)),
-            asr: to_local(times.time(Prayer::Asr)),
-            maghrib: to_local(times.time(Prayer::Maghrib)),
-            isha: to_local(times.time(Prayer::Isha)),
-        })
+        // From higher up the sun is seen over the dipped horizon: earlier
+        // sunrise, later sunset. salah has no elevation input, so shift here.
+        let dip = Duration::seconds(
+            (horizon_dip_minutes(self.lat, date, self.elevation_m) * 60.0).round() as i64,
+        );
+
+        let adj = &self.adjustments;
+        // Measured on full timestamps, so a night running past midnight
+        // doesn't come out negative
+        let night_start = times.time(Prayer::Maghrib) + dip + Duration::minutes(adj.maghrib_adj as i64);
+        let night_end = next_fajr_utc + Duration::minutes(adj.fajr_adj as i64);
+        let last_third_utc = night_start + (night_end - night_start) * 2 / 3;
+
+        log::debug!(
+            "{} UTC: fajr {} sunrise {} zuhr {} asr {} maghrib {} isha {} (horizon dip {}s)",
+            date,
+            fajr_utc.format("%H:%M:%S"),
+            times.time(Prayer::Sunrise).format("%H:%M:%S"),
+            times.time(Prayer::Dhuhr).format("%H:%M:%S"),
+            times.time(Prayer::Asr).format("%H:%M:%S"),
+            times.time(Prayer::Maghrib).format("%H:%M:%S"),
+            isha_utc.format("%H:%M:%S"),
+            dip.num_seconds()
+        );
+
+        let fajr = to_local(fajr_utc, adj.fajr_adj);
+        let local = PrayerTimesLocal {
+            imsak: fajr - Duration::minutes(self.imsak_minutes as i64),
+            fajr,
+            sunrise: to_local(times.time(Prayer::Sunrise) - dip, 0),
+            zuhr: to_local(times.time(Prayer::Dhuhr), adj.zuhr_adj),
+            asr: to_local(times.time(Prayer::Asr), adj.asr_adj),
+            maghrib: to_local(times.time(Prayer::Maghrib) + dip, adj.maghrib_adj),
+            isha: to_local(isha_utc, adj.isha_adj),
+            last_third_start: to_local(last_third_utc, 0),
+        };
+        log::debug!("{} local: {:?}", date, local);
+        Ok(local)
     }
 
     pub fn times_for_date(&self, date: NaiveDate) -> Result<PrayerTimesLocal> {
         self.compute_times(date)
     }
 
-    /// Ensure prayer_times_cache has entries for today through `days_ahead` days.
-    pub fn ensure_cached(&self, conn: &Connection, days_ahead: u32) -> Result<()> {
-        let today = chrono::Local::now().date_naive();
-
+    /// Ensure prayer_times_cache has entries for `from` through `days_ahead` days after it.
+    /// An imported timetable is left as it is; days it lacks are reported when needed.
+    pub fn ensure_cached(&self, conn: &Connection, from: NaiveDate, days_ahead: u32) -> Result<()> {
+        if self.source == TimesSource::File {
+            return Ok(());
+        }
         for i in 0..=(days_ahead as i64) {
-            let date = today + Duration::days(i);
-            let date_str = date.format("%Y-%m-%d").to_string();
+            let date = from + Duration::days(i);
+            let date_str = date_str(date);
 
             if CacheRepo::get_times_for_date(conn, &date_str)?.is_none() {
                 let times = self.compute_times(date)?;
-                let cached = crate::db::repository::CachedTimes {
+                let cached = CachedTimes {
+                    imsak: times.imsak,
                     fajr: times.fajr,
                     sunrise: times.sunrise,
                     zuhr: times.zuhr,
@@ -102,27 +346,62 @@ impl PrayerCalculator {
         Ok(())
     }
 
+    /// Asr on `date` under the madhab not configured, with that madhab's
+    /// name. The madhab changes nothing else, so only Asr is worked out.
+    pub fn other_madhab_asr(&self, date: NaiveDate) -> Result<(&'static str, NaiveTime)> {
+        let (name, madhab) = match parse_madhab(&self.madhab_str)? {
+            Madhab::Hanafi => ("Shafi", Madhab::Shafi),
+            Madhab::Shafi => ("Hanafi", Madhab::Hanafi),
+        };
+        check_sun_rises_and_sets(self.lat, date)?;
+        let params = Configuration::with(parse_method(&self.method_str)?, madhab);
+        let (params, _) = reachable_params(params, self.lat, date);
+        let times = PrayerSchedule::new()
+            .on(date)
+            .for_location(Coordinates::new(self.lat, self.lng))
+            .with_configuration(params)
+            .calculate()
+            .map_err(|e| anyhow!("Prayer calculation failed: {}", e))?;
+        let offset = FixedOffset::east_opt(self.tz_offset_minutes * 60)
+            .ok_or_else(|| anyhow!("Invalid timezone offset: {}", self.tz_offset_minutes))?;
+        let asr = times.time(Prayer::Asr) + Duration::minutes(self.adjustments.asr_adj as i64);
+        Ok((name, asr.with_timezone(&offset).time()))
+    }
+
     /// Get times from cache (or compute if missing) for a specific date.
+    /// With `source = "file"` a day missing from the imported timetable is an error.
     pub fn get_cached_or_compute(
         &self,
         conn: &Connection,
         date: NaiveDate,
     ) -> Result<PrayerTimesLocal> {
-        let date_str = date.format("%Y-%m-%d").to_string();
+        let date_str = date_str(date);
 
-        if let Some(cached) = CacheRepo::get_times_for_date(conn, &date_str)? {
-            return Ok(PrayerTimesLocal {
-                fajr: cached.fajr,
-                sunrise: cached.sunrise,
-                zuhr: cached.zuhr,
-                asr: cached.asr,
-                maghrib: cached.maghrib,
-                isha: cached.isha,
-            });
+        // The last third needs tomorrow's Fajr too; without it in the
+        // cache, fall through and compute the day afresh
+        let next = date.succ_opt().unwrap_or(date);
+        let cached = CacheRepo::get_times_for_date(conn, &date_str)?;
+        let next_cached = CacheRepo::get_times_for_date(conn, &crate::utils::date::date_str(next))?;
+        if self.source == TimesSource::File {
+            let cached = cached.ok_or_else(|| {
+                anyhow!(
+                    "No imported prayer times for {}. Import a timetable covering it with \
+                     `sujood times import <file>`, or set source = \"calculated\" under [salah]",
+                    date_str
+                )
+            })?;
+            // On the timetable's last day, measure the night to that day's own Fajr
+            let next_fajr = next_cached.map_or(cached.fajr, |n| n.fajr);
+            return Ok(from_cached(date, &cached, next_fajr));
+        }
+        if let (Some(cached), Some(next_cached)) = (cached, next_cached) {
+            log::debug!("{}: cached times used, not recalculated", date);
+            return Ok(from_cached(date, &cached, next_cached.fajr));
         }
 
         let times = self.compute_times(date)?;
-        let cached = crate::db::repository::CachedTimes {
+        let cached = CachedTimes {
+            imsak: times.imsak,
             fajr: times.fajr,
             sunrise: times.sunrise,
             zuhr: times.zuhr,
@@ -134,42 +413,91 @@ impl PrayerCalculator {
         Ok(times)
     }
 
+    /// Freeze `date`'s current times onto its prayer rows (see `PrayerRepo::snapshot_times`).
+    pub fn snapshot_times(&self, conn: &Connection, date: NaiveDate) -> Result<()> {
+        let times = self.get_cached_or_compute(conn, date)?;
+        let snapshot: Vec<(PrayerType, NaiveTime)> = PrayerType::all()
+            .into_iter()
+            .map(|pt| {
+                let time = times.get(&pt);
+                (pt, time)
+            })
+            .collect();
+        PrayerRepo::snapshot_times(conn, &date_str(date), &snapshot)
+    }
+
+    /// The night opened by `date`'s Maghrib, split at the start of its last third.
+    pub fn night_of(&self, conn: &Connection, date: NaiveDate) -> Result<NightWindow> {
+        let next = date.succ_opt().unwrap_or(date);
+        let maghrib = date.and_time(self.get_cached_or_compute(conn, date)?.maghrib);
+        let fajr = next.and_time(self.get_cached_or_compute(conn, next)?.fajr);
+        let last_third = maghrib + (fajr - maghrib) * 2 / 3;
+        Ok(NightWindow { date, maghrib, last_third, fajr })
+    }
+
+    /// The night `now` belongs to: tonight once Maghrib has passed, otherwise last night.
+    pub fn night_at(&self, conn: &Connection, now: NaiveDateTime) -> Result<NightWindow> {
+        let today = now.date();
+        let maghrib = self.get_cached_or_compute(conn, today)?.maghrib;
+        if now.time() >= maghrib {
+            self.night_of(conn, today)
+        } else {
+            self.night_of(conn, today.pred_opt().unwrap_or(today))
+        }
+    }
+
     /// Returns (next PrayerType, seconds until it).
-    /// `now_time` is the current local time.
+    /// `now_time` is the current local time. Each prayer is placed on the
+    /// day it actually falls, so an Isha past midnight is still found after
+    /// it, and after today's last prayer the next is tomorrow's Fajr.
     pub fn get_next_prayer(
         &self,
         conn: &Connection,
         now_date: NaiveDate,
         now_time: NaiveTime,
     ) -> Result<Option<(PrayerType, i64)>> {
-        let today_times = self.get_cached_or_compute(conn, now_date)?;
-
-        let schedule = [
-            (PrayerType::Fajr, today_times.fajr),
-            (PrayerType::Zuhr, today_times.zuhr),
-            (PrayerType::Asr, today_times.asr),
-            (PrayerType::Maghrib, today_times.maghrib),
-            (PrayerType::Isha, today_times.isha),
-        ];
+        let now = now_date.and_time(now_time);
+        let mut upcoming = self.get_cached_or_compute(conn, now_date)?.prayer_datetimes(now_date);
 
-        for (prayer, time) in &schedule {
-            if *time > now_time {
-                let secs = (*time - now_time).num_seconds();
-                return Ok(Some((prayer.clone(), secs)));
+        // Before today's Fajr, yesterday's Isha may not have come in yet
+        if upcoming.first().is_some_and(|(_, at)| now < *at) {
+            let yesterday = now_date.pred_opt().map(|d| (d, self.get_cached_or_compute(conn, d)));
+            if let Some((yesterday, Ok(times))) = yesterday {
+                upcoming.extend(times.prayer_datetimes(yesterday));
             }
         }
 
-        // All prayers passed — next is Fajr tomorrow
-        let tomorrow = now_date.succ_opt().unwrap_or(now_date);
-        let tomorrow_times = self.get_cached_or_compute(conn, tomorrow)?;
-        let midnight_to_fajr = tomorrow_times.fajr.signed_duration_since(NaiveTime::from_hms_opt(0, 0, 0).unwrap());
-        let remaining_today = NaiveTime::from_hms_opt(23, 59, 59).unwrap()
-            .signed_duration_since(now_time);
-        let secs = remaining_today.num_seconds() + midnight_to_fajr.num_seconds() + 1;
-        Ok(Some((PrayerType::Fajr, secs)))
+        // All of today's prayers passed — next is Fajr tomorrow
+        if upcoming.iter().all(|(_, at)| *at <= now)
+            && let Some(tomorrow) = now_date.succ_opt()
+        {
+            let times = self.get_cached_or_compute(conn, tomorrow)?;
+            upcoming.extend(times.prayer_datetimes(tomorrow).into_iter().take(1));
+        }
+
+        Ok(upcoming
+            .into_iter()
+            .filter(|(_, at)| *at > now)
+            .min_by_key(|(_, at)| *at)
+            .map(|(prayer, at)| (prayer, (at - now).num_seconds())))
     }
 }
 
+/// Give `date` its five pending rows and, with `[day] snapshot_times` on,
+/// freeze the times they were created under onto them. A day whose times
+/// can't be worked out still gets its rows, just without a snapshot.
+pub fn ensure_rows(conn: &Connection, config: &AppConfig, date: NaiveDate) -> Result<()> {
+    PrayerRepo::ensure_rows_for_date(conn, &date_str(date))?;
+    if config.day.snapshot_times {
+        let snapshot = PrayerCalculator::from_config(&config.salah)
+            .and_then(|calc| calc.snapshot_times(conn, date));
+        if let Err(e) = snapshot {
+            log::warn!("{}: prayer rows created without a time snapshot: {:#}", date, e);
+        }
+    }
+    Ok(())
+}
+
 fn parse_method(s: &str) -> Result<Method> {
     match s {
         "MuslimWorldLeague" => Ok(Method::MuslimWorldLeague),
@@ -197,6 +525,242 @@ fn parse_madhab(s: &str) -> Result<Madhab> {
     }
 }
 
+/// How Fajr and Isha are bounded when twilight lasts most (or all) of the night.
+///
+/// salah 0.7 always clamps to the middle of the night and does not export its own
+/// rule type, so the tighter rules are applied here on top of its result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HighLatitudeRule {
+    MiddleOfTheNight,
+    SeventhOfTheNight,
+    TwilightAngle,
+    /// SeventhOfTheNight above 48° latitude, MiddleOfTheNight elsewhere
+    Recommended,
+}
+
+impl HighLatitudeRule {
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "MiddleOfTheNight" => Ok(Self::MiddleOfTheNight),
+            "SeventhOfTheNight" => Ok(Self::SeventhOfTheNight),
+            "TwilightAngle" => Ok(Self::TwilightAngle),
+            "Recommended" => Ok(Self::Recommended),
+            _ => Err(anyhow!(
+                "Unknown high-latitude rule: '{}' (expected one of: {})",
+                s,
+                HIGH_LATITUDE_RULES.join(", ")
+            )),
+        }
+    }
+
+    fn resolve(self, lat: f64) -> Self {
+        match self {
+            Self::Recommended if lat.abs() > 48.0 => Self::SeventhOfTheNight,
+            Self::Recommended => Self::MiddleOfTheNight,
+            rule => rule,
+        }
+    }
+
+    /// Portions of the night (Fajr, Isha) that twilight may occupy.
+    fn night_portions(self, params: &Parameters) -> (f64, f64) {
+        match self {
+            Self::SeventhOfTheNight => (1.0 / 7.0, 1.0 / 7.0),
+            Self::TwilightAngle => (params.fajr_angle / 60.0, params.isha_angle / 60.0),
+            Self::MiddleOfTheNight | Self::Recommended => (0.5, 0.5),
+        }
+    }
+
+    /// Fajr and Isha (UTC) with this rule applied.
+    /// Fajr is never earlier than `sunrise - portion × night`, and Isha never later
+    /// than `sunset + portion × night`, where night runs from sunset to tomorrow's sunrise.
+    /// When twilight never ends, the bound itself becomes the time.
+    fn apply(
+        self,
+        params: &Parameters,
+        today: &PrayerTimes,
+        tomorrow: &PrayerTimes,
+        twilight: Twilight,
+    ) -> (DateTime<Utc>, DateTime<Utc>) {
+        let fajr = today.time(Prayer::Fajr);
+        let isha = today.time(Prayer::Isha);
+        // salah already bounds to the middle of the night, and MoonsightingCommittee
+        // has its own seasonal twilight model
+        let salah_bounded =
+            self == Self::MiddleOfTheNight || params.method == Method::MoonsightingCommittee;
+        if salah_bounded && twilight.fajr_reached && twilight.isha_reached {
+            return (fajr, isha);
+        }
+
+        // Undo salah's method offsets so the bounds are computed from raw sun times
+        let offset = |prayer: Prayer| Duration::minutes(params.time_adjustments(prayer));
+        let sunrise = today.time(Prayer::Sunrise) - offset(Prayer::Sunrise);
+        let sunset = today.time(Prayer::Maghrib) - offset(Prayer::Maghrib);
+        let next_sunrise = tomorrow.time(Prayer::Sunrise) - offset(Prayer::Sunrise);
+        let night_secs = (next_sunrise - sunset).num_seconds() as f64;
+        let (fajr_portion, isha_portion) = self.night_portions(params);
+
+        let safe_fajr = sunrise - Duration::seconds((fajr_portion * night_secs) as i64)
+            + offset(Prayer::Fajr);
+        let fajr = if twilight.fajr_reached {
+            fajr.max(safe_fajr)
+        } else {
+            safe_fajr
+        };
+
+        let safe_isha = sunset + Duration::seconds((isha_portion * night_secs) as i64)
+            + offset(Prayer::Isha);
+        // A fixed interval after Maghrib (e.g. Umm al-Qura) is never bounded
+        let isha = if params.isha_interval > 0 {
+            isha
+        } else if twilight.isha_reached {
+            isha.min(safe_isha)
+        } else {
+            safe_isha
+        };
+
+        (fajr, isha)
+    }
+}
+
+/// Whether the sun gets deep enough below the horizon for the angle-based times.
+#[derive(Debug, Clone, Copy)]
+struct Twilight {
+    fajr_reached: bool,
+    isha_reached: bool,
+}
+
+/// Safety margin (degrees) for the approximate declination used below.
+const DEPTH_MARGIN: f64 = 1.0;
+
+/// Approximate solar declination (degrees) for a date.
+fn solar_declination(date: NaiveDate) -> f64 {
+    let day = date.ordinal() as f64;
+    -23.44 * (2.0 * std::f64::consts::PI / 365.0 * (day + 10.0)).cos()
+}
+
+/// Lowest and highest sun altitude (degrees) over the day at this latitude.
+fn solar_altitude_range(lat: f64, date: NaiveDate) -> (f64, f64) {
+    let decl = solar_declination(date) * lat.signum();
+    let lat = lat.abs();
+    (lat + decl - 90.0, 90.0 - lat + decl)
+}
+
+/// Minutes by which sunrise comes earlier and sunset later for an observer
+/// `elevation_m` above the surrounding terrain.
+///
+/// Approximation: the horizon dips by 2.076′·√h (geometric dip plus standard
+/// refraction), and near the horizon the sun's altitude changes at
+/// 15°/h · cos φ · cos δ · sin H₀. Good to within a minute outside polar regions;
+/// it assumes an unobstructed horizon at sea level, so mountains in the way
+/// reduce the real effect.
+fn horizon_dip_minutes(lat: f64, date: NaiveDate, elevation_m: f64) -> f64 {
+    if elevation_m <= 0.0 {
+        return 0.0;
+    }
+    let dip_deg = 2.076 * elevation_m.sqrt() / 60.0;
+
+    let phi = lat.to_radians();
+    let decl = solar_declination(date).to_radians();
+    let h0 = (-0.833f64).to_radians();
+    let cos_h = (h0.sin() - phi.sin() * decl.sin()) / (phi.cos() * decl.cos());
+    if cos_h.abs() >= 1.0 {
+        return 0.0;
+    }
+    let sin_h = (1.0 - cos_h * cos_h).sqrt();
+
+    // Degrees of altitude per minute at the horizon crossing
+    let rate = 0.25 * phi.cos() * decl.cos() * sin_h;
+    if rate <= f64::EPSILON {
+        return 0.0;
+    }
+    (dip_deg / rate).min(MAX_DIP_MINUTES)
+}
+
+/// Cap for the dip correction, where the sun grazes the horizon near the poles.
+const MAX_DIP_MINUTES: f64 = 30.0;
+
+fn sun_rises_and_sets(lat: f64, date: NaiveDate) -> bool {
+    let (lowest, highest) = solar_altitude_range(lat, date);
+    lowest < -0.833 - DEPTH_MARGIN && highest > -0.833 + DEPTH_MARGIN
+}
+
+/// Err unless the sun rises and sets on `date` and the two days after it.
+/// salah works out the next day's sun for a schedule, and `compute_times`
+/// schedules tomorrow too, so a day next to polar day or night would panic.
+fn check_sun_rises_and_sets(lat: f64, date: NaiveDate) -> Result<()> {
+    let day_after = |d: NaiveDate| d.succ_opt().unwrap_or(d);
+    let tomorrow = day_after(date);
+    for day in [date, tomorrow, day_after(tomorrow)] {
+        if !sun_rises_and_sets(lat, day) {
+            return Err(anyhow!(
+                "The sun does not rise and set at latitude {:.2} on {} — prayer times can't be calculated",
+                lat,
+                day
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// `params` with any unreachable twilight angle swapped for the horizon, so salah
+/// can still produce the other times. salah also needs tomorrow's Fajr for its
+/// night calculations, so both days are checked.
+fn reachable_params(mut params: Parameters, lat: f64, date: NaiveDate) -> (Parameters, Twilight) {
+    let reaches = |angle: f64, day: NaiveDate| {
+        solar_altitude_range(lat, day).0 < -angle - DEPTH_MARGIN
+    };
+    let next = date.succ_opt().unwrap_or(date);
+    let twilight = Twilight {
+        fajr_reached: reaches(params.fajr_angle, date) && reaches(params.fajr_angle, next),
+        isha_reached: params.isha_interval > 0 || reaches(params.isha_angle, date),
+    };
+    if !twilight.fajr_reached {
+        params.fajr_angle = 0.0;
+    }
+    if !twilight.isha_reached {
+        params.isha_angle = 0.0;
+    }
+    (params, twilight)
+}
+
+/// Fajr angle used by the "later Fajr" preference — the 15° of ISNA, against
+/// the 18°+ most other methods use.
+pub const LATER_FAJR_ANGLE: f64 = 15.0;
+
+/// A method's own Fajr angle, or None for "Other" / unknown names.
+pub fn method_fajr_angle(method: &str) -> Option<f64> {
+    match parse_method(method) {
+        Ok(Method::Other) | Err(_) => None,
+        Ok(m) => Some(Configuration::with(m, Madhab::Shafi).fajr_angle),
+    }
+}
+
+pub const HIGH_LATITUDE_RULES: &[&str] = &[
+    "MiddleOfTheNight",
+    "SeventhOfTheNight",
+    "TwilightAngle",
+    "Recommended",
+];
+
+/// The calculation method conventionally used in a country, keyed by
+/// ISO 3166-1 alpha-2 code. Anywhere unlisted falls back to Muslim World League.
+pub fn suggested_method_for_country(code: &str) -> &'static str {
+    match code.trim().to_uppercase().as_str() {
+        "PK" | "IN" | "BD" | "AF" => "Karachi",
+        "EG" | "SD" | "SY" | "LB" | "LY" => "Egyptian",
+        "SA" | "YE" => "UmmAlQura",
+        "AE" => "Dubai",
+        "KW" => "Kuwait",
+        "QA" => "Qatar",
+        "SG" | "MY" | "BN" | "ID" => "Singapore",
+        "IR" => "Tehran",
+        "TR" => "Turkey",
+        "US" | "CA" => "NorthAmerica",
+        "GB" => "MoonsightingCommittee",
+        _ => "MuslimWorldLeague",
+    }
+}
+
 pub const CALC_METHODS: &[&str] = &[
     "MuslimWorldLeague",
     "Egyptian",
@@ -212,3 +776,260 @@ pub const CALC_METHODS: &[&str] = &[
     "Turkey",
     "Other",
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::repository::QiyamRepo;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// Minutes from `from` to `to`, counting `to` on the next day when it
+    /// comes earlier on the clock.
+    fn minutes_after(from: NaiveTime, to: NaiveTime) -> i64 {
+        (to - from).num_minutes().rem_euclid(24 * 60)
+    }
+
+    #[test]
+    fn next_prayer_at_ten_to_midnight_is_tomorrows_fajr() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+        let calc = PrayerCalculator::new(40.0, 0.0, "MuslimWorldLeague", "Shafi", 0, None).unwrap();
+        let day = date(2026, 3, 20);
+        let now = day.and_hms_opt(23, 50, 0).unwrap();
+
+        let fajr = calc.get_cached_or_compute(&conn, date(2026, 3, 21)).unwrap().fajr;
+        let expected = (date(2026, 3, 21).and_time(fajr) - now).num_seconds();
+        let next = calc.get_next_prayer(&conn, day, now.time()).unwrap();
+        assert_eq!(next, Some((PrayerType::Fajr, expected)));
+    }
+
+    #[test]
+    fn isha_past_midnight_is_still_next_after_maghrib() {
+        // Trondheim in June, where the middle of the night comes after midnight
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+        let mut calc = PrayerCalculator::new(63.43, 10.40, "MuslimWorldLeague", "Shafi", 120, None)
+            .unwrap();
+        calc.high_latitude_str = "MiddleOfTheNight".to_string();
+        let day = date(2026, 6, 15);
+        let times = calc.get_cached_or_compute(&conn, day).unwrap();
+        assert!(times.isha < times.maghrib, "Isha {} should wrap past midnight", times.isha);
+        let isha = date(2026, 6, 16).and_time(times.isha);
+
+        // Just before midnight Isha is an hour or so away, not a day
+        let now = day.and_hms_opt(23, 59, 0).unwrap();
+        let next = calc.get_next_prayer(&conn, day, now.time()).unwrap();
+        assert_eq!(next, Some((PrayerType::Isha, (isha - now).num_seconds())));
+
+        // Past midnight it's still yesterday's Isha that comes next
+        let now = date(2026, 6, 16).and_hms_opt(0, 30, 0).unwrap();
+        let next = calc.get_next_prayer(&conn, now.date(), now.time()).unwrap();
+        assert_eq!(next, Some((PrayerType::Isha, (isha - now).num_seconds())));
+    }
+
+    #[test]
+    fn high_latitude_rule_bounds_summer_twilight_above_60() {
+        // Trondheim in June: the sun sets, but never gets 18° below the horizon
+        let mut calc = PrayerCalculator::new(63.43, 10.40, "MuslimWorldLeague", "Shafi", 120, None)
+            .unwrap();
+        let day = date(2026, 6, 15);
+
+        calc.high_latitude_str = "SeventhOfTheNight".to_string();
+        let seventh = calc.times_for_date(day).unwrap();
+        calc.high_latitude_str = "MiddleOfTheNight".to_string();
+        let middle = calc.times_for_date(day).unwrap();
+
+        for times in [&seventh, &middle] {
+            assert!(times.fajr < times.sunrise);
+            assert!(minutes_after(times.maghrib, times.isha) > 0);
+        }
+        // A seventh of a ~3½ hour night sits close to sunset and sunrise
+        assert!(minutes_after(seventh.maghrib, seventh.isha) <= 40);
+        assert!(minutes_after(seventh.fajr, seventh.sunrise) <= 40);
+        assert!(
+            minutes_after(middle.maghrib, middle.isha) > minutes_after(seventh.maghrib, seventh.isha)
+        );
+
+        // Recommended picks the seventh this far north
+        calc.high_latitude_str = "Recommended".to_string();
+        let recommended = calc.times_for_date(day).unwrap();
+        assert_eq!((recommended.fajr, recommended.isha), (seventh.fajr, seventh.isha));
+    }
+
+    #[test]
+    fn qiyam_records_whether_it_fell_in_the_last_third() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+        let calc = PrayerCalculator::new(21.42, 39.83, "UmmAlQura", "Shafi", 180, None).unwrap();
+        let night = calc.night_of(&conn, date(2026, 10, 16)).unwrap();
+        assert!(night.maghrib < night.last_third && night.last_third < night.fajr);
+
+        let cases = [
+            (night.last_third + Duration::minutes(10), true),
+            (night.fajr - Duration::minutes(1), true),
+            (night.last_third - Duration::minutes(1), false),
+            (night.maghrib + Duration::hours(2), false),
+            (night.fajr, false),
+        ];
+        for (at, in_last_third) in cases {
+            QiyamRepo::mark(&conn, &night.qiyam_at(at)).unwrap();
+            let stored = QiyamRepo::get(&conn, "2026-10-16").unwrap().unwrap();
+            assert_eq!(stored.in_last_third, in_last_third, "prayed at {at}");
+            assert_eq!(stored.prayed_at, at.format(DATETIME_FMT).to_string());
+        }
+    }
+
+    #[test]
+    fn other_method_needs_custom_angles() {
+        let err = PrayerCalculator::new(51.5, -0.13, "Other", "Shafi", 0, None)
+            .err()
+            .expect("Other without angles should be rejected");
+        assert!(err.to_string().contains("fajr_angle and isha_angle"));
+
+        let calc = PrayerCalculator::new(51.5, -0.13, "Other", "Shafi", 0, Some((16.0, 14.0)))
+            .expect("Other with angles should be accepted");
+        let times = calc.times_for_date(date(2026, 3, 20)).unwrap();
+        assert!(times.fajr < times.sunrise && times.maghrib < times.isha);
+    }
+
+    #[test]
+    fn snapshot_survives_a_config_change() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+        let day = date(2026, 10, 16);
+        let scheduled = |conn: &Connection| -> Vec<Option<NaiveTime>> {
+            PrayerRepo::get_by_date(conn, "2026-10-16")
+                .unwrap()
+                .iter()
+                .map(|p| p.scheduled_time)
+                .collect()
+        };
+
+        let mut config = AppConfig::default();
+        config.day.snapshot_times = true;
+        ensure_rows(&conn, &config, day).unwrap();
+        let before = scheduled(&conn);
+        assert_eq!(before.len(), 5);
+        assert!(before.iter().all(Option::is_some));
+
+        // A new method and adjustment move the times, as a settings change
+        // would after clearing the cache
+        config.salah.calc_method = "Karachi".to_string();
+        config.salah.adjustments.fajr_adj = 15;
+        CacheRepo::clear_computed(&conn).unwrap();
+        ensure_rows(&conn, &config, day).unwrap();
+        let now = PrayerCalculator::from_config(&config.salah)
+            .unwrap()
+            .get_cached_or_compute(&conn, day)
+            .unwrap();
+        assert_ne!(Some(now.fajr), before[0]);
+        assert_eq!(scheduled(&conn), before);
+    }
+
+    #[test]
+    fn rows_are_not_snapshotted_unless_enabled() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+        ensure_rows(&conn, &AppConfig::default(), date(2026, 10, 16)).unwrap();
+        let rows = PrayerRepo::get_by_date(&conn, "2026-10-16").unwrap();
+        assert_eq!(rows.len(), 5);
+        assert!(rows.iter().all(|p| p.scheduled_time.is_none()));
+    }
+
+    #[test]
+    fn country_codes_suggest_their_usual_method() {
+        let cases = [
+            ("PK", "Karachi"),
+            ("eg", "Egyptian"),
+            (" SA ", "UmmAlQura"),
+            ("TR", "Turkey"),
+            ("US", "NorthAmerica"),
+            ("GB", "MoonsightingCommittee"),
+            ("MY", "Singapore"),
+        ];
+        for (code, method) in cases {
+            assert_eq!(suggested_method_for_country(code), method, "{code}");
+        }
+        for unknown in ["FR", "ZZ", ""] {
+            assert_eq!(suggested_method_for_country(unknown), "MuslimWorldLeague");
+        }
+        // Every suggestion is a method the calculator accepts
+        for code in ["PK", "EG", "SA", "AE", "KW", "QA", "SG", "IR", "TR", "US", "GB", "FR"] {
+            assert!(parse_method(suggested_method_for_country(code)).is_ok());
+        }
+    }
+
+    #[test]
+    fn elevation_delays_maghrib_by_a_couple_of_minutes() {
+        let mut calc = PrayerCalculator::new(40.0, 0.0, "MuslimWorldLeague", "Shafi", 0, None)
+            .unwrap();
+        let day = date(2026, 3, 20);
+        let sea_level = calc.times_for_date(day).unwrap();
+        calc.elevation_m = 1000.0;
+        let raised = calc.times_for_date(day).unwrap();
+
+        let later = (raised.maghrib - sea_level.maghrib).num_seconds();
+        assert!((90..=360).contains(&later), "Maghrib moved {later}s");
+        assert_eq!((sea_level.sunrise - raised.sunrise).num_seconds(), later);
+        // Only the horizon moves, not the sun's other positions
+        assert_eq!(raised.zuhr, sea_level.zuhr);
+        assert_eq!(raised.fajr, sea_level.fajr);
+    }
+
+    #[test]
+    fn elevation_meters_in_config_shifts_sunrise_and_maghrib() {
+        let salah = |extra: &str| -> crate::config::SalahConfig {
+            toml::from_str(&format!("latitude = 40.0\nlongitude = 0.0\n{extra}")).unwrap()
+        };
+        let day = date(2026, 3, 20);
+        let sea_level = PrayerCalculator::from_config(&salah("")).unwrap().times_for_date(day);
+        let raised = PrayerCalculator::from_config(&salah("elevation_meters = 1500"))
+            .unwrap()
+            .times_for_date(day);
+        let (sea_level, raised) = (sea_level.unwrap(), raised.unwrap());
+
+        let later = (raised.maghrib - sea_level.maghrib).num_seconds();
+        assert!((150..=420).contains(&later), "Maghrib moved {later}s at 1500 m");
+        assert!(raised.sunrise < sea_level.sunrise);
+        assert_eq!(raised.asr, sea_level.asr);
+    }
+
+    #[test]
+    fn later_fajr_comes_after_the_method_default() {
+        // Toronto in spring, where Fajr still reaches 18°
+        let mut calc =
+            PrayerCalculator::new(43.65, -79.38, "MuslimWorldLeague", "Shafi", -240, None).unwrap();
+        let day = date(2026, 4, 1);
+        let default = calc.times_for_date(day).unwrap();
+        calc.later_fajr = true;
+        let later = calc.times_for_date(day).unwrap();
+
+        let delay = (later.fajr - default.fajr).num_minutes();
+        assert!((10..=30).contains(&delay), "Fajr moved {delay} min");
+        assert_eq!(later.imsak, later.fajr - Duration::minutes(calc.imsak_minutes as i64));
+        assert_eq!((later.zuhr, later.isha), (default.zuhr, default.isha));
+
+        // A method already at or below 15° is left as it is
+        let mut isna = PrayerCalculator::new(43.65, -79.38, "NorthAmerica", "Shafi", -240, None)
+            .unwrap();
+        let before = isna.times_for_date(day).unwrap().fajr;
+        isna.later_fajr = true;
+        assert_eq!(isna.times_for_date(day).unwrap().fajr, before);
+    }
+    #[test]
+    fn other_asr_errs_on_the_day_before_polar_night() {
+        // Tromsø: the last day the sun still rises before it stops for winter
+        let calc = PrayerCalculator::new(69.65, 18.96, "MuslimWorldLeague", "Shafi", 60, None)
+            .unwrap();
+        let mut day = date(2026, 11, 1);
+        while sun_rises_and_sets(calc.lat, day.succ_opt().unwrap()) {
+            day = day.succ_opt().unwrap();
+        }
+        assert!(sun_rises_and_sets(calc.lat, day));
+        assert!(calc.other_madhab_asr(day).is_err());
+        assert!(calc.other_madhab_asr(day - Duration::days(3)).is_ok());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod calculator;
+pub mod timetable;
 
 pub use calculator::PrayerCalculator;
 #[allow(unused_imports)]
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+use crate::db::repository::CachedTimes;
+use crate::utils::date::DATE_FMT;
+
+/// Columns a timetable must have, in this order.
+pub const COLUMNS: [&str; 7] = ["date", "fajr", "sunrise", "zuhr", "asr", "maghrib", "isha"];
+
+/// Malformed lines listed before the rest are summarised as a count.
+const MAX_REPORTED: usize = 10;
+
+/// Parse a published timetable: one `date,fajr,sunrise,zuhr,asr,maghrib,isha`
+/// line per day, times as `HH:MM`. A header line, blank lines and `#`
+/// comments are skipped. Imsak is set `imsak_minutes` before Fajr. Every
+/// malformed line is reported with its line number, and nothing is returned
+/// unless the whole file is good.
+pub fn parse(csv: &str, imsak_minutes: u32) -> Result<Vec<(NaiveDate, CachedTimes)>> {
+    let mut days: BTreeMap<NaiveDate, CachedTimes> = BTreeMap::new();
+    let mut problems = Vec::new();
+
+    for (idx, line) in csv.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim().trim_matches('"')).collect();
+        if fields[0].eq_ignore_ascii_case(COLUMNS[0]) {
+            continue;
+        }
+        match parse_line(&fields, imsak_minutes) {
+            Ok((date, times)) => {
+                if days.insert(date, times).is_some() {
+                    problems.push(format!("line {}: {} appears more than once", line_no, date));
+                }
+            }
+            Err(e) => problems.push(format!("line {}: {}", line_no, e)),
+        }
+    }
+
+    if !problems.is_empty() {
+        let mut report: Vec<String> = problems.iter().take(MAX_REPORTED).cloned().collect();
+        if problems.len() > MAX_REPORTED {
+            report.push(format!("… and {} more", problems.len() - MAX_REPORTED));
+        }
+        bail!(
+            "{} malformed line{} — nothing imported:\n  {}",
+            problems.len(),
+            if problems.len() == 1 { "" } else { "s" },
+            report.join("\n  ")
+        );
+    }
+    if days.is_empty() {
+        bail!("No days found. Expected lines like: {}", COLUMNS.join(","));
+    }
+    Ok(days.into_iter().collect())
+}
+
+fn parse_line(fields: &[&str], imsak_minutes: u32) -> Result<(NaiveDate, CachedTimes)> {
+    if fields.len() != COLUMNS.len() {
+        bail!("expected {} columns ({}), found {}", COLUMNS.len(), COLUMNS.join(","), fields.len());
+    }
+    let date = NaiveDate::parse_from_str(fields[0], DATE_FMT)
+        .map_err(|_| anyhow!("invalid date '{}' (expected YYYY-MM-DD)", fields[0]))?;
+    let mut times = [NaiveTime::MIN; 6];
+    for (slot, (column, value)) in times.iter_mut().zip(COLUMNS[1..].iter().zip(&fields[1..])) {
+        *slot = NaiveTime::parse_from_str(value, "%H:%M")
+            .map_err(|_| anyhow!("invalid {} time '{}' (expected HH:MM)", column, value))?;
+    }
+    let [fajr, sunrise, zuhr, asr, maghrib, isha] = times;
+    Ok((
+        date,
+        CachedTimes {
+            imsak: fajr - Duration::minutes(imsak_minutes as i64),
+            fajr,
+            sunrise,
+            zuhr,
+            asr,
+            maghrib,
+            isha,
+        },
+    ))
+}
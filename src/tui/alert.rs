@@ -0,0 +1,75 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// What to play when a prayer time enters, from `[notifications] sound`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Alert {
+    Off,
+    /// The terminal bell
+    Bell,
+    /// An audio file, played through the system's command-line player
+    File(PathBuf),
+}
+
+impl Alert {
+    /// "off", "bell", or a path to an audio file (`~/` is expanded).
+    pub fn from_config(sound: &str) -> Self {
+        match sound.trim() {
+            "" | "off" => Alert::Off,
+            "bell" => Alert::Bell,
+            path => {
+                let path = match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+                    (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+                    _ => PathBuf::from(path),
+                };
+                Alert::File(path)
+            }
+        }
+    }
+
+    /// Fire and forget — a file plays in the background, and falls back to
+    /// the bell when no player could be started.
+    pub fn play(&self) {
+        match self {
+            Alert::Off => {}
+            Alert::Bell => bell(),
+            Alert::File(path) => {
+                if !play_file(path) {
+                    bell();
+                }
+            }
+        }
+    }
+}
+
+fn bell() {
+    let mut out = std::io::stdout();
+    let _ = out.write_all(b"\x07");
+    let _ = out.flush();
+}
+
+/// Try the usual players for the platform, stopping at the first that starts.
+fn play_file(path: &PathBuf) -> bool {
+    let players: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("afplay", &[])]
+    } else {
+        &[
+            ("paplay", &[]),
+            ("pw-play", &[]),
+            ("aplay", &["-q"]),
+            ("ffplay", &["-nodisp", "-autoexit", "-loglevel", "quiet"]),
+        ]
+    };
+    path.exists()
+        && players.iter().any(|(cmd, args)| {
+            Command::new(cmd)
+                .args(*args)
+                .arg(path)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .is_ok()
+        })
+}
@@ -1,29 +1,55 @@
-use anyhow::Result;
-use chrono::Local;
-use crossterm::event::{KeyCode, KeyEventKind};
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use crossterm::event::{
+    DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Position, Rect},
     style::Modifier,
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 use rusqlite::Connection;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use crate::cli::catchup::{self, CatchupAction, Gap};
 use crate::config::AppConfig;
-use crate::db::repository::{DhikrRepo, PrayerRepo, QadaRepo, QuranRepo, StatsRepo};
-use crate::models::{DailyStats, DhikrDef, DhikrLog, DhikrType, Prayer, PrayerType, Streak};
-use crate::utils::hijri::today_hijri_string;
+use crate::db::repository::{
+    DhikrRepo, EidRepo, FastingRepo, JummahRepo, MetaRepo, NaflRepo, PrayerRepo, QadaRepo, QiyamRepo,
+    QuranRepo, StatsRepo,
+};
+use crate::models::{
+    group_dhikr, remaining_prayers, DailyStats, DhikrDef, DhikrFrequency, DhikrLog, DhikrType, Eid,
+    EidEntry, Goal, JummahEntry, Khatm, KhatmPlan, Nafl, OnTime, Prayer, PrayerStatus, PrayerType, QadaPace, QiyamEntry, StatsRange,
+    StatsView, Streak, SunnahFast,
+};
+use crate::utils::date::{
+    date_str, jummah_leave_at, jummah_start, last_friday, now_local, place_in_order, today_date,
+    DayCutoff, DATETIME_FMT,
+};
+use crate::utils::format::format_pages;
+use crate::utils::hijri::{
+    days_until_month, eid_on, hijri_month_days, is_ramadan, sunnah_fast_on, today_hijri_string,
+    HijriMonth,
+};
+use crate::prayer_times::calculator::{self, ForbiddenWindow, NightWindow, PrayerTimesLocal};
 use crate::prayer_times::PrayerCalculator;
+use crate::tui::alert::Alert;
 use crate::tui::events::{Event, EventHandler};
+use crate::tui::keymap::{Action, Keymap};
+use crate::tui::terminal;
 use crate::tui::theme;
-use crate::tui::widgets::{adhkar, header, next_prayer, prayers, qada, quran, statusbar, streak};
+use crate::tui::widgets::{
+    adhkar, fasting, header, hijri, month, nafl, next_prayer, prayers, qada, qiyam, quran, statusbar, streak,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum View {
     Dashboard,
     Stats,
+    MonthStats,
+    Hijri,
     Help,
 }
 
@@ -31,6 +57,7 @@ pub enum View {
 pub enum FocusSection {
     Prayers,
     Dhikr,
+    Nafl,
     None,
 }
 
@@ -38,6 +65,94 @@ pub enum FocusSection {
 pub enum InputMode {
     Normal,
     QuranInput,
+    NoteInput,
+    DhikrFilter,
+}
+
+/// What the `i` popup shows for one dhikr.
+pub struct DhikrDetail {
+    pub def: DhikrDef,
+    pub streak: Streak,
+    /// The last `DETAIL_STRIP_LEN` days (or weeks), oldest first
+    pub recent: Vec<(NaiveDate, bool)>,
+}
+
+const DETAIL_STRIP_LEN: usize = 7;
+/// Longest prayer note the `n` popup accepts.
+const NOTE_MAX_CHARS: usize = 80;
+/// How close to zero the countdown must have been for a change of next
+/// prayer to count as that prayer entering.
+const ALERT_WINDOW_SECS: i64 = 60;
+/// Fastest `ui.tick_ms` honoured; anything quicker only burns CPU.
+const MIN_TICK_MS: u64 = 100;
+
+/// Where the dashboard's fixed panels go; clicks are matched against the
+/// same areas the last frame was drawn in.
+struct DashboardLayout {
+    header: Rect,
+    prayers: Rect,
+    adhkar: Rect,
+    quran: Rect,
+    right: Rect,
+    status: Rect,
+}
+
+impl DashboardLayout {
+    fn new(area: Rect) -> Self {
+        let outer_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(5), // header
+                Constraint::Min(0),    // body
+                Constraint::Length(1), // status bar
+            ])
+            .split(area);
+
+        // Body split into columns
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(outer_chunks[1]);
+
+        // Left column: Prayers + Adhkar + Quran
+        let left_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(9),  // prayers
+                Constraint::Length(10), // adhkar
+                Constraint::Length(4),  // quran
+            ])
+            .split(columns[0]);
+
+        Self {
+            header: outer_chunks[0],
+            prayers: left_chunks[0],
+            adhkar: left_chunks[1],
+            quran: left_chunks[2],
+            right: columns[1],
+            status: outer_chunks[2],
+        }
+    }
+}
+
+/// The line of a bordered list under `position`, counting from the first
+/// line inside the border.
+fn list_line(area: Rect, position: Position) -> Option<usize> {
+    let inner = area.inner(Margin { horizontal: 1, vertical: 1 });
+    inner.contains(position).then(|| (position.y - inner.y) as usize)
+}
+
+/// The header's Hijri date for `date`, and whether the offset had to be left
+/// out: if the adjusted day can't be converted, `date` itself is shown
+/// rather than passing some other day off as the adjusted one.
+fn header_hijri(date: NaiveDate, offset_days: i32) -> (String, bool) {
+    match today_hijri_string(date, offset_days) {
+        Ok(hijri) => (hijri, false),
+        Err(_) => match today_hijri_string(date, 0) {
+            Ok(hijri) => (hijri, true),
+            Err(_) => ("Hijri date unavailable".to_string(), false),
+        },
+    }
 }
 
 pub struct App {
@@ -46,30 +161,79 @@ pub struct App {
     pub focus_section: FocusSection,
     pub focus_idx: usize,
     pub should_quit: bool,
+    pub confirm_quit: bool, // Esc pressed once with `ui.confirm_quit`; the next key decides
     pub input_mode: InputMode,
     pub input_buffer: String,
     pub input_error: Option<String>,   // shown in quran popup on bad input
+    pub quran_history: Vec<f64>,       // recent page counts, most recent first
+    pub history_idx: Option<usize>,    // position while recalling with ↑↓
     pub show_qada_overlay: bool,       // `q` toggles this
+    pub dhikr_detail: Option<DhikrDetail>, // `i` on a focused dhikr
+    pub prayer_detail: Option<Prayer>,     // `i` on a focused prayer
+    pub catchup_gap: Option<Gap>,      // welcome-back prompt after untracked days
+    pub show_day_summary: bool,        // end-of-day reflection, opened at the cutoff
+    pub day_summary_dismissed: bool,   // Esc'd for this session — don't reopen today
+    pub day_cutoff: Option<DayCutoff>,
+    pub collapsed_groups: HashSet<String>,
+    pub dhikr_filter: String, // kept from `/` after Enter; typing happens in input_buffer
 
     // Cached state (refreshed on tick/action)
+    pub live_date: NaiveDate, // the real tracking day; `today` may be an earlier one being reviewed
+    pub today: NaiveDate,
     pub today_str: String,
     pub hijri_str: String,
+    pub hijri_unadjusted: bool, // `hijri_str` is missing the configured offset
     pub prayers: Vec<Prayer>,
     pub dhikr_defs: Vec<DhikrDef>,
     pub dhikr_logs: HashMap<i64, DhikrLog>,
-    pub qada_count: i64,
+    pub qada_pace: QadaPace, // what's owed, and how fast it's being made up
+    pub qada_made_up_week: usize,
+    pub qada_made_up_today: usize,
     pub quran_today: f64,
     pub quran_weekly: f64,
+    pub khatm: Khatm,
+    pub khatm_plan: Option<KhatmPlan>, // with `quran.khatm_deadline`, measured on the live day
     pub streak: Streak,
+    pub on_time: OnTime, // last 30 days
     pub weekly_grid: Vec<DailyStats>,
+    pub weekly_goal: Option<Goal>, // `goals.weekly_prayers`, counted over the live week
+    pub monthly_goal: Option<Goal>,
+    pub stats_view: StatsView,
+    pub stats_grid: Vec<DailyStats>,  // the stats view's last 7 days, under its prayer filter
+    pub month_stats: Vec<DailyStats>, // loaded with stats_grid when the stats view opens
+    pub hijri_month: Option<HijriMonth>, // the month shown in the Hijri calendar view
     pub next_prayer_info: Option<(PrayerType, i64)>,
+    pub remaining_today: Option<Vec<PrayerType>>, // see `upcoming_pending`
+    pub times: Option<PrayerTimesLocal>, // the viewed day's computed times
+    pub other_asr: Option<(&'static str, NaiveTime)>, // Asr by the other madhab, with `display.both_asr`
+    pub alert: Alert,
+    pub alerted: Option<(NaiveDate, PrayerType)>, // last prayer entry the alert sounded for
+    pub tomorrow_rows_ready: bool,
+    pub night: Option<NightWindow>,
+    pub qiyam: Option<QiyamEntry>,
+    pub qiyam_streak: u32,
+    pub nafl_prayed: Vec<Nafl>, // the viewed day's nafl, with tahajjud taken from `qiyam`
+    pub day_closed: bool,
+    pub eid_today: Option<(Eid, usize)>, // Eid and its Hijri year, when the live day is Eid
+    pub eid_entry: Option<EidEntry>,
+    pub is_jummah: bool, // the live day is a Friday and Jummah tracking is on
+    pub jummah_entry: Option<JummahEntry>,
+    pub ramadan: bool, // the viewed day falls in Ramadan; shows Imsak above Fajr
+    pub sunnah_fast: Option<SunnahFast>, // recommended fast on the live day, with [fasting] on
+    pub sunnah_fast_tomorrow: Option<SunnahFast>,
+    pub fasted: bool,
+    pub jummah_streak: u32,
+    pub data_version: Option<i64>, // last `PRAGMA data_version` seen; a change means another process wrote
+    pub keymap: Keymap, // `[keybindings]`, checked before the fixed keys
 }
 
 impl App {
     pub fn new(config: AppConfig) -> Self {
-        let today = Local::now().date_naive();
-        let today_str = today.format("%Y-%m-%d").to_string();
-        let hijri_str = today_hijri_string(config.salah.hijri_offset);
+        let today = today_date(&config);
+        let today_str = date_str(today);
+        let (hijri_str, hijri_unadjusted) = header_hijri(today, config.salah.hijri_offset);
+        let alert = Alert::from_config(&config.notifications.sound);
+        let keymap = Keymap::new(&config.keybindings);
 
         App {
             view: View::Dashboard,
@@ -77,99 +241,398 @@ impl App {
             focus_section: FocusSection::Prayers,
             focus_idx: 0,
             should_quit: false,
+            confirm_quit: false,
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
             input_error: None,
+            quran_history: Vec::new(),
+            history_idx: None,
             show_qada_overlay: false,
+            dhikr_detail: None,
+            prayer_detail: None,
+            catchup_gap: None,
+            show_day_summary: false,
+            day_summary_dismissed: false,
+            day_cutoff: None,
+            collapsed_groups: HashSet::new(),
+            dhikr_filter: String::new(),
+            live_date: today,
+            today,
             today_str,
             hijri_str,
+            hijri_unadjusted,
             prayers: Vec::new(),
             dhikr_defs: Vec::new(),
             dhikr_logs: HashMap::new(),
-            qada_count: 0,
+            qada_pace: QadaPace::default(),
+            qada_made_up_week: 0,
+            qada_made_up_today: 0,
             quran_today: 0.0,
             quran_weekly: 0.0,
+            khatm: Khatm::default(),
+            khatm_plan: None,
             streak: Streak::default(),
+            on_time: OnTime::default(),
             weekly_grid: Vec::new(),
+            weekly_goal: None,
+            monthly_goal: None,
+            stats_view: StatsView::default(),
+            stats_grid: Vec::new(),
+            month_stats: Vec::new(),
+            hijri_month: None,
             next_prayer_info: None,
+            remaining_today: None,
+            times: None,
+            other_asr: None,
+            alert,
+            alerted: None,
+            tomorrow_rows_ready: false,
+            night: None,
+            qiyam: None,
+            qiyam_streak: 0,
+            nafl_prayed: Vec::new(),
+            day_closed: false,
+            eid_today: None,
+            eid_entry: None,
+            is_jummah: false,
+            jummah_entry: None,
+            ramadan: false,
+            sunnah_fast: None,
+            sunnah_fast_tomorrow: None,
+            fasted: false,
+            jummah_streak: 0,
+            data_version: None,
+            keymap,
         }
     }
 
     pub fn load(&mut self, conn: &Connection) -> Result<()> {
-        // Ensure today's prayer rows exist
-        PrayerRepo::ensure_today_rows(conn, &self.today_str)?;
+        // Ensure today's prayer rows exist. Past days are only given rows once
+        // something is marked, so browsing doesn't turn untracked days pending.
+        let viewing_live = self.is_viewing_live();
+        if viewing_live {
+            calculator::ensure_rows(conn, &self.config, self.today)?;
+        }
 
         // Load prayers + times from cache
         let calc = self.make_calculator()?;
-        let today = Local::now().date_naive();
-        let cached_times = calc.get_cached_or_compute(conn, today).ok();
+        let cached_times = calc.get_cached_or_compute(conn, self.today).ok();
 
         let mut db_prayers = PrayerRepo::get_by_date(conn, &self.today_str)?;
+        if db_prayers.is_empty() {
+            db_prayers = PrayerType::all()
+                .into_iter()
+                .map(|prayer_type| Prayer {
+                    id: None,
+                    prayer_type,
+                    date: self.today_str.clone(),
+                    status: PrayerStatus::Pending,
+                    is_qada: false,
+                    note: None,
+                    time: None,
+                    scheduled_time: None,
+                    marked_at: None,
+                })
+                .collect();
+        }
         if let Some(times) = &cached_times {
             for p in &mut db_prayers {
-                p.time = Some(match p.prayer_type {
-                    PrayerType::Fajr => times.fajr,
-                    PrayerType::Zuhr => times.zuhr,
-                    PrayerType::Asr => times.asr,
-                    PrayerType::Maghrib => times.maghrib,
-                    PrayerType::Isha => times.isha,
-                });
+                p.time = Some(times.get(&p.prayer_type));
             }
         }
         self.prayers = db_prayers;
+        self.times = cached_times;
+        self.ramadan = is_ramadan(self.today, self.config.salah.hijri_offset);
+        self.other_asr = if self.config.display.both_asr {
+            calc.other_madhab_asr(self.today).ok()
+        } else {
+            None
+        };
 
         // Dhikr
-        self.dhikr_defs = DhikrRepo::get_active_definitions(conn)?;
+        // Keep each category contiguous so list order matches the grouped widget
+        let defs = DhikrRepo::get_active_definitions(conn)?;
+        self.dhikr_defs = group_dhikr(&defs)
+            .into_iter()
+            .flat_map(|(_, members)| members.into_iter().cloned())
+            .collect();
         let logs = DhikrRepo::get_log_for_date(conn, &self.today_str)?;
         self.dhikr_logs = logs.into_iter().map(|l| (l.dhikr_id, l)).collect();
 
         // Qada
-        self.qada_count = QadaRepo::count_pending(conn)?;
+        self.qada_pace = QadaRepo::pace(conn, self.live_date)?;
+        let live_str = date_str(self.live_date);
+        let live_week_start = date_str(self.live_date - chrono::Duration::days(6));
+        self.qada_made_up_week =
+            QadaRepo::completed_in_range(conn, &live_week_start, &live_str)?.len();
+        self.qada_made_up_today = QadaRepo::completed_in_range(conn, &live_str, &live_str)?.len();
 
         // Quran
         self.quran_today = QuranRepo::get_today(conn, &self.today_str)?;
-        let week_start = (Local::now().date_naive() - chrono::Duration::days(6))
-            .format("%Y-%m-%d")
-            .to_string();
+        let week_start = date_str(self.today - chrono::Duration::days(6));
         self.quran_weekly = QuranRepo::get_weekly_total(conn, &week_start, &self.today_str)?;
+        self.khatm = QuranRepo::khatm(conn, self.live_date, self.config.quran.mushaf_pages)?;
+        self.khatm_plan = match self.config.quran.khatm_deadline {
+            Some(deadline) => {
+                let live_pages = QuranRepo::get_today(conn, &live_str)?;
+                Some(self.khatm.plan(deadline, self.live_date, live_pages))
+            }
+            None => None,
+        };
 
-        // Streak
-        self.streak = StatsRepo::calculate_streak(conn)?;
-
-        // Weekly grid
-        let week_end = &self.today_str;
-        self.weekly_grid = StatsRepo::get_weekly_grid(conn, &week_start, week_end)?;
+        // Streak and weekly grid always reflect the live day
+        self.streak = StatsRepo::calculate_streak(conn, &self.config.streak, self.live_date)?;
+        let grid_start = date_str(self.live_date - chrono::Duration::days(6));
+        let grid_end = date_str(self.live_date);
+        self.weekly_grid = StatsRepo::get_weekly_grid(conn, &grid_start, &grid_end)?;
+        let goals = &self.config.goals;
+        self.weekly_goal = goals
+            .weekly_prayers
+            .map(|target| StatsRepo::goal(conn, StatsRange::Week, target, self.live_date))
+            .transpose()?;
+        self.monthly_goal = goals
+            .monthly_prayers
+            .map(|target| StatsRepo::goal(conn, StatsRange::Month, target, self.live_date))
+            .transpose()?;
+        let month_ago = date_str(self.live_date - chrono::Duration::days(29));
+        self.on_time = StatsRepo::on_time(conn, &month_ago, &grid_end)?;
 
         // Next prayer
-        let now_time = Local::now().time();
+        let now = now_local(&self.config);
         self.next_prayer_info = calc
-            .get_next_prayer(conn, today, now_time)
+            .get_next_prayer(conn, now.date(), now.time())
             .ok()
             .flatten();
+        self.remaining_today = self.upcoming_pending(now);
+
+        // Qiyam
+        self.night = calc.night_at(conn, now).ok();
+        self.load_qiyam(conn)?;
+        self.load_nafl(conn)?;
+
+        self.day_closed =
+            MetaRepo::get(conn, DAY_CLOSED_KEY)?.as_deref() == Some(self.today_str.as_str());
+
+        self.load_eid(conn)?;
+
+        // Jummah
+        self.is_jummah = self.config.jummah.enabled && last_friday(self.live_date) == self.live_date;
+        self.jummah_entry = JummahRepo::get(conn, &date_str(self.live_date))?;
+        self.jummah_streak = JummahRepo::streak(conn, self.live_date)?.current;
+
+        // Fasting
+        if self.config.fasting.enabled {
+            let offset = self.config.salah.hijri_offset;
+            self.sunnah_fast = sunnah_fast_on(self.live_date, offset);
+            self.sunnah_fast_tomorrow = self
+                .live_date
+                .succ_opt()
+                .and_then(|d| sunnah_fast_on(d, offset));
+            self.fasted = FastingRepo::fasted_on(conn, &date_str(self.live_date))?;
+        }
 
         Ok(())
     }
 
+    /// Offer the Eid tracker when the live day is Eid, with what's recorded for it.
+    fn load_eid(&mut self, conn: &Connection) -> Result<()> {
+        self.eid_today = eid_on(self.live_date, self.config.salah.hijri_offset)
+            .filter(|_| self.config.eid.enabled);
+        self.eid_entry = match self.eid_today {
+            Some((eid, year)) => EidRepo::get(conn, year as i64, eid)?,
+            None => None,
+        };
+        Ok(())
+    }
+
+    fn load_qiyam(&mut self, conn: &Connection) -> Result<()> {
+        match &self.night {
+            Some(night) => {
+                self.qiyam = QiyamRepo::get(conn, &date_str(night.date))?;
+                self.qiyam_streak = QiyamRepo::current_streak(conn, night.date)?;
+            }
+            None => {
+                self.qiyam = None;
+                self.qiyam_streak = 0;
+            }
+        }
+        Ok(())
+    }
+
+    fn load_nafl(&mut self, conn: &Connection) -> Result<()> {
+        self.nafl_prayed = NaflRepo::prayed_on(conn, &self.today_str)?;
+        if self.qiyam.is_some() {
+            self.nafl_prayed.push(Nafl::Tahajjud);
+        }
+        Ok(())
+    }
+
     pub fn tick(&mut self, conn: &Connection) {
-        // Refresh countdown
-        let today = Local::now().date_naive();
-        let now_time = Local::now().time();
+        let now = now_local(&self.config);
+        let now_time = now.time();
+
+        // Date rollover — move the dashboard onto the new day, unless an
+        // earlier day is being reviewed
+        let today = today_date(&self.config);
+        if today != self.live_date {
+            if self.is_viewing_live() {
+                self.set_viewed_date(today);
+            }
+            self.live_date = today;
+            self.tomorrow_rows_ready = false;
+            self.show_day_summary = false;
+            self.day_summary_dismissed = false;
+            let _ = self.load(conn);
+        }
+
+        // Pick up edits made with `sujood` commands in another terminal
+        if let Ok(version) = MetaRepo::data_version(conn) {
+            if self.data_version.is_some_and(|seen| seen != version) {
+                let _ = self.load(conn);
+            }
+            self.data_version = Some(version);
+        }
+
+        // Refresh countdown, and move qiyam onto tonight once Maghrib passes
         if let Ok(calc) = self.make_calculator() {
+            let previous = self.next_prayer_info.clone();
             self.next_prayer_info = calc
-                .get_next_prayer(conn, today, now_time)
+                .get_next_prayer(conn, now.date(), now_time)
                 .ok()
                 .flatten();
+            self.alert_on_prayer_entry(previous, now.date());
+            self.remaining_today = self.upcoming_pending(now);
+            let night = calc.night_at(conn, now).ok();
+            if night != self.night {
+                self.night = night;
+                let _ = self.load_qiyam(conn);
+                let _ = self.load_nafl(conn);
+            }
+        }
+
+        self.offer_day_summary(now);
+        self.precreate_tomorrow(conn, today, now);
+    }
+
+    /// Offer the end-of-day summary once the configured cutoff is crossed.
+    fn offer_day_summary(&mut self, now: chrono::NaiveDateTime) {
+        if self.is_viewing_live()
+            && !self.show_day_summary
+            && !self.day_summary_dismissed
+            && !self.day_closed
+            && self.past_day_cutoff(now)
+        {
+            self.show_day_summary = true;
+        }
+    }
+
+    /// Once every prayer time has passed, pre-create tomorrow's rows so the
+    /// first interaction after midnight finds them already there.
+    fn precreate_tomorrow(&mut self, conn: &Connection, today: NaiveDate, now: chrono::NaiveDateTime) {
+        if self.config.day.precreate_tomorrow_rows
+            && !self.tomorrow_rows_ready
+            && now.date() == today
+            && self.day_has_ended(now)
+            && let Some(tomorrow) = today.succ_opt()
+        {
+            self.tomorrow_rows_ready =
+                calculator::ensure_rows(conn, &self.config, tomorrow).is_ok();
+        }
+    }
+
+    /// Sound the configured alert when the countdown runs out and the next
+    /// prayer moves on. Only a countdown that was about to end counts, so
+    /// opening the app or changing settings stays quiet; `alerted` keeps it
+    /// to once per prayer.
+    fn alert_on_prayer_entry(&mut self, previous: Option<(PrayerType, i64)>, date: NaiveDate) {
+        let Some((entered, secs_left)) = previous else {
+            return;
+        };
+        let moved_on = self.next_prayer_info.as_ref().is_none_or(|(next, _)| *next != entered);
+        if !moved_on || secs_left > ALERT_WINDOW_SECS {
+            return;
+        }
+        let key = (date, entered);
+        if self.alerted.as_ref() != Some(&key) {
+            self.alert.play();
+            self.alerted = Some(key);
+        }
+    }
+
+    /// True when today's prayer times are known and all of them are behind
+    /// us, counting an Isha after midnight as still ahead until it comes.
+    fn day_has_ended(&self, now: chrono::NaiveDateTime) -> bool {
+        let times: Option<Vec<NaiveTime>> = self.prayers.iter().map(|p| p.time).collect();
+        match times {
+            Some(times) if !times.is_empty() => {
+                place_in_order(self.today, &times).iter().all(|at| *at <= now)
+            }
+            _ => false,
+        }
+    }
+
+    /// The live day's pending prayers whose time is still ahead, in order.
+    /// Empty once every prayer is marked; `None` when another day is being
+    /// viewed, or when what's left has already come in and has no time ahead.
+    fn upcoming_pending(&self, now: chrono::NaiveDateTime) -> Option<Vec<PrayerType>> {
+        if !self.is_viewing_live() {
+            return None;
+        }
+        if remaining_prayers(&self.prayers).is_empty() {
+            return Some(Vec::new());
+        }
+        let times: Vec<NaiveTime> = self.prayers.iter().map(|p| p.time).collect::<Option<_>>()?;
+        let upcoming: Vec<PrayerType> = self
+            .prayers
+            .iter()
+            .zip(place_in_order(self.today, &times))
+            .filter(|(p, at)| p.status == PrayerStatus::Pending && *at > now)
+            .map(|(p, _)| p.prayer_type.clone())
+            .collect();
+        (!upcoming.is_empty()).then_some(upcoming)
+    }
+
+    /// Isha's moment on the viewed day, on the next date if it falls after midnight.
+    fn isha_moment(&self) -> Option<chrono::NaiveDateTime> {
+        let times: Option<Vec<NaiveTime>> = self.prayers.iter().map(|p| p.time).collect();
+        let idx = self.prayers.iter().position(|p| p.prayer_type == PrayerType::Isha)?;
+        place_in_order(self.today, &times?).get(idx).copied()
+    }
+
+    /// Whether the dashboard shows the live day rather than an earlier one.
+    pub fn is_viewing_live(&self) -> bool {
+        self.today == self.live_date
+    }
+
+    fn set_viewed_date(&mut self, date: NaiveDate) {
+        self.today = date;
+        self.today_str = date_str(date);
+        (self.hijri_str, self.hijri_unadjusted) =
+            header_hijri(date, self.config.salah.hijri_offset);
+    }
+
+    /// Step the viewed day by `days`, never past the live day.
+    fn shift_viewed_date(&mut self, days: i64, conn: &Connection) {
+        let target = (self.today + chrono::Duration::days(days)).min(self.live_date);
+        if target != self.today {
+            self.set_viewed_date(target);
+            let _ = self.load(conn);
+        }
+    }
+
+    /// True once `now` is at or past the configured close of today.
+    fn past_day_cutoff(&self, now: chrono::NaiveDateTime) -> bool {
+        let Some(cutoff) = self.day_cutoff else {
+            return false;
+        };
+        match self.isha_moment() {
+            Some(isha) => now >= cutoff.moment(self.today, isha, self.config.day.rollover_hour),
+            None => false,
         }
     }
 
     fn make_calculator(&self) -> Result<PrayerCalculator> {
-        PrayerCalculator::new(
-            self.config.salah.latitude,
-            self.config.salah.longitude,
-            &self.config.salah.calc_method,
-            &self.config.salah.madhab,
-            self.config.salah.timezone_offset,
-        )
+        PrayerCalculator::from_config(&self.config.salah)
     }
 
     pub fn handle_key(&mut self, key: crossterm::event::KeyEvent, conn: &Connection) {
@@ -179,102 +642,393 @@ impl App {
         }
         match self.input_mode {
             InputMode::QuranInput => self.handle_quran_input(key, conn),
+            InputMode::NoteInput => self.handle_note_input(key, conn),
+            InputMode::DhikrFilter => self.handle_dhikr_filter_input(key),
             InputMode::Normal => self.handle_normal_key(key, conn),
         }
     }
 
+    /// A left click on the plain dashboard: a prayer row toggles it done, a
+    /// dhikr row toggles or counts it, a status bar hint runs its action.
+    /// Clicks elsewhere, or while a popup or prompt is open, are ignored;
+    /// one during the quit confirmation cancels it.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, conn: &Connection) {
+        if self.confirm_quit {
+            self.confirm_quit = false;
+            return;
+        }
+        let popup_open = self.catchup_gap.is_some()
+            || self.show_day_summary
+            || self.show_qada_overlay
+            || self.dhikr_detail.is_some()
+            || self.prayer_detail.is_some();
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left)
+            || self.view != View::Dashboard
+            || self.input_mode != InputMode::Normal
+            || popup_open
+        {
+            return;
+        }
+        let Ok((width, height)) = crossterm::terminal::size() else {
+            return;
+        };
+        let layout = DashboardLayout::new(Rect::new(0, 0, width, height));
+        let position = Position::new(mouse.column, mouse.row);
+
+        if layout.status.contains(position) {
+            if let Some(action) = statusbar::action_at(&self.keymap, layout.status, mouse.column) {
+                self.run_action(action, conn);
+            }
+        } else if let Some(line) = list_line(layout.prayers, position) {
+            let imsak = self.imsak_row();
+            let sunrise = self.sunrise_row();
+            if let Some(idx) = prayers::prayer_at(&self.prayers, imsak, sunrise, line) {
+                self.focus_section = FocusSection::Prayers;
+                self.focus_idx = idx;
+                self.toggle_focused_done(conn);
+            }
+        } else if let Some(line) = list_line(layout.adhkar, position) {
+            let hit = adhkar::dhikr_at(&self.dhikr_defs, &self.collapsed_groups, self.dhikr_query(), line);
+            if let Some(idx) = hit {
+                self.focus_section = FocusSection::Dhikr;
+                self.focus_idx = idx;
+                self.toggle_focused_dhikr(conn);
+            }
+        }
+    }
+
     fn handle_normal_key(&mut self, key: crossterm::event::KeyEvent, conn: &Connection) {
         match self.view {
             View::Dashboard => self.handle_dashboard_key(key, conn),
-            View::Stats => self.handle_stats_key(key),
+            View::Stats => self.handle_stats_key(key, conn),
+            View::MonthStats => self.handle_month_stats_key(key, conn),
+            View::Hijri => self.handle_hijri_key(key),
             View::Help => self.handle_help_key(key),
         }
     }
 
     fn handle_dashboard_key(&mut self, key: crossterm::event::KeyEvent, conn: &Connection) {
+        // Welcome back: 1/2/3 record the gap, Esc leaves it for later
+        if let Some(gap) = self.catchup_gap {
+            let action = match key.code {
+                KeyCode::Char('1') => Some(CatchupAction::Done),
+                KeyCode::Char('2') => Some(CatchupAction::Missed),
+                KeyCode::Char('3') => Some(CatchupAction::Skip),
+                KeyCode::Esc => {
+                    self.catchup_gap = None;
+                    None
+                }
+                _ => None,
+            };
+            if let Some(action) = action {
+                let _ = catchup::apply(conn, &self.config, gap, action);
+                self.catchup_gap = None;
+                let _ = self.load(conn);
+            }
+            return;
+        }
+
+        // End-of-day summary: Enter closes the day, Esc postpones until next launch
+        if self.show_day_summary {
+            match key.code {
+                KeyCode::Enter => {
+                    let _ = MetaRepo::set(conn, DAY_CLOSED_KEY, &self.today_str);
+                    self.day_closed = true;
+                    self.show_day_summary = false;
+                }
+                KeyCode::Esc => {
+                    self.day_summary_dismissed = true;
+                    self.show_day_summary = false;
+                }
+                _ => {}
+            }
+            return;
+        }
+
         // If qada overlay is open, any key closes it (q toggles, others dismiss)
         if self.show_qada_overlay {
             self.show_qada_overlay = false;
             return;
         }
+        if self.dhikr_detail.is_some() || self.prayer_detail.is_some() {
+            self.dhikr_detail = None;
+            self.prayer_detail = None;
+            return;
+        }
+        // Quit asked once: Esc (or the quit key) again quits, anything else cancels
+        if self.confirm_quit {
+            self.confirm_quit = false;
+            if key.code == KeyCode::Esc || self.keymap.action(key.code) == Some(Action::Quit) {
+                self.should_quit = true;
+            }
+            return;
+        }
+
+        if let Some(action) = self.keymap.action(key.code) {
+            self.run_action(action, conn);
+            return;
+        }
 
         match key.code {
+            // Esc clears a kept dhikr filter first, then quits
+            KeyCode::Esc if !self.dhikr_filter.is_empty() => {
+                self.dhikr_filter.clear();
+                self.focus_idx = 0;
+            }
             // Esc = quit, q = qada overlay (they are different)
-            KeyCode::Esc => {
-                self.should_quit = true;
+            KeyCode::Esc => self.run_action(Action::Quit, conn),
+            KeyCode::Char('h') => {
+                self.show_hijri_month(self.live_date);
+                self.view = View::Hijri;
+            }
+            KeyCode::Up => self.run_action(Action::Up, conn),
+            KeyCode::Down => self.run_action(Action::Down, conn),
+            KeyCode::Tab => {
+                self.focus_section = match self.focus_section {
+                    FocusSection::Prayers => FocusSection::Dhikr,
+                    FocusSection::Dhikr if !self.config.nafl.is_empty() => FocusSection::Nafl,
+                    FocusSection::Dhikr | FocusSection::Nafl => FocusSection::Prayers,
+                    FocusSection::None => FocusSection::Prayers,
+                };
+                self.focus_idx = 0;
+            }
+            KeyCode::Enter => self.run_action(Action::Mark, conn),
+            // c collapses the focused dhikr's category, C expands all
+            KeyCode::Char('c') if self.focus_section == FocusSection::Dhikr => {
+                if let Some(def) = self.visible_dhikr().get(self.focus_idx) {
+                    self.collapsed_groups.insert(def.group().to_string());
+                }
+                self.focus_idx = self
+                    .focus_idx
+                    .min(self.visible_dhikr().len().saturating_sub(1));
+            }
+            KeyCode::Char('C') => {
+                self.collapsed_groups.clear();
+            }
+            KeyCode::Char('/') => {
+                self.focus_section = FocusSection::Dhikr;
+                self.focus_idx = 0;
+                self.input_buffer = self.dhikr_filter.clone();
+                self.input_mode = InputMode::DhikrFilter;
+            }
+            KeyCode::Char('t') => {
+                self.toggle_qiyam(conn);
             }
-            KeyCode::Char('q') => {
-                self.show_qada_overlay = true;
+            KeyCode::Char('e') => self.toggle_eid(conn),
+            KeyCode::Char('j') => self.toggle_jummah(conn),
+            KeyCode::Char('f') => self.toggle_fast(conn),
+            KeyCode::Char('[') => self.shift_viewed_date(-1, conn),
+            KeyCode::Char(']') => self.shift_viewed_date(1, conn),
+            KeyCode::Char('g') => {
+                let live = self.live_date;
+                self.shift_viewed_date((live - self.today).num_days(), conn);
             }
-            KeyCode::Char('?') => {
-                self.view = View::Help;
+            KeyCode::Char('n') if self.focus_section == FocusSection::Prayers => {
+                if let Some(prayer) = self.prayers.get(self.focus_idx) {
+                    self.input_buffer = prayer.note.clone().unwrap_or_default();
+                    self.input_error = None;
+                    self.input_mode = InputMode::NoteInput;
+                }
+            }
+            KeyCode::Char('i') => {
+                if self.focus_section == FocusSection::Prayers {
+                    self.prayer_detail = self.prayers.get(self.focus_idx).cloned();
+                }
+                if self.focus_section == FocusSection::Dhikr
+                    && let Some(def) = self.visible_dhikr().get(self.focus_idx).copied()
+                {
+                    let def = def.clone();
+                    let streak = StatsRepo::dhikr_streak(conn, def.id, self.live_date)
+                        .unwrap_or_default();
+                    let span_days = match def.frequency {
+                        DhikrFrequency::Daily => DETAIL_STRIP_LEN as i64,
+                        DhikrFrequency::Weekly => DETAIL_STRIP_LEN as i64 * 7 + 6,
+                    };
+                    let start = date_str(self.live_date - chrono::Duration::days(span_days));
+                    let logs = DhikrRepo::log_range(conn, def.id, &start, &date_str(self.live_date))
+                        .unwrap_or_default();
+                    let recent = def.recent_completion(&logs, self.live_date, DETAIL_STRIP_LEN);
+                    self.dhikr_detail = Some(DhikrDetail { def, streak, recent });
+                }
+            }
+            // - takes one off a focused counter dhikr, 0 / x reset it for today
+            KeyCode::Char('-') => self.adjust_focused_counter(conn, |count| count - 1),
+            KeyCode::Char('0') | KeyCode::Char('x') => {
+                self.adjust_focused_counter(conn, |_| 0)
+            }
+            _ => {}
+        }
+    }
+
+    /// Run a dashboard action reached through its `[keybindings]` key, or the
+    /// fixed key that shares it (Enter marks, Esc quits, arrows move).
+    fn run_action(&mut self, action: Action, conn: &Connection) {
+        match action {
+            // Marks the focused prayer done, or toggles the focused nafl
+            Action::Mark => match self.focus_section {
+                FocusSection::Prayers => self.mark_focused_done(conn),
+                FocusSection::Nafl => self.toggle_focused_nafl(conn),
+                _ => {}
+            },
+            Action::Missed => {
+                if self.focus_section == FocusSection::Prayers {
+                    self.mark_focused_missed(conn);
+                }
             }
-            KeyCode::Char('s') => {
-                self.view = View::Stats;
+            // Always works on dhikr — auto-switches to Dhikr section if needed
+            Action::Dhikr => {
+                if self.focus_section != FocusSection::Dhikr {
+                    self.focus_section = FocusSection::Dhikr;
+                    self.focus_idx = 0;
+                }
+                self.toggle_focused_dhikr(conn);
             }
-            KeyCode::Char('r') => {
+            Action::Quran => {
                 self.input_mode = InputMode::QuranInput;
                 self.input_buffer.clear();
                 self.input_error = None;
+                self.quran_history = QuranRepo::get_input_history(conn).unwrap_or_default();
+                self.history_idx = None;
             }
-            KeyCode::Up => {
+            Action::Stats => self.open_stats(conn),
+            Action::Help => self.view = View::Help,
+            Action::Qada => self.show_qada_overlay = true,
+            Action::Quit if self.config.ui.confirm_quit => self.confirm_quit = true,
+            Action::Quit => self.should_quit = true,
+            Action::Up => {
                 if self.focus_idx > 0 {
                     self.focus_idx -= 1;
                 }
             }
-            KeyCode::Down => {
+            Action::Down => {
                 let max = match self.focus_section {
                     FocusSection::Prayers => self.prayers.len().saturating_sub(1),
-                    FocusSection::Dhikr => self.dhikr_defs.len().saturating_sub(1),
+                    FocusSection::Dhikr => self.visible_dhikr().len().saturating_sub(1),
+                    FocusSection::Nafl => self.config.nafl.len().saturating_sub(1),
                     FocusSection::None => 0,
                 };
                 if self.focus_idx < max {
                     self.focus_idx += 1;
                 }
             }
-            KeyCode::Tab => {
-                self.focus_section = match self.focus_section {
-                    FocusSection::Prayers => FocusSection::Dhikr,
-                    FocusSection::Dhikr => FocusSection::Prayers,
-                    FocusSection::None => FocusSection::Prayers,
-                };
-                self.focus_idx = 0;
+        }
+    }
+
+    /// Open stats where they were left: the remembered range and prayer filter.
+    fn open_stats(&mut self, conn: &Connection) {
+        self.stats_view = StatsRepo::load_view(conn).unwrap_or_default();
+        self.load_stats(conn);
+        self.view = match self.stats_view.range {
+            StatsRange::Week => View::Stats,
+            StatsRange::Month => View::MonthStats,
+        };
+    }
+
+    fn load_stats(&mut self, conn: &Connection) {
+        let end = date_str(self.live_date);
+        let prayer = self.stats_view.prayer.as_ref();
+        let week_start = date_str(self.live_date - chrono::Duration::days(6));
+        self.stats_grid = StatsRepo::get_daily_stats_range(conn, &week_start, &end, prayer)
+            .unwrap_or_default();
+        let month_start = date_str(self.month_start());
+        self.month_stats = StatsRepo::get_daily_stats_range(conn, &month_start, &end, prayer)
+            .unwrap_or_default();
+    }
+
+    /// Switch the stats range or filter and remember it for next time.
+    fn update_stats_view(&mut self, view: StatsView, conn: &Connection) {
+        let _ = StatsRepo::save_view(conn, &view);
+        let refilter = view.prayer != self.stats_view.prayer;
+        self.stats_view = view;
+        if refilter {
+            self.load_stats(conn);
+        }
+        self.view = match self.stats_view.range {
+            StatsRange::Week => View::Stats,
+            StatsRange::Month => View::MonthStats,
+        };
+    }
+
+    fn handle_stats_key(&mut self, key: crossterm::event::KeyEvent, conn: &Connection) {
+        match key.code {
+            KeyCode::Esc => {
+                self.view = View::Dashboard;
             }
-            // m / Enter marks focused prayer done (only when in Prayers section)
-            KeyCode::Char('m') | KeyCode::Enter => {
-                if self.focus_section == FocusSection::Prayers {
-                    self.mark_focused_done(conn);
-                }
+            code if self.keymap.action(code) == Some(Action::Stats) => {
+                self.view = View::Dashboard;
             }
-            KeyCode::Char('M') => {
-                if self.focus_section == FocusSection::Prayers {
-                    self.mark_focused_missed(conn);
-                }
+            KeyCode::Char('m') => {
+                let view = StatsView { range: StatsRange::Month, prayer: self.stats_view.prayer.clone() };
+                self.update_stats_view(view, conn);
             }
-            // d always works on dhikr — auto-switches to Dhikr section if needed
-            KeyCode::Char('d') => {
-                if self.focus_section != FocusSection::Dhikr {
-                    self.focus_section = FocusSection::Dhikr;
-                    self.focus_idx = 0;
-                }
-                self.toggle_focused_dhikr(conn);
+            KeyCode::Char('p') => {
+                let view = StatsView {
+                    range: self.stats_view.range,
+                    prayer: self.stats_view.next_prayer(),
+                };
+                self.update_stats_view(view, conn);
             }
             _ => {}
         }
     }
 
-    fn handle_stats_key(&mut self, key: crossterm::event::KeyEvent) {
+    fn handle_month_stats_key(&mut self, key: crossterm::event::KeyEvent, conn: &Connection) {
         match key.code {
-            KeyCode::Esc | KeyCode::Char('s') => {
+            KeyCode::Esc | KeyCode::Char('m') => {
+                let view = StatsView { range: StatsRange::Week, prayer: self.stats_view.prayer.clone() };
+                self.update_stats_view(view, conn);
+            }
+            code if self.keymap.action(code) == Some(Action::Stats) => {
                 self.view = View::Dashboard;
             }
+            KeyCode::Char('p') => {
+                let view = StatsView {
+                    range: self.stats_view.range,
+                    prayer: self.stats_view.next_prayer(),
+                };
+                self.update_stats_view(view, conn);
+            }
             _ => {}
         }
     }
 
+    /// First day of the live day's month.
+    fn month_start(&self) -> NaiveDate {
+        self.live_date.with_day(1).unwrap_or(self.live_date)
+    }
+
+    /// Load the Hijri month containing `date` into the calendar view.
+    fn show_hijri_month(&mut self, date: NaiveDate) {
+        self.hijri_month = hijri_month_days(date, self.config.salah.hijri_offset).ok();
+    }
+
+    fn handle_hijri_key(&mut self, key: crossterm::event::KeyEvent) {
+        let shown = self.hijri_month.as_ref();
+        let target = match key.code {
+            KeyCode::Esc | KeyCode::Char('h') => {
+                self.view = View::Dashboard;
+                None
+            }
+            KeyCode::Char('[') | KeyCode::Left => {
+                shown.and_then(|m| m.first_date()).and_then(|d| d.pred_opt())
+            }
+            KeyCode::Char(']') | KeyCode::Right => {
+                shown.and_then(|m| m.last_date()).and_then(|d| d.succ_opt())
+            }
+            KeyCode::Char('g') => Some(self.live_date),
+            _ => None,
+        };
+        if let Some(date) = target {
+            self.show_hijri_month(date);
+        }
+    }
+
     fn handle_help_key(&mut self, key: crossterm::event::KeyEvent) {
         match key.code {
-            KeyCode::Esc | KeyCode::Char('?') => {
+            KeyCode::Esc => {
+                self.view = View::Dashboard;
+            }
+            code if self.keymap.action(code) == Some(Action::Help) => {
                 self.view = View::Dashboard;
             }
             _ => {}
@@ -294,9 +1048,38 @@ impl App {
                     self.input_error = Some("Enter a number first (e.g. 2 or 0.5)".to_string());
                     return;
                 }
-                match trimmed.parse::<f64>() {
-                    Ok(pages) if pages > 0.0 => {
+                // A leading `=` sets the day's total instead of adding to it
+                let (set, number) = match trimmed.strip_prefix('=') {
+                    Some(rest) => (true, rest.trim()),
+                    None => (false, trimmed.as_str()),
+                };
+                match number.parse::<f64>() {
+                    Ok(pages) if set && pages >= 0.0 => {
+                        let _ = QuranRepo::set_pages(conn, &self.today_str, pages);
+                        let _ = QuranRepo::advance_khatm(
+                            conn,
+                            self.live_date,
+                            self.config.quran.mushaf_pages,
+                        );
+                        let _ = self.load(conn);
+                        self.input_mode = InputMode::Normal;
+                        self.input_buffer.clear();
+                        self.input_error = None;
+                    }
+                    Ok(pages) if !set && pages > 0.0 => {
                         let _ = QuranRepo::log_pages(conn, &self.today_str, pages);
+                        let _ = QuranRepo::advance_khatm(
+                            conn,
+                            self.live_date,
+                            self.config.quran.mushaf_pages,
+                        );
+                        if self.config.quran.input_history_size > 0 {
+                            let _ = QuranRepo::remember_input(
+                                conn,
+                                pages,
+                                self.config.quran.input_history_size,
+                            );
+                        }
                         let _ = self.load(conn);
                         self.input_mode = InputMode::Normal;
                         self.input_buffer.clear();
@@ -306,33 +1089,150 @@ impl App {
                         self.input_error = Some("Pages must be greater than 0".to_string());
                     }
                     Err(_) => {
-                        self.input_error = Some(format!("'{}' is not a valid number", trimmed));
+                        self.input_error = Some(format!("'{}' is not a valid number", number));
+                    }
+                }
+            }
+            // ↑ recalls older entries, ↓ newer ones and finally an empty prompt
+            KeyCode::Up if !self.quran_history.is_empty() => {
+                let idx = match self.history_idx {
+                    None => 0,
+                    Some(i) => (i + 1).min(self.quran_history.len() - 1),
+                };
+                self.history_idx = Some(idx);
+                self.input_buffer = self.quran_history[idx].to_string();
+                self.input_error = None;
+            }
+            KeyCode::Down => {
+                match self.history_idx {
+                    Some(0) | None => {
+                        self.history_idx = None;
+                        self.input_buffer.clear();
+                    }
+                    Some(i) => {
+                        self.history_idx = Some(i - 1);
+                        self.input_buffer = self.quran_history[i - 1].to_string();
                     }
                 }
+                self.input_error = None;
             }
             KeyCode::Backspace => {
                 self.input_buffer.pop();
                 self.input_error = None;
+                self.history_idx = None;
             }
-            KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+            KeyCode::Char(c)
+                if c.is_ascii_digit() || c == '.' || (c == '=' && self.input_buffer.is_empty()) =>
+            {
                 self.input_buffer.push(c);
                 self.input_error = None;
+                self.history_idx = None;
             }
             _ => {}
         }
     }
 
-    fn mark_focused_done(&mut self, conn: &Connection) {
-        if self.focus_section == FocusSection::Prayers {
-            if let Some(prayer) = self.prayers.get(self.focus_idx) {
-                let _ = PrayerRepo::mark_status(
-                    conn,
-                    prayer.prayer_type.as_str(),
-                    &self.today_str,
-                    "done",
-                );
+    /// Save the note typed for the focused prayer. An empty note clears it.
+    fn handle_note_input(&mut self, key: crossterm::event::KeyEvent, conn: &Connection) {
+        match key.code {
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+                self.input_error = None;
+            }
+            KeyCode::Enter => {
+                if let Some(prayer) = self.prayers.get(self.focus_idx) {
+                    let note = self.input_buffer.trim();
+                    let _ = calculator::ensure_rows(conn, &self.config, self.today);
+                    let _ = PrayerRepo::set_note(
+                        conn,
+                        prayer.prayer_type.as_str(),
+                        &self.today_str,
+                        Some(note).filter(|n| !n.is_empty()),
+                    );
+                    let _ = self.load(conn);
+                }
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+                self.input_error = None;
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+                self.input_error = None;
+            }
+            KeyCode::Char(c) => {
+                if self.input_buffer.chars().count() < NOTE_MAX_CHARS {
+                    self.input_buffer.push(c);
+                } else {
+                    self.input_error = Some(format!("Notes are at most {} characters", NOTE_MAX_CHARS));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Narrow the Adhkar list as the name is typed. Enter keeps the filter
+    /// so `d` and `i` work on the matches; Esc drops it.
+    fn handle_dhikr_filter_input(&mut self, key: crossterm::event::KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.dhikr_filter.clear();
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                self.dhikr_filter = self.input_buffer.trim().to_string();
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => return,
+        }
+        self.focus_idx = 0;
+    }
+
+    fn mark_focused_done(&mut self, conn: &Connection) {
+        if self.focus_section == FocusSection::Prayers {
+            if let Some(prayer) = self.prayers.get(self.focus_idx) {
+                let _ = calculator::ensure_rows(conn, &self.config, self.today);
+                let marked_at = self.is_viewing_live().then(|| now_local(&self.config));
+                let _ = PrayerRepo::mark_status(
+                    conn,
+                    prayer.prayer_type.as_str(),
+                    &self.today_str,
+                    "done",
+                    marked_at,
+                );
+                // Prayed after all, so it's no longer owed
+                if prayer.status == PrayerStatus::Missed {
+                    let _ = QadaRepo::remove_for(conn, prayer.prayer_type.as_str(), &self.today_str);
+                }
+                let _ = self.load(conn);
+            }
+        }
+    }
+
+    /// Mark the focused prayer done, or back to pending if it already is.
+    fn toggle_focused_done(&mut self, conn: &Connection) {
+        match self.prayers.get(self.focus_idx) {
+            Some(prayer) if prayer.status == PrayerStatus::Done => {
+                let prayer_type = prayer.prayer_type.as_str().to_string();
+                let _ = PrayerRepo::mark_status(
+                    conn,
+                    &prayer_type,
+                    &self.today_str,
+                    PrayerStatus::Pending.as_str(),
+                    None,
+                );
                 let _ = self.load(conn);
             }
+            Some(_) => self.mark_focused_done(conn),
+            None => {}
         }
     }
 
@@ -341,7 +1241,8 @@ impl App {
             if let Some(prayer) = self.prayers.get(self.focus_idx) {
                 let prayer_type = prayer.prayer_type.as_str().to_string();
                 let date = self.today_str.clone();
-                let _ = PrayerRepo::mark_status(conn, &prayer_type, &date, "missed");
+                let _ = calculator::ensure_rows(conn, &self.config, self.today);
+                let _ = PrayerRepo::mark_status(conn, &prayer_type, &date, "missed", None);
                 let _ = QadaRepo::add_entry(conn, &prayer_type, &date);
                 let _ = self.load(conn);
             }
@@ -350,40 +1251,209 @@ impl App {
 
     fn toggle_focused_dhikr(&mut self, conn: &Connection) {
         // focus_section is guaranteed to be Dhikr by the caller
-        if let Some(def) = self.dhikr_defs.get(self.focus_idx) {
+        if let Some(def) = self.visible_dhikr().get(self.focus_idx).copied() {
             let log = self.dhikr_logs.get(&def.id);
+            let date = def.log_date(&self.today_str);
             match def.dhikr_type {
                 DhikrType::Checkbox => {
                     let was_done = log.map(|l| l.completed).unwrap_or(false);
-                    let _ = DhikrRepo::upsert_log(conn, def.id, &self.today_str, 1, !was_done);
+                    let _ = DhikrRepo::upsert_log(conn, def.id, &date, 1, !was_done);
                 }
                 DhikrType::Counter => {
                     let count = log.map(|l| l.count).unwrap_or(0) + 1;
                     let completed = count >= def.target_count;
-                    let _ = DhikrRepo::upsert_log(conn, def.id, &self.today_str, count, completed);
+                    let _ = DhikrRepo::upsert_log(conn, def.id, &date, count, completed);
                 }
             }
             let _ = self.load(conn);
         }
     }
 
+    /// Rewrite the focused counter dhikr's count, never below zero.
+    fn adjust_focused_counter(&mut self, conn: &Connection, adjust: impl Fn(i32) -> i32) {
+        if self.focus_section != FocusSection::Dhikr {
+            return;
+        }
+        let Some(def) = self.visible_dhikr().get(self.focus_idx).copied() else {
+            return;
+        };
+        if def.dhikr_type != DhikrType::Counter {
+            return;
+        }
+        let count = self.dhikr_logs.get(&def.id).map(|l| l.count).unwrap_or(0);
+        let count = adjust(count).max(0);
+        let date = def.log_date(&self.today_str);
+        let _ = DhikrRepo::upsert_log(conn, def.id, &date, count, count >= def.target_count);
+        let _ = self.load(conn);
+    }
+
+    fn toggle_eid(&mut self, conn: &Connection) {
+        let Some((eid, year)) = self.eid_today else {
+            return;
+        };
+        let attended = !self.eid_entry.as_ref().is_some_and(|e| e.attended);
+        let entry = EidEntry {
+            hijri_year: year as i64,
+            eid,
+            date: date_str(self.live_date),
+            attended,
+        };
+        if EidRepo::record(conn, &entry).is_ok() {
+            self.eid_entry = Some(entry);
+        }
+    }
+
+    fn toggle_jummah(&mut self, conn: &Connection) {
+        if !self.is_jummah {
+            return;
+        }
+        let entry = JummahEntry {
+            date: date_str(self.live_date),
+            attended: !self.jummah_entry.as_ref().is_some_and(|e| e.attended),
+        };
+        if JummahRepo::record(conn, &entry).is_ok() {
+            self.jummah_entry = Some(entry);
+            if let Ok(streak) = JummahRepo::streak(conn, self.live_date) {
+                self.jummah_streak = streak.current;
+            }
+        }
+    }
+
+    fn toggle_qiyam(&mut self, conn: &Connection) {
+        let Some(night) = &self.night else {
+            return;
+        };
+        let night_str = date_str(night.date);
+        if self.qiyam.is_some() {
+            let _ = QiyamRepo::unmark(conn, &night_str);
+        } else {
+            let entry = night.qiyam_at(now_local(&self.config));
+            let _ = QiyamRepo::mark(conn, &entry);
+        }
+        let _ = self.load_qiyam(conn);
+        let _ = self.load_nafl(conn);
+    }
+
+    /// Tahajjud goes through the qiyam tracker; the rest are kept per day.
+    fn toggle_focused_nafl(&mut self, conn: &Connection) {
+        let Some(&nafl) = self.config.nafl.get(self.focus_idx) else {
+            return;
+        };
+        if nafl == Nafl::Tahajjud {
+            self.toggle_qiyam(conn);
+            return;
+        }
+        let _ = if self.nafl_prayed.contains(&nafl) {
+            NaflRepo::unmark(conn, nafl, &self.today_str)
+        } else {
+            let now = now_local(&self.config).format(DATETIME_FMT).to_string();
+            NaflRepo::mark(conn, nafl, &self.today_str, &now)
+        };
+        let _ = self.load_nafl(conn);
+    }
+
+    /// The name filter in effect: the one being typed, else the kept one.
+    fn dhikr_query(&self) -> &str {
+        match self.input_mode {
+            InputMode::DhikrFilter => self.input_buffer.trim(),
+            _ => &self.dhikr_filter,
+        }
+    }
+
+    /// Dhikr definitions in expanded categories — the rows `focus_idx` can land on.
+    /// While filtering, every match is shown whether or not its category is collapsed.
+    fn visible_dhikr(&self) -> Vec<&DhikrDef> {
+        let query = self.dhikr_query();
+        self.dhikr_defs
+            .iter()
+            .filter(|d| {
+                if query.is_empty() {
+                    !self.collapsed_groups.contains(d.group())
+                } else {
+                    d.name_matches(query)
+                }
+            })
+            .collect()
+    }
+
     pub fn draw(&self, frame: &mut Frame) {
         match self.view {
             View::Dashboard => self.draw_dashboard(frame),
             View::Stats => self.draw_stats(frame),
+            View::MonthStats => self.draw_month_stats(frame),
+            View::Hijri => self.draw_hijri(frame),
             View::Help => {
                 self.draw_dashboard(frame);
                 self.draw_help_overlay(frame);
             }
         }
 
-        if self.input_mode == InputMode::QuranInput {
-            self.draw_quran_input(frame);
+        match self.input_mode {
+            InputMode::QuranInput => self.draw_quran_input(frame),
+            InputMode::NoteInput => self.draw_note_input(frame),
+            InputMode::DhikrFilter | InputMode::Normal => {}
         }
 
         if self.show_qada_overlay {
             self.draw_qada_overlay(frame);
         }
+
+        if let Some(detail) = &self.dhikr_detail {
+            self.draw_dhikr_detail(frame, detail);
+        }
+
+        if let Some(prayer) = &self.prayer_detail {
+            self.draw_prayer_detail(frame, prayer);
+        }
+
+        if self.show_day_summary {
+            self.draw_day_summary(frame);
+        }
+
+        if let Some(gap) = self.catchup_gap {
+            self.draw_catchup(frame, gap);
+        }
+    }
+    fn toggle_fast(&mut self, conn: &Connection) {
+        if !self.config.fasting.enabled {
+            return;
+        }
+        let date = date_str(self.live_date);
+        let result = if self.fasted {
+            FastingRepo::unmark(conn, &date)
+        } else {
+            let recorded_at = now_local(&self.config).format(DATETIME_FMT).to_string();
+            FastingRepo::mark(conn, &date, &recorded_at)
+        };
+        if result.is_ok() {
+            self.fasted = !self.fasted;
+        }
+    }
+
+    /// Pages to read on the viewed day. The deadline plan only sets the live
+    /// day's; a reviewed past day is measured against `daily_target`.
+    fn quran_target(&self) -> f64 {
+        let plan = self.khatm_plan.as_ref().filter(|_| self.is_viewing_live());
+        KhatmPlan::target(plan, self.config.quran.daily_target)
+    }
+
+    /// Imsak above Fajr in the prayer list, during Ramadan.
+    fn imsak_row(&self) -> Option<NaiveTime> {
+        self.times.as_ref().filter(|_| self.ramadan).map(|t| t.imsak)
+    }
+
+    /// Sunrise below Fajr in the prayer list, with `display.show_sunrise`.
+    fn sunrise_row(&self) -> Option<NaiveTime> {
+        self.times
+            .as_ref()
+            .filter(|_| self.config.display.show_sunrise)
+            .map(|t| t.sunrise)
+    }
+
+    /// Whether the fasting card has anything to say on the live day.
+    fn show_fasting(&self) -> bool {
+        self.config.fasting.enabled
+            && (self.sunnah_fast.is_some() || self.sunnah_fast_tomorrow.is_some() || self.fasted)
     }
 
     fn draw_dashboard(&self, frame: &mut Frame) {
@@ -395,82 +1465,164 @@ impl App {
             area,
         );
 
-        let outer_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(5), // header
-                Constraint::Min(0),    // body
-                Constraint::Length(1), // status bar
-            ])
-            .split(area);
+        let layout = DashboardLayout::new(area);
 
         // Header
-        header::render(frame, outer_chunks[0], &self.hijri_str);
+        header::render(
+            frame,
+            layout.header,
+            &header::HeaderInfo {
+                today: self.today,
+                hijri_str: &self.hijri_str,
+                hijri_unadjusted: self.hijri_unadjusted,
+                remaining: remaining_prayers(&self.prayers).len(),
+                past: !self.is_viewing_live(),
+                eid: self
+                    .eid_today
+                    .map(|(eid, _)| (eid, self.eid_entry.as_ref().map(|e| e.attended))),
+                jummah: self.is_jummah.then(|| {
+                    (self.jummah_entry.as_ref().map(|e| e.attended), self.jummah_streak)
+                }),
+                leave_for_jummah: self.jummah_leave_reminder(),
+                profile: self.config.profile.as_deref(),
+            },
+        );
 
         // Status bar
-        statusbar::render(frame, outer_chunks[2]);
-
-        // Body split into columns
-        let body = outer_chunks[1];
-        let columns = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
-            .split(body);
-
-        let left = columns[0];
-        let right = columns[1];
-
-        // Left column: Prayers + Adhkar + Quran
-        let left_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(9),  // prayers
-                Constraint::Length(8),  // adhkar
-                Constraint::Length(3),  // quran
-            ])
-            .split(left);
+        if self.confirm_quit {
+            statusbar::render_confirm_quit(frame, layout.status);
+        } else {
+            statusbar::render(frame, layout.status, &self.keymap);
+        }
 
         let focused_prayers = self.focus_section == FocusSection::Prayers;
         let focused_dhikr = self.focus_section == FocusSection::Dhikr;
 
         prayers::render(
             frame,
-            left_chunks[0],
+            layout.prayers,
             &self.prayers,
+            &prayers::ExtraTimes {
+                imsak: self.imsak_row(),
+                sunrise: self.sunrise_row(),
+                jummah: self
+                    .times
+                    .as_ref()
+                    .and_then(|t| jummah_start(&self.config, self.today, t.zuhr)),
+                other_asr: self.other_asr,
+            },
+            self.config.ui.time_format,
             self.focus_idx,
             focused_prayers,
         );
 
         adhkar::render(
             frame,
-            left_chunks[1],
+            layout.adhkar,
             &self.dhikr_defs,
             &self.dhikr_logs,
+            &adhkar::ListView {
+                collapsed: &self.collapsed_groups,
+                filter: self.dhikr_query(),
+                typing_filter: self.input_mode == InputMode::DhikrFilter,
+            },
             self.focus_idx,
             focused_dhikr,
         );
 
         quran::render(
             frame,
-            left_chunks[2],
+            layout.quran,
             self.quran_today,
             self.quran_weekly,
-            self.config.quran.daily_target,
+            self.quran_target(),
+            &self.khatm,
+            self.khatm_plan.as_ref(),
         );
 
-        // Right column: Next Prayer + Streak + Qada
+        // Right column: Next Prayer + Streak + Qiyam + Fasting + Nafl + Qada
         let right_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(9),  // next prayer
                 Constraint::Length(7),  // streak
+                Constraint::Length(3),  // qiyam
+                Constraint::Length(if self.show_fasting() { 3 } else { 0 }),
+                Constraint::Length(if self.config.nafl.is_empty() { 0 } else { self.config.nafl.len() as u16 + 2 }),
                 Constraint::Min(0),     // qada
             ])
-            .split(right);
+            .split(layout.right);
+
+        next_prayer::render(
+            frame,
+            right_chunks[0],
+            self.next_prayer_info.as_ref(),
+            self.remaining_today.as_deref(),
+            self.forbidden_window().as_ref(),
+            self.config.ui.time_format,
+        );
+        streak::render(
+            frame,
+            right_chunks[1],
+            &self.streak,
+            &self.weekly_grid,
+            self.weekly_goal.as_ref().or(self.monthly_goal.as_ref()),
+        );
+        qiyam::render(
+            frame,
+            right_chunks[2],
+            self.qiyam.as_ref(),
+            self.night.as_ref(),
+            self.qiyam_streak,
+        );
+        if self.show_fasting() {
+            fasting::render(
+                frame,
+                right_chunks[3],
+                self.sunnah_fast,
+                self.sunnah_fast_tomorrow,
+                self.fasted,
+            );
+        }
+        if !self.config.nafl.is_empty() {
+            nafl::render(
+                frame,
+                right_chunks[4],
+                &self.config.nafl,
+                &self.nafl_prayed,
+                self.focus_idx,
+                self.focus_section == FocusSection::Nafl,
+            );
+        }
+        qada::render(frame, right_chunks[5], &self.qada_pace, self.live_date);
+    }
+
+    /// The makruh window the current time falls in, if any.
+    fn forbidden_window(&self) -> Option<ForbiddenWindow> {
+        if !self.config.display.forbidden_windows || !self.is_viewing_live() {
+            return None;
+        }
+        let now = now_local(&self.config);
+        if now.date() != self.today {
+            return None;
+        }
+        self.times.as_ref()?.forbidden_at(now.time())
+    }
 
-        next_prayer::render(frame, right_chunks[0], self.next_prayer_info.as_ref());
-        streak::render(frame, right_chunks[1], &self.streak, &self.weekly_grid);
-        qada::render(frame, right_chunks[2], self.qada_count);
+    /// Jummah's start time while the leave-early window is open.
+    fn jummah_leave_reminder(&self) -> Option<NaiveTime> {
+        if !self.is_jummah || self.jummah_entry.is_some() {
+            return None;
+        }
+        let zuhr = self
+            .prayers
+            .iter()
+            .find(|p| p.prayer_type == PrayerType::Zuhr)
+            .and_then(|p| p.time)?;
+        let start = jummah_start(&self.config, self.live_date, zuhr)?;
+        let leave_at = jummah_leave_at(&self.config, self.live_date, zuhr)?;
+        let now = now_local(&self.config).time();
+        (now >= leave_at && now < start).then_some(start)
     }
 
     fn draw_stats(&self, frame: &mut Frame) {
@@ -489,7 +1641,7 @@ impl App {
         // Simple title
         let title = Paragraph::new(Line::from(vec![
             Span::styled("  Stats  ", theme::gold().add_modifier(Modifier::BOLD)),
-            Span::styled("  [Esc] back", theme::dim()),
+            Span::styled("  [m] month  ·  [p] prayer  ·  [Esc] back", theme::dim()),
         ]));
         frame.render_widget(title, chunks[0]);
 
@@ -507,10 +1659,24 @@ impl App {
                 Span::styled("  Streak (best):     ", theme::dim()),
                 Span::styled(format!("{} days", self.streak.best), theme::green()),
             ]),
+            Line::from(vec![
+                Span::styled("  On time (30d):     ", theme::dim()),
+                match self.on_time.percent() {
+                    Some(percent) => Span::styled(
+                        format!("{}%  of {} marked on the day", percent, self.on_time.timed),
+                        theme::green(),
+                    ),
+                    None => Span::styled("—", theme::dim()),
+                },
+            ]),
             Line::from(""),
             Line::from(vec![
                 Span::styled("  Qada owed:         ", theme::dim()),
-                Span::styled(format!("{}", self.qada_count), theme::amber()),
+                Span::styled(format!("{}", self.qada_pace.owed), theme::amber()),
+            ]),
+            Line::from(vec![
+                Span::styled("  Qada made up (7d): ", theme::dim()),
+                Span::styled(format!("{}", self.qada_made_up_week), theme::green()),
             ]),
             Line::from(""),
             Line::from(vec![
@@ -525,15 +1691,24 @@ impl App {
                 Span::styled(format!("{} pages", self.quran_weekly), theme::amber()),
             ]),
             Line::from(""),
-            Line::from(Span::styled("  Last 7 Days", theme::gold())),
-            Line::from(""),
         ];
 
         let mut all_lines = lines;
+        if let Some(goal) = &self.weekly_goal {
+            let mut spans = vec![Span::styled("  Weekly goal:       ", theme::dim())];
+            spans.extend(streak::goal_spans(goal));
+            all_lines.push(Line::from(spans));
+            all_lines.push(Line::from(""));
+        }
+        all_lines.push(Line::from(vec![
+            Span::styled("  Last 7 Days", theme::gold()),
+            Span::styled(format!("  ·  {}", self.stats_view.prayer_label()), theme::dim()),
+        ]));
+        all_lines.push(Line::from(""));
 
         // Weekly heatmap
-        for stat in &self.weekly_grid {
-            let icon = match stat.prayers_done {
+        for stat in &self.stats_grid {
+            let icon = match stat.level() {
                 5 => Span::styled("  ████████████  ", theme::green()),
                 4 => Span::styled("  █████████░░░  ", theme::green()),
                 3 => Span::styled("  ████████░░░░  ", theme::amber()),
@@ -544,7 +1719,7 @@ impl App {
             all_lines.push(Line::from(vec![
                 icon,
                 Span::styled(
-                    format!("{}  {}/5", stat.date, stat.prayers_done),
+                    format!("{}  {}/{}", stat.date, stat.prayers_done, stat.prayers_total),
                     theme::dim(),
                 ),
             ]));
@@ -554,18 +1729,183 @@ impl App {
         frame.render_widget(paragraph, chunks[1]);
     }
 
-    fn draw_help_overlay(&self, frame: &mut Frame) {
+    fn draw_month_stats(&self, frame: &mut Frame) {
         let area = frame.area();
+        frame.render_widget(Block::default().style(theme::base()), area);
 
-        // Center a help box
-        let popup_area = Rect {
-            x: area.width / 4,
-            y: area.height / 4,
-            width: area.width / 2,
-            height: area.height / 2,
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(18),
+                Constraint::Min(0),
+            ])
+            .split(area);
+
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled("  This Month  ", theme::gold().add_modifier(Modifier::BOLD)),
+            Span::styled(format!("  {}", self.stats_view.prayer_label()), theme::dim()),
+            Span::styled("  ·  [p] prayer  ·  [m] / [Esc] back to stats", theme::dim()),
+        ]));
+        frame.render_widget(title, chunks[0]);
+
+        let first = self.month_start();
+        let calendar_area = Rect {
+            width: chunks[1].width.min(42),
+            ..chunks[1]
         };
+        month::render(frame, calendar_area, first, &self.month_stats, self.live_date);
+
+        // Totals over the days that have rows, so a partly tracked month still reads fairly
+        let tracked = self.month_stats.len();
+        let done: u32 = self.month_stats.iter().map(|s| s.prayers_done as u32).sum();
+        let expected: u32 = self.month_stats.iter().map(|s| s.prayers_total as u32).sum();
+        let full: HashSet<&str> = self
+            .month_stats
+            .iter()
+            .filter(|s| s.level() >= 5)
+            .map(|s| s.date.as_str())
+            .collect();
+        let mut best = 0u32;
+        let mut run = 0u32;
+        for day in first.iter_days().take_while(|d| *d <= self.live_date) {
+            run = if full.contains(date_str(day).as_str()) { run + 1 } else { 0 };
+            best = best.max(run);
+        }
+        let elapsed = (self.live_date - first).num_days() + 1;
 
-        frame.render_widget(Clear, popup_area);
+        let mut lines = if tracked == 0 {
+            vec![Line::from(Span::styled(
+                "  Nothing tracked this month yet",
+                theme::dim(),
+            ))]
+        } else {
+            vec![
+                Line::from(vec![
+                    Span::styled("  Prayers prayed:    ", theme::dim()),
+                    Span::styled(
+                        format!("{}", done),
+                        theme::green().add_modifier(Modifier::BOLD),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::styled("  Completion:        ", theme::dim()),
+                    Span::styled(
+                        format!("{:.0}%", done as f64 * 100.0 / expected.max(1) as f64),
+                        theme::green(),
+                    ),
+                    Span::styled(
+                        format!("  over {} of {} days tracked", tracked, elapsed),
+                        theme::dim(),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::styled("  Best streak:       ", theme::dim()),
+                    Span::styled(
+                        format!("{} day{}", best, if best == 1 { "" } else { "s" }),
+                        theme::green(),
+                    ),
+                ]),
+            ]
+        };
+        if let Some(goal) = &self.monthly_goal {
+            let mut spans = vec![Span::styled("  Monthly goal:      ", theme::dim())];
+            spans.extend(streak::goal_spans(goal));
+            lines.push(Line::from(spans));
+        }
+        frame.render_widget(Paragraph::new(lines), chunks[2]);
+    }
+
+    fn draw_hijri(&self, frame: &mut Frame) {
+        let area = frame.area();
+        frame.render_widget(Block::default().style(theme::base()), area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(18),
+                Constraint::Min(0),
+            ])
+            .split(area);
+
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled("  Hijri Calendar  ", theme::gold().add_modifier(Modifier::BOLD)),
+            Span::styled("  [ / ] month  ·  [g] this month  ·  [h] / [Esc] back", theme::dim()),
+        ]));
+        frame.render_widget(title, chunks[0]);
+
+        let Some(month) = &self.hijri_month else {
+            let msg = Paragraph::new(Span::styled(
+                "  The Hijri date could not be computed for this day",
+                theme::dim(),
+            ));
+            frame.render_widget(msg, chunks[1]);
+            return;
+        };
+        let calendar_area = Rect {
+            width: chunks[1].width.min(48),
+            ..chunks[1]
+        };
+        hijri::render(frame, calendar_area, month, self.live_date);
+
+        // The month's marked days, then where today sits relative to Ramadan and Hajj
+        let mut lines: Vec<Line> = month
+            .days
+            .iter()
+            .filter_map(|d| d.event.map(|e| (d, e)))
+            .map(|(d, event)| {
+                Line::from(vec![
+                    Span::styled(format!("  {:>2} {:<16}", d.day, event.label()), theme::bold()),
+                    Span::styled(d.date.format("%a %-d %b").to_string(), theme::dim()),
+                ])
+            })
+            .collect();
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        let offset = self.config.salah.hijri_offset;
+        for (month_no, name) in [(9, "Ramadan"), (12, "Dhu al-Hijjah")] {
+            let line = match hijri_month_days(self.live_date, offset) {
+                Ok(current) if current.month == month_no => {
+                    let day = current.days.iter().position(|d| d.date == self.live_date).unwrap_or(0) + 1;
+                    Span::styled(format!("  {}: day {} today", name, day), theme::green())
+                }
+                _ => match days_until_month(self.live_date, offset, month_no) {
+                    Some(days) => Span::styled(
+                        format!("  {} begins in {} day{}", name, days, if days == 1 { "" } else { "s" }),
+                        theme::dim(),
+                    ),
+                    None => continue,
+                },
+            };
+            lines.push(Line::from(line));
+        }
+        frame.render_widget(Paragraph::new(lines), chunks[2]);
+    }
+
+    fn draw_help_overlay(&self, frame: &mut Frame) {
+        let area = frame.area();
+        // Rebindable keys, joined with their fixed alternative when there is one
+        let key = |action: Action, fixed: &str| {
+            let hint = self.keymap.hint(action);
+            let label = match (hint.is_empty(), fixed.is_empty()) {
+                (_, true) => hint,
+                (true, false) => fixed.to_string(),
+                (false, false) => format!("{} / {}", hint, fixed),
+            };
+            Span::styled(format!("  {:<13}", label), theme::gold())
+        };
+        let vim_keys: Vec<String> = [Action::Up, Action::Down]
+            .into_iter()
+            .map(|action| self.keymap.hint(action))
+            .filter(|hint| !hint.is_empty())
+            .collect();
+        let navigate = if vim_keys.is_empty() {
+            "Navigate items".to_string()
+        } else {
+            format!("Navigate items (also {})", vim_keys.join(" "))
+        };
 
         let help_text = vec![
             Line::from(Span::styled(
@@ -574,48 +1914,111 @@ impl App {
             )),
             Line::from(""),
             Line::from(vec![
-                Span::styled("  [m] / Enter  ", theme::gold()),
-                Span::styled("Mark prayer done", theme::dim()),
+                key(Action::Mark, "Enter"),
+                Span::styled("Mark prayer done (toggle a nafl when Nafl is focused)", theme::dim()),
             ]),
             Line::from(vec![
-                Span::styled("  [M]          ", theme::gold()),
+                key(Action::Missed, ""),
                 Span::styled("Mark prayer missed + qada", theme::dim()),
             ]),
             Line::from(vec![
-                Span::styled("  [d]          ", theme::gold()),
+                key(Action::Dhikr, ""),
                 Span::styled("Toggle / increment dhikr", theme::dim()),
             ]),
             Line::from(vec![
-                Span::styled("  [r]          ", theme::gold()),
+                Span::styled("  [-] / [0]    ", theme::gold()),
+                Span::styled("Counter dhikr: take one off / reset to zero ([x] too)", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [i]          ", theme::gold()),
+                Span::styled("Prayer note / dhikr details and streak", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [n]          ", theme::gold()),
+                Span::styled("Add or edit a note on the focused prayer", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [c] / [C]    ", theme::gold()),
+                Span::styled("Collapse dhikr category / expand all", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [/]          ", theme::gold()),
+                Span::styled("Filter adhkar by name (Esc clears)", theme::dim()),
+            ]),
+            Line::from(vec![
+                key(Action::Quran, ""),
                 Span::styled("Log Quran pages", theme::dim()),
             ]),
             Line::from(vec![
-                Span::styled("  [s]          ", theme::gold()),
+                Span::styled("  [t]          ", theme::gold()),
+                Span::styled("Toggle qiyam for tonight", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [e]          ", theme::gold()),
+                Span::styled("Toggle Eid prayer (on Eid day)", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [j]          ", theme::gold()),
+                Span::styled("Toggle Jummah attended (on Fridays)", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [f]          ", theme::gold()),
+                Span::styled("Toggle a voluntary fast (with [fasting] on)", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [ / ]        ", theme::gold()),
+                Span::styled("Previous / next day", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [g]          ", theme::gold()),
+                Span::styled("Back to today", theme::dim()),
+            ]),
+            Line::from(vec![
+                key(Action::Stats, ""),
                 Span::styled("Stats view", theme::dim()),
             ]),
+            Line::from(vec![
+                key(Action::Qada, ""),
+                Span::styled("Qada queue", theme::dim()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [h]          ", theme::gold()),
+                Span::styled("Hijri month calendar", theme::dim()),
+            ]),
             Line::from(vec![
                 Span::styled("  [Tab]        ", theme::gold()),
                 Span::styled("Switch focus section", theme::dim()),
             ]),
             Line::from(vec![
                 Span::styled("  [↑ ↓]        ", theme::gold()),
-                Span::styled("Navigate items", theme::dim()),
+                Span::styled(navigate, theme::dim()),
             ]),
             Line::from(vec![
-                Span::styled("  [?]          ", theme::gold()),
+                key(Action::Help, ""),
                 Span::styled("Toggle help", theme::dim()),
             ]),
             Line::from(vec![
-                Span::styled("  [Esc]        ", theme::gold()),
+                key(Action::Quit, "[Esc]"),
                 Span::styled("Quit", theme::dim()),
             ]),
         ];
 
-        let block = Block::default()
+        // Center a help box, tall enough for every binding
+        let height = (area.height / 2).max(help_text.len() as u16 + 2).min(area.height);
+        let popup_area = Rect {
+            x: area.width / 4,
+            y: (area.height - height) / 2,
+            width: area.width / 2,
+            height,
+        };
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
             .title(Span::styled(" Help ", theme::gold()))
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(theme::gold())
+            .border_style(theme::border(true))
             .style(theme::surface());
 
         let paragraph = Paragraph::new(help_text).block(block);
@@ -644,7 +2047,11 @@ impl App {
             ]),
             Line::from(""),
             Line::from(Span::styled(
-                "  Type a number, then [Enter]  ·  [Esc] cancel",
+                if self.quran_history.is_empty() {
+                    "  Type a number (=N sets the total), then [Enter]  ·  [Esc] cancel"
+                } else {
+                    "  Type a number (=N sets the total), then [Enter]  ·  [↑↓] recent  ·  [Esc] cancel"
+                },
                 theme::dim(),
             )),
         ];
@@ -674,6 +2081,368 @@ impl App {
         frame.render_widget(paragraph, popup_area);
     }
 
+    fn draw_day_summary(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let popup_area = Rect {
+            x: area.width / 4,
+            y: area.height / 4,
+            width: area.width / 2,
+            height: (area.height / 2).max(14).min(area.height),
+        };
+
+        frame.render_widget(Clear, popup_area);
+
+        let names = |status: PrayerStatus| -> String {
+            let list: Vec<&str> = self
+                .prayers
+                .iter()
+                .filter(|p| p.status == status)
+                .map(|p| p.prayer_type.display_name())
+                .collect();
+            if list.is_empty() {
+                "—".to_string()
+            } else {
+                list.join(", ")
+            }
+        };
+        let dhikr_done = self.dhikr_logs.values().filter(|l| l.completed).count();
+        let quran_style = if self.quran_today >= self.quran_target() {
+            theme::green()
+        } else {
+            theme::amber()
+        };
+
+        let lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  {}", self.today.format("%A, %d %B")),
+                theme::gold().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("  Prayed    ", theme::dim()),
+                Span::styled(names(PrayerStatus::Done), theme::green()),
+            ]),
+            Line::from(vec![
+                Span::styled("  Missed    ", theme::dim()),
+                Span::styled(names(PrayerStatus::Missed), theme::red()),
+            ]),
+            Line::from(vec![
+                Span::styled("  Pending   ", theme::dim()),
+                Span::styled(names(PrayerStatus::Pending), theme::amber()),
+            ]),
+            Line::from(vec![
+                Span::styled("  Adhkar    ", theme::dim()),
+                Span::styled(
+                    format!("{} / {} done", dhikr_done, self.dhikr_defs.len()),
+                    theme::bold(),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("  Quran     ", theme::dim()),
+                Span::styled(
+                    format!(
+                        "{} / {} pages",
+                        format_pages(self.quran_today),
+                        format_pages(self.quran_target())
+                    ),
+                    quran_style,
+                ),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                "  [Enter] close the day  ·  [Esc] later",
+                theme::dim(),
+            )),
+        ];
+
+        let block = Block::default()
+            .title(Span::styled(" End of Day ", theme::gold()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme::border(true))
+            .style(theme::surface());
+
+        let paragraph = Paragraph::new(lines).block(block);
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    fn draw_catchup(&self, frame: &mut Frame, gap: Gap) {
+        let area = frame.area();
+
+        let height = 14.min(area.height);
+        let popup_area = Rect {
+            x: area.width / 6,
+            y: area.height.saturating_sub(height) / 2,
+            width: area.width * 2 / 3,
+            height,
+        };
+
+        frame.render_widget(Clear, popup_area);
+
+        let lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "  Welcome back",
+                theme::gold().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    "  {} days without a prayer tracked ({} → {}).",
+                    gap.days(),
+                    gap.from.format("%b %-d"),
+                    gap.to.format("%b %-d")
+                ),
+                theme::base(),
+            )),
+            Line::from(Span::styled(
+                "  However those days went, today is a fresh start.",
+                theme::dim(),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("  [1] ", theme::gold()),
+                Span::styled("I prayed them — mark done", theme::base()),
+            ]),
+            Line::from(vec![
+                Span::styled("  [2] ", theme::gold()),
+                Span::styled(
+                    format!("Missed — add {} prayers to qada", gap.prayers()),
+                    theme::base(),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("  [3] ", theme::gold()),
+                Span::styled("Leave those days blank", theme::base()),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                "  [Esc] decide later (`sujood catchup`)",
+                theme::dim(),
+            )),
+        ];
+
+        let block = Block::default()
+            .title(Span::styled(" Catch up ", theme::gold()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme::border(true))
+            .style(theme::surface());
+
+        let paragraph = Paragraph::new(lines).block(block);
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    fn draw_note_input(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let height = if self.input_error.is_some() { 7 } else { 5 };
+        let name = self
+            .prayers
+            .get(self.focus_idx)
+            .map(|p| p.prayer_type.display_name())
+            .unwrap_or_default();
+
+        let popup_area = Rect {
+            x: area.width / 4,
+            y: area.height / 2 - 3,
+            width: area.width / 2,
+            height,
+        };
+
+        frame.render_widget(Clear, popup_area);
+
+        let mut text = vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("  Note: ", theme::dim()),
+                Span::styled(self.input_buffer.as_str(), theme::gold().add_modifier(Modifier::BOLD)),
+                Span::styled("█", theme::amber()),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                "  [Enter] save (empty clears it)  ·  [Esc] cancel",
+                theme::dim(),
+            )),
+        ];
+
+        if let Some(err) = &self.input_error {
+            text.push(Line::from(""));
+            text.push(Line::from(Span::styled(format!("  ✗ {}", err), theme::red())));
+        }
+
+        let block = Block::default()
+            .title(Span::styled(format!(" {} Note ", name), theme::gold()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme::amber())
+            .style(theme::surface());
+
+        let paragraph = Paragraph::new(text).block(block);
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    fn draw_prayer_detail(&self, frame: &mut Frame, prayer: &Prayer) {
+        let area = frame.area();
+
+        let popup_area = Rect {
+            x: area.width / 4,
+            y: area.height / 4,
+            width: area.width / 2,
+            height: 11.min(area.height),
+        };
+
+        frame.render_widget(Clear, popup_area);
+
+        let (status, status_style) = match prayer.status {
+            PrayerStatus::Done => ("done", theme::green()),
+            PrayerStatus::Missed => ("missed", theme::red()),
+            PrayerStatus::Pending => ("upcoming", theme::dim()),
+        };
+        let time = prayer
+            .time
+            .map(|t| t.format("%H:%M").to_string())
+            .unwrap_or_else(|| "--:--".to_string());
+
+        let lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  {}", prayer.prayer_type.display_name()),
+                theme::gold().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(format!("  {}  ·  {}", prayer.date, time), theme::dim())),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(format!("  {:<12}", "Status"), theme::dim()),
+                Span::styled(status, status_style),
+            ]),
+            Line::from(vec![
+                Span::styled(format!("  {:<12}", "Note"), theme::dim()),
+                match &prayer.note {
+                    Some(note) => Span::styled(note.as_str(), theme::base()),
+                    None => Span::styled("none  ·  [n] to add one", theme::dim()),
+                },
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("  [any key] close", theme::dim())),
+        ];
+
+        let block = Block::default()
+            .title(Span::styled(" Prayer ", theme::gold()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme::border(true))
+            .style(theme::surface());
+
+        let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    fn draw_dhikr_detail(&self, frame: &mut Frame, detail: &DhikrDetail) {
+        let area = frame.area();
+        let (def, streak) = (&detail.def, &detail.streak);
+
+        let popup_area = Rect {
+            x: area.width / 4,
+            y: area.height / 4,
+            width: area.width / 2,
+            height: 15.min(area.height),
+        };
+
+        frame.render_widget(Clear, popup_area);
+
+        let unit = match def.frequency {
+            DhikrFrequency::Daily => "day",
+            DhikrFrequency::Weekly => "week",
+        };
+        let plural = |n: u32| if n == 1 { "" } else { "s" };
+        let log = self.dhikr_logs.get(&def.id);
+        let progress = match def.dhikr_type {
+            DhikrType::Checkbox if log.is_some_and(|l| l.completed) => "done".to_string(),
+            DhikrType::Checkbox => "not yet".to_string(),
+            DhikrType::Counter => {
+                format!("{}/{}", log.map(|l| l.count).unwrap_or(0), def.target_count)
+            }
+        };
+        let period = match def.frequency {
+            DhikrFrequency::Daily => "Today",
+            DhikrFrequency::Weekly => "This week",
+        };
+
+        // Recent history strip: a dot per day (or week) over its label
+        let mut dots = vec![Span::styled("  ", theme::dim())];
+        let mut labels = vec![Span::styled("  ", theme::dim())];
+        for (date, done) in &detail.recent {
+            let (dot, style) = if *done {
+                ("●", theme::green().add_modifier(Modifier::BOLD))
+            } else {
+                ("○", theme::dim())
+            };
+            dots.push(Span::styled(format!("{:<4}", dot), style));
+            let label = match def.frequency {
+                DhikrFrequency::Daily => date.format("%a").to_string()[..2].to_string(),
+                DhikrFrequency::Weekly => date.format("%d").to_string(),
+            };
+            labels.push(Span::styled(format!("{:<4}", label), theme::dim()));
+        }
+
+        let lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  {}", def.name),
+                theme::gold().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "  {} · {} · {}",
+                    def.group(),
+                    def.dhikr_type.as_str(),
+                    def.frequency.as_str()
+                ),
+                theme::dim(),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(format!("  {:<12}", period), theme::dim()),
+                Span::styled(progress, theme::base()),
+            ]),
+            Line::from(vec![
+                Span::styled(format!("  {:<12}", "Streak"), theme::dim()),
+                Span::styled(
+                    format!("{} {}{}", streak.current, unit, plural(streak.current)),
+                    theme::green(),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled(format!("  {:<12}", "Best"), theme::dim()),
+                Span::styled(
+                    format!("{} {}{}", streak.best, unit, plural(streak.best)),
+                    theme::base(),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  Last {} {}s", detail.recent.len(), unit),
+                theme::dim(),
+            )),
+            Line::from(dots),
+            Line::from(labels),
+            Line::from(""),
+            Line::from(Span::styled("  [any key] close", theme::dim())),
+        ];
+
+        let block = Block::default()
+            .title(Span::styled(" Dhikr ", theme::gold()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme::border(true))
+            .style(theme::surface());
+
+        let paragraph = Paragraph::new(lines).block(block);
+        frame.render_widget(paragraph, popup_area);
+    }
+
     fn draw_qada_overlay(&self, frame: &mut Frame) {
         let area = frame.area();
 
@@ -688,19 +2457,29 @@ impl App {
 
         let mut lines = vec![Line::from("")];
 
-        if self.qada_count == 0 {
+        if self.qada_pace.owed == 0 {
             lines.push(Line::from(vec![
                 Span::styled("  ", theme::dim()),
                 Span::styled("✓ No qada prayers owed", theme::green()),
             ]));
+            if self.qada_made_up_today > 0 {
+                lines.push(Line::from(Span::styled(
+                    format!("  {} made up today", self.qada_made_up_today),
+                    theme::green(),
+                )));
+            }
         } else {
             lines.push(Line::from(vec![
                 Span::styled("  ", theme::dim()),
                 Span::styled(
-                    format!("{} prayers owed", self.qada_count),
+                    format!("{} prayers owed", self.qada_pace.owed),
                     theme::amber().add_modifier(Modifier::BOLD),
                 ),
             ]));
+            lines.push(Line::from(Span::styled(
+                format!("  {} made up today", self.qada_made_up_today),
+                theme::green(),
+            )));
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
                 "  Use `sujood qada list` to see details",
@@ -712,7 +2491,11 @@ impl App {
             )));
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
-                format!("  At 1/day: ~{} days to clear", self.qada_count),
+                format!("  {}", self.qada_pace.estimate(self.live_date)),
+                theme::dim(),
+            )));
+            lines.push(Line::from(Span::styled(
+                format!("  {}", self.qada_pace.rate_label()),
                 theme::dim(),
             )));
         }
@@ -735,30 +2518,234 @@ impl App {
     }
 }
 
+/// app_meta key holding the last date closed from the end-of-day summary.
+const DAY_CLOSED_KEY: &str = "day_closed";
+
 /// Run the TUI event loop.
 pub fn run(conn: Connection, config: AppConfig) -> Result<()> {
     let mut app = App::new(config);
+    app.day_cutoff = app
+        .config
+        .day
+        .close_at
+        .as_deref()
+        .map(DayCutoff::parse)
+        .transpose()
+        .context("Reading [day] close_at")?;
     app.load(&conn)?;
+    app.catchup_gap = catchup::detect(&conn, app.live_date)?;
 
-    let mut terminal = ratatui::init();
-    let events = EventHandler::new(500);
-
-    loop {
-        terminal.draw(|frame| app.draw(frame))?;
-
-        match events.next()? {
-            Event::Key(key) => {
-                app.handle_key(key, &conn);
-                if app.should_quit {
-                    break;
+    let mouse = app.config.ui.mouse;
+    terminal::with_terminal(|terminal| {
+        if mouse {
+            crossterm::execute!(std::io::stdout(), EnableMouseCapture)
+                .context("Enabling mouse capture")?;
+        }
+        let events = EventHandler::new(app.config.ui.tick_ms.max(MIN_TICK_MS));
+
+        let result = (|| -> Result<()> {
+            loop {
+                terminal.draw(|frame| app.draw(frame))?;
+
+                match events.next()? {
+                    Event::Key(key) => {
+                        app.handle_key(key, &conn);
+                        if app.should_quit {
+                            break;
+                        }
+                    }
+                    Event::Mouse(mouse) => app.handle_mouse(mouse, &conn),
+                    Event::Resize => {}
+                    Event::Tick => {
+                        app.tick(&conn);
+                    }
                 }
             }
-            Event::Tick => {
-                app.tick(&conn);
-            }
+            Ok(())
+        })();
+
+        if mouse {
+            let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
+        }
+        result
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::run_migrations;
+    use crate::models::{Prayer, PrayerStatus};
+    use crossterm::event::{KeyEvent, KeyModifiers};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn all_pending() -> [PrayerStatus; 5] {
+        std::array::from_fn(|_| PrayerStatus::Pending)
+    }
+
+    /// An app viewing `today`, with prayers at 05:30, 12:30, 15:30, 18:30 and 19:30.
+    fn app_on(config: AppConfig, today: NaiveDate, statuses: [PrayerStatus; 5]) -> App {
+        let mut app = App::new(config);
+        app.today = today;
+        app.today_str = date_str(today);
+        app.live_date = today;
+        app.prayers = PrayerType::all()
+            .into_iter()
+            .zip(statuses)
+            .zip([5, 12, 15, 18, 19])
+            .map(|((prayer_type, status), hour)| Prayer {
+                id: None,
+                prayer_type,
+                date: date_str(today),
+                status,
+                is_qada: false,
+                note: None,
+                time: NaiveTime::from_hms_opt(hour, 30, 0),
+                scheduled_time: None,
+                marked_at: None,
+            })
+            .collect();
+        app
+    }
+
+    #[test]
+    fn day_end_precreates_tomorrows_pending_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let mut config = AppConfig::default();
+        config.day.precreate_tomorrow_rows = true;
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let mut app = app_on(config, today, all_pending());
+
+        // Isha has not come yet: nothing is created
+        app.precreate_tomorrow(&conn, today, today.and_hms_opt(19, 0, 0).unwrap());
+        assert!(!app.tomorrow_rows_ready);
+        assert!(PrayerRepo::get_by_date(&conn, "2026-10-17").unwrap().is_empty());
+
+        app.precreate_tomorrow(&conn, today, today.and_hms_opt(23, 0, 0).unwrap());
+        assert!(app.tomorrow_rows_ready);
+        let tomorrow = PrayerRepo::get_by_date(&conn, "2026-10-17").unwrap();
+        assert_eq!(tomorrow.len(), 5);
+        assert!(tomorrow.iter().all(|p| p.status == PrayerStatus::Pending));
+    }
+
+    #[test]
+    fn quran_prompt_recalls_older_entries_up_and_newer_down() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        for pages in [2.0, 10.0, 20.0] {
+            QuranRepo::remember_input(&conn, pages, 10).unwrap();
         }
+        let mut app = App::new(AppConfig::default());
+        app.run_action(Action::Quran, &conn);
+
+        let mut press = |code| {
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &conn);
+            app.input_buffer.clone()
+        };
+        let recalled: Vec<String> = [
+            KeyCode::Up,
+            KeyCode::Up,
+            KeyCode::Up,
+            KeyCode::Up,
+            KeyCode::Down,
+            KeyCode::Down,
+            KeyCode::Down,
+        ]
+        .into_iter()
+        .map(&mut press)
+        .collect();
+        assert_eq!(recalled, ["20", "10", "2", "2", "10", "20", ""]);
+    }
+
+    #[test]
+    fn day_summary_opens_once_the_cutoff_is_crossed() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let mut app = app_on(AppConfig::default(), today, all_pending());
+        // Isha at 19:30, closing an hour after it
+        app.day_cutoff = Some(DayCutoff::AfterIsha(60));
+
+        app.offer_day_summary(today.and_hms_opt(20, 29, 0).unwrap());
+        assert!(!app.show_day_summary);
+        app.offer_day_summary(today.and_hms_opt(20, 30, 0).unwrap());
+        assert!(app.show_day_summary);
+
+        // Esc'd for the session: it stays closed
+        app.show_day_summary = false;
+        app.day_summary_dismissed = true;
+        app.offer_day_summary(today.and_hms_opt(23, 0, 0).unwrap());
+        assert!(!app.show_day_summary);
+
+        // Without a cutoff it never opens
+        let mut app = app_on(AppConfig::default(), today, all_pending());
+        app.offer_day_summary(today.and_hms_opt(23, 59, 0).unwrap());
+        assert!(!app.show_day_summary);
+    }
+
+    #[test]
+    fn day_summary_lists_prayed_missed_and_pending() {
+        use PrayerStatus::*;
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let mut app = app_on(AppConfig::default(), today, [Done, Missed, Done, Pending, Done]);
+        app.quran_today = 3.0;
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|frame| app.draw_day_summary(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+        let row = |label: &str| {
+            rows.iter()
+                .find(|r| r.contains(label))
+                .unwrap_or_else(|| panic!("no {label} row"))
+                .clone()
+        };
+
+        assert!(row("Prayed").contains("Fajr, Asr, Isha"));
+        assert!(row("Missed").contains("Zuhr"));
+        assert!(row("Pending").contains("Maghrib"));
+        assert!(row("Quran").contains("3 / "));
+        assert!(rows.iter().any(|r| r.contains("Friday, 16 October")));
     }
 
-    ratatui::restore();
-    Ok(())
+    #[test]
+    fn eid_tracker_is_offered_on_eid_and_records_attendance() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        let start = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        let (eid_day, (eid, year)) = start
+            .iter_days()
+            .take(366)
+            .find_map(|d| eid_on(d, 0).map(|found| (d, found)))
+            .expect("an Eid within the year");
+
+        let mut app = app_on(AppConfig::default(), eid_day, all_pending());
+        app.load_eid(&conn).unwrap();
+        assert_eq!(app.eid_today, Some((eid, year)));
+        assert!(app.eid_entry.is_none());
+
+        app.toggle_eid(&conn);
+        let entry = EidRepo::get(&conn, year as i64, eid).unwrap().unwrap();
+        assert!(entry.attended);
+        assert_eq!(entry.date, date_str(eid_day));
+        app.toggle_eid(&conn);
+        assert!(!EidRepo::get(&conn, year as i64, eid).unwrap().unwrap().attended);
+
+        // The day before isn't Eid: nothing is offered or recorded
+        let mut app = app_on(AppConfig::default(), eid_day.pred_opt().unwrap(), all_pending());
+        app.load_eid(&conn).unwrap();
+        assert!(app.eid_today.is_none());
+        app.toggle_eid(&conn);
+        assert_eq!(EidRepo::history(&conn).unwrap().len(), 1);
+
+        // Nor when the tracker is turned off
+        let mut config = AppConfig::default();
+        config.eid.enabled = false;
+        let mut app = app_on(config, eid_day, all_pending());
+        app.load_eid(&conn).unwrap();
+        assert!(app.eid_today.is_none());
+    }
 }
@@ -1,12 +1,16 @@
 use std::sync::mpsc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crossterm::event::{self, Event as CEvent, KeyEvent};
+use crossterm::event::{self, Event as CEvent, KeyEvent, MouseEvent, MouseEventKind};
 
 #[derive(Debug)]
 pub enum Event {
     Key(KeyEvent),
+    /// A mouse button pressed; moves, drags and releases aren't passed on
+    Mouse(MouseEvent),
+    /// The terminal was resized; redraw without waiting for the next tick
+    Resize,
     Tick,
 }
 
@@ -15,34 +19,38 @@ pub struct EventHandler {
 }
 
 impl EventHandler {
+    /// Ticks are aligned to the wall clock, so with a 1000 ms rate each one
+    /// lands just after a second turns over and a minute countdown changes
+    /// on time. Between events the thread sleeps in `poll`.
     pub fn new(tick_rate_ms: u64) -> Self {
         let (tx, rx) = mpsc::channel();
-        let tick_rate = Duration::from_millis(tick_rate_ms);
+        let tick_rate_ms = tick_rate_ms.max(1);
 
         thread::spawn(move || {
-            let mut last_tick = std::time::Instant::now();
+            let mut next_tick = Instant::now() + until_next_tick(tick_rate_ms);
             loop {
-                let timeout = tick_rate
-                    .checked_sub(last_tick.elapsed())
-                    .unwrap_or(Duration::ZERO);
+                let timeout = next_tick.saturating_duration_since(Instant::now());
 
                 if event::poll(timeout).unwrap_or(false) {
-                    match event::read() {
-                        Ok(CEvent::Key(key)) => {
-                            if tx.send(Event::Key(key)).is_err() {
-                                break;
-                            }
+                    let sent = match event::read() {
+                        Ok(CEvent::Key(key)) => tx.send(Event::Key(key)),
+                        Ok(CEvent::Mouse(mouse)) if matches!(mouse.kind, MouseEventKind::Down(_)) => {
+                            tx.send(Event::Mouse(mouse))
                         }
-                        Ok(_) => {}
+                        Ok(CEvent::Resize(..)) => tx.send(Event::Resize),
+                        Ok(_) => Ok(()),
                         Err(_) => break,
+                    };
+                    if sent.is_err() {
+                        break;
                     }
                 }
 
-                if last_tick.elapsed() >= tick_rate {
+                if Instant::now() >= next_tick {
                     if tx.send(Event::Tick).is_err() {
                         break;
                     }
-                    last_tick = std::time::Instant::now();
+                    next_tick = Instant::now() + until_next_tick(tick_rate_ms);
                 }
             }
         });
@@ -54,3 +62,12 @@ impl EventHandler {
         self.rx.recv()
     }
 }
+
+/// Time left until the clock next reaches a whole multiple of `tick_rate_ms`.
+fn until_next_tick(tick_rate_ms: u64) -> Duration {
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    Duration::from_millis(tick_rate_ms - now_ms % tick_rate_ms)
+}
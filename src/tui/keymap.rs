@@ -0,0 +1,62 @@
+use crossterm::event::KeyCode;
+use std::collections::HashMap;
+
+use crate::config::KeybindingsConfig;
+
+/// Dashboard actions whose keys come from `[keybindings]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Mark,
+    Missed,
+    Dhikr,
+    Quran,
+    Stats,
+    Help,
+    Qada,
+    Quit,
+    Up,
+    Down,
+}
+
+/// The configured keys, looked up before the dashboard's fixed ones.
+pub struct Keymap {
+    actions: HashMap<char, Action>,
+    keys: HashMap<Action, char>,
+}
+
+impl Keymap {
+    pub fn new(config: &KeybindingsConfig) -> Self {
+        let mut actions = HashMap::new();
+        let mut keys = HashMap::new();
+        for (name, key) in config.bindings() {
+            let action = match name {
+                "mark" => Action::Mark,
+                "missed" => Action::Missed,
+                "dhikr" => Action::Dhikr,
+                "quran" => Action::Quran,
+                "stats" => Action::Stats,
+                "help" => Action::Help,
+                "qada" => Action::Qada,
+                "quit" => Action::Quit,
+                "up" => Action::Up,
+                "down" => Action::Down,
+                _ => continue,
+            };
+            actions.insert(key, action);
+            keys.insert(action, key);
+        }
+        Self { actions, keys }
+    }
+
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        match code {
+            KeyCode::Char(c) => self.actions.get(&c).copied(),
+            _ => None,
+        }
+    }
+
+    /// The action's key as hints show it, e.g. "[m]"; empty when unbound.
+    pub fn hint(&self, action: Action) -> String {
+        self.keys.get(&action).map(|k| format!("[{}]", k)).unwrap_or_default()
+    }
+}
@@ -1,4 +1,7 @@
+pub mod alert;
 pub mod app;
 pub mod events;
+pub mod keymap;
+pub mod terminal;
 pub mod theme;
 pub mod widgets;
@@ -0,0 +1,58 @@
+use std::io::{self, IsTerminal};
+
+use anyhow::{anyhow, Context, Result};
+use ratatui::{layout::Rect, DefaultTerminal};
+
+/// Whether an area has room to draw anything at all. Broken or detached
+/// terminals (e.g. a dropped SSH session) report 0×0.
+pub fn is_usable_area(area: Rect) -> bool {
+    area.width > 0 && area.height > 0
+}
+
+/// Check the terminal can host a full-screen UI before switching it into raw
+/// mode, so a bad session gets a plain message instead of a garbled screen.
+pub fn ensure_usable() -> Result<()> {
+    let unusable = |reason: &str| {
+        anyhow!(
+            "Can't start the full-screen interface: {}. \
+             Commands such as `sujood today` work without it.",
+            reason
+        )
+    };
+
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(unusable("not running in an interactive terminal"));
+    }
+    if std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+        return Err(unusable("TERM=dumb has no cursor or colour support"));
+    }
+    let (width, height) = crossterm::terminal::size()
+        .map_err(|e| unusable(&format!("terminal size unavailable ({})", e)))?;
+    if !is_usable_area(Rect::new(0, 0, width, height)) {
+        return Err(unusable(&format!("terminal reports a {}×{} window", width, height)));
+    }
+    Ok(())
+}
+
+/// Run `body` on an initialised terminal, restoring it however `body` exits.
+pub fn with_terminal<T>(body: impl FnOnce(&mut DefaultTerminal) -> Result<T>) -> Result<T> {
+    ensure_usable()?;
+    let mut terminal = ratatui::try_init().context("Initialising terminal")?;
+    let result = body(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_sized_areas_are_unusable() {
+        assert!(!is_usable_area(Rect::new(0, 0, 0, 0)));
+        assert!(!is_usable_area(Rect::new(0, 0, 80, 0)));
+        assert!(!is_usable_area(Rect::new(0, 0, 0, 24)));
+        assert!(is_usable_area(Rect::new(0, 0, 80, 24)));
+        assert!(is_usable_area(Rect::new(0, 0, 1, 1)));
+    }
+}
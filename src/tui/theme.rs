@@ -1,46 +1,138 @@
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
 use ratatui::style::{Color, Modifier, Style};
 
-pub const BG: Color = Color::Rgb(18, 16, 14);
-pub const SURFACE: Color = Color::Rgb(28, 25, 20);
-pub const BORDER: Color = Color::Rgb(55, 48, 36);
-pub const BORDER_FOCUS: Color = Color::Rgb(196, 160, 68);
-pub const TEXT: Color = Color::Rgb(230, 218, 196);
-pub const TEXT_DIM: Color = Color::Rgb(130, 118, 96);
-pub const GOLD: Color = Color::Rgb(196, 160, 68);
-pub const GREEN: Color = Color::Rgb(92, 148, 92);
-pub const AMBER: Color = Color::Rgb(210, 138, 60);
-pub const RED: Color = Color::Rgb(180, 82, 62);
-pub const FILL: Color = Color::Rgb(70, 62, 48);
-pub const EMPTY: Color = Color::Rgb(38, 34, 26);
+/// Every color the TUI draws with. `set` picks one for the session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub bg: Color,
+    pub surface: Color,
+    pub border: Color,
+    pub border_focus: Color,
+    pub text: Color,
+    pub text_dim: Color,
+    pub gold: Color,
+    pub green: Color,
+    pub amber: Color,
+    pub red: Color,
+    pub fill: Color,
+    pub empty: Color,
+}
+
+pub const DARK: Theme = Theme {
+    bg: Color::Rgb(18, 16, 14),
+    surface: Color::Rgb(28, 25, 20),
+    border: Color::Rgb(55, 48, 36),
+    border_focus: Color::Rgb(196, 160, 68),
+    text: Color::Rgb(230, 218, 196),
+    text_dim: Color::Rgb(130, 118, 96),
+    gold: Color::Rgb(196, 160, 68),
+    green: Color::Rgb(92, 148, 92),
+    amber: Color::Rgb(210, 138, 60),
+    red: Color::Rgb(180, 82, 62),
+    fill: Color::Rgb(70, 62, 48),
+    empty: Color::Rgb(38, 34, 26),
+};
+
+/// Parchment background with the accents darkened enough to read on it.
+pub const LIGHT: Theme = Theme {
+    bg: Color::Rgb(250, 247, 240),
+    surface: Color::Rgb(255, 252, 245),
+    border: Color::Rgb(205, 195, 175),
+    border_focus: Color::Rgb(150, 110, 20),
+    text: Color::Rgb(40, 34, 26),
+    text_dim: Color::Rgb(115, 104, 86),
+    gold: Color::Rgb(150, 110, 20),
+    green: Color::Rgb(46, 115, 52),
+    amber: Color::Rgb(180, 95, 20),
+    red: Color::Rgb(170, 50, 40),
+    fill: Color::Rgb(215, 203, 178),
+    empty: Color::Rgb(236, 229, 214),
+};
+
+/// Names accepted by `ui.theme` and `--theme`.
+pub const THEME_NAMES: &[&str] = &["dark", "light"];
+
+impl Theme {
+    pub fn by_name(name: &str) -> Result<Theme> {
+        match name.to_lowercase().as_str() {
+            "dark" => Ok(DARK),
+            "light" => Ok(LIGHT),
+            _ => Err(anyhow!(
+                "Unknown theme '{}'. Use one of: {}",
+                name,
+                THEME_NAMES.join(", ")
+            )),
+        }
+    }
+
+    /// Panel border: `border_focus` for the focused panel or an overlay, `border` otherwise.
+    pub fn border_style(&self, focused: bool) -> Style {
+        Style::default().fg(if focused { self.border_focus } else { self.border })
+    }
+}
+
+static ACTIVE: OnceLock<Theme> = OnceLock::new();
+
+/// Choose the session's theme. Only the first call takes effect; until then
+/// everything draws with `DARK`.
+pub fn set(theme: Theme) {
+    let _ = ACTIVE.set(theme);
+}
+
+pub fn palette() -> &'static Theme {
+    ACTIVE.get().unwrap_or(&DARK)
+}
 
 pub fn base() -> Style {
-    Style::default().fg(TEXT).bg(BG)
+    Style::default().fg(palette().text).bg(palette().bg)
 }
 
 pub fn dim() -> Style {
-    Style::default().fg(TEXT_DIM)
+    Style::default().fg(palette().text_dim)
 }
 
 pub fn gold() -> Style {
-    Style::default().fg(GOLD)
+    Style::default().fg(palette().gold)
 }
 
 pub fn green() -> Style {
-    Style::default().fg(GREEN)
+    Style::default().fg(palette().green)
 }
 
 pub fn amber() -> Style {
-    Style::default().fg(AMBER)
+    Style::default().fg(palette().amber)
 }
 
 pub fn red() -> Style {
-    Style::default().fg(RED)
+    Style::default().fg(palette().red)
 }
 
 pub fn bold() -> Style {
-    Style::default().fg(TEXT).add_modifier(Modifier::BOLD)
+    Style::default().fg(palette().text).add_modifier(Modifier::BOLD)
+}
+
+pub fn border(focused: bool) -> Style {
+    palette().border_style(focused)
 }
 
 pub fn surface() -> Style {
-    Style::default().fg(TEXT).bg(SURFACE)
+    Style::default().fg(palette().text).bg(palette().surface)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn focus_and_plain_borders_use_the_themes_colors() {
+        for theme in [DARK, LIGHT] {
+            assert_eq!(theme.border_style(true).fg, Some(theme.border_focus));
+            assert_eq!(theme.border_style(false).fg, Some(theme.border));
+        }
+        assert_ne!(DARK.border_style(true), LIGHT.border_style(true));
+        assert_eq!(border(true), palette().border_style(true));
+        assert_eq!(border(false), palette().border_style(false));
+    }
 }
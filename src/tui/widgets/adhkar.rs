@@ -6,75 +6,166 @@ use ratatui::{
     Frame,
 };
 
-use crate::models::{DhikrDef, DhikrLog, DhikrType};
+use crate::models::{group_dhikr, DhikrDef, DhikrLog, DhikrType};
 use crate::tui::theme;
 
+/// A line of the adhkar list: a category heading, or a dhikr with its index
+/// among the focusable ones.
+enum Row<'a> {
+    Group { name: String, collapsed: Option<usize> },
+    Dhikr(usize, &'a DhikrDef),
+    NoMatches,
+}
+
+/// `focus_idx` counts only dhikr in expanded groups, or only matches while
+/// filtering; a collapsed group shows how many it hides.
+fn rows<'a>(
+    defs: &'a [DhikrDef],
+    collapsed: &std::collections::HashSet<String>,
+    filter: &str,
+) -> Vec<Row<'a>> {
+    let mut rows = Vec::new();
+    let mut visible_idx = 0;
+
+    for (group, mut members) in group_dhikr(defs) {
+        if !filter.is_empty() {
+            members.retain(|d| d.name_matches(filter));
+            if members.is_empty() {
+                continue;
+            }
+        } else if collapsed.contains(&group) {
+            rows.push(Row::Group { name: group, collapsed: Some(members.len()) });
+            continue;
+        }
+        rows.push(Row::Group { name: group, collapsed: None });
+        for def in members {
+            rows.push(Row::Dhikr(visible_idx, def));
+            visible_idx += 1;
+        }
+    }
+
+    if rows.is_empty() && !filter.is_empty() {
+        rows.push(Row::NoMatches);
+    }
+    rows
+}
+
+/// The focusable index of the dhikr drawn on line `row` of the list (0 is
+/// the first line inside the border), for the same groups and filter as `render`.
+pub fn dhikr_at(
+    defs: &[DhikrDef],
+    collapsed: &std::collections::HashSet<String>,
+    filter: &str,
+    row: usize,
+) -> Option<usize> {
+    match rows(defs, collapsed, filter).get(row)? {
+        Row::Dhikr(idx, _) => Some(*idx),
+        _ => None,
+    }
+}
+
+/// How the list is narrowed: the collapsed groups and the name filter.
+pub struct ListView<'a> {
+    pub collapsed: &'a std::collections::HashSet<String>,
+    pub filter: &'a str,
+    /// The filter is being typed, so it shows a cursor
+    pub typing_filter: bool,
+}
+
 pub fn render(
     frame: &mut Frame,
     area: Rect,
     defs: &[DhikrDef],
     logs: &std::collections::HashMap<i64, DhikrLog>,
+    view: &ListView,
     focus_idx: usize,
     focused: bool,
 ) {
+    let ListView { collapsed, filter, typing_filter } = *view;
+    let mut title = vec![Span::styled(" Adhkar ", theme::gold())];
+    if typing_filter || !filter.is_empty() {
+        title.push(Span::styled(format!("/{}", filter), theme::amber()));
+        if typing_filter {
+            title.push(Span::styled("█", theme::amber()));
+        }
+        title.push(Span::raw(" "));
+    }
     let block = Block::default()
-        .title(Span::styled(" Adhkar ", theme::gold()))
+        .title(Line::from(title))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(if focused {
-            theme::gold()
-        } else {
-            ratatui::style::Style::default().fg(crate::tui::theme::BORDER)
-        })
+        .border_style(theme::border(focused))
         .style(theme::surface());
 
-    let items: Vec<ListItem> = defs
-        .iter()
-        .enumerate()
-        .map(|(i, def)| {
-            let log = logs.get(&def.id);
-            let is_focused = focused && i == focus_idx;
-
-            let name_style = if is_focused {
-                theme::gold().add_modifier(Modifier::BOLD)
-            } else {
-                theme::bold()
-            };
-
-            let status_span = match &def.dhikr_type {
-                DhikrType::Checkbox => {
-                    let done = log.map(|l| l.completed).unwrap_or(false);
-                    if done {
-                        Span::styled("●", theme::green())
-                    } else {
-                        Span::styled("○", theme::dim())
-                    }
-                }
-                DhikrType::Counter => {
-                    let count = log.map(|l| l.count).unwrap_or(0);
-                    let target = def.target_count;
-                    let done = count >= target;
-
-                    // Build a small progress bar (6 chars wide)
-                    let ratio = (count as f64 / target as f64).min(1.0);
-                    let filled = (ratio * 5.0).round() as usize;
-                    let empty = 5usize.saturating_sub(filled);
-                    let bar = format!("{}{}", "▓".repeat(filled), "░".repeat(empty));
-
-                    let color = if done { theme::green() } else { theme::amber() };
-                    let text = format!("{} {}/{}", bar, count, target);
-                    Span::styled(text, color)
+    let mut items: Vec<ListItem> = Vec::new();
+
+    for row in rows(defs, collapsed, filter) {
+        let (idx, def) = match row {
+            Row::Group { name, collapsed: Some(hidden) } => {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    format!("  ▸ {} ({})", name, hidden),
+                    theme::dim(),
+                ))));
+                continue;
+            }
+            Row::Group { name, collapsed: None } => {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    format!("  ▾ {}", name),
+                    theme::dim(),
+                ))));
+                continue;
+            }
+            Row::NoMatches => {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    "  No dhikr matches",
+                    theme::dim(),
+                ))));
+                continue;
+            }
+            Row::Dhikr(idx, def) => (idx, def),
+        };
+        let log = logs.get(&def.id);
+        let is_focused = focused && idx == focus_idx;
+
+        let name_style = if is_focused {
+            theme::gold().add_modifier(Modifier::BOLD)
+        } else {
+            theme::bold()
+        };
+
+        let status_span = match &def.dhikr_type {
+            DhikrType::Checkbox => {
+                let done = log.map(|l| l.completed).unwrap_or(false);
+                if done {
+                    Span::styled("●", theme::green())
+                } else {
+                    Span::styled("○", theme::dim())
                 }
-            };
+            }
+            DhikrType::Counter => {
+                let count = log.map(|l| l.count).unwrap_or(0);
+                let target = def.target_count;
+                let done = count >= target;
+
+                // Build a small progress bar (6 chars wide)
+                let ratio = (count as f64 / target as f64).min(1.0);
+                let filled = (ratio * 5.0).round() as usize;
+                let empty = 5usize.saturating_sub(filled);
+                let bar = format!("{}{}", "▓".repeat(filled), "░".repeat(empty));
+
+                let color = if done { theme::green() } else { theme::amber() };
+                let text = format!("{} {}/{}", bar, count, target);
+                Span::styled(text, color)
+            }
+        };
 
-            let line = Line::from(vec![
-                Span::styled(format!("  {:<28}", def.name), name_style),
-                status_span,
-            ]);
+        let line = Line::from(vec![
+            Span::styled(format!("    {:<26}", def.name), name_style),
+            status_span,
+        ]);
 
-            ListItem::new(line)
-        })
-        .collect();
+        items.push(ListItem::new(line));
+    }
 
     let list = List::new(items).block(block);
     frame.render_widget(list, area);
@@ -0,0 +1,47 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+use crate::models::SunnahFast;
+use crate::tui::theme;
+
+/// A gentle nudge on days a voluntary fast is recommended, with whether
+/// one was kept. Mentions tomorrow's when today has none, for suhoor.
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    today: Option<SunnahFast>,
+    tomorrow: Option<SunnahFast>,
+    fasted: bool,
+) {
+    let block = Block::default()
+        .title(Span::styled(" Fasting ", theme::gold()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::border(false))
+        .style(theme::surface());
+
+    let status = if fasted {
+        Span::styled("  ● fasted", theme::green())
+    } else {
+        Span::styled("  ○ [f] to record", theme::dim())
+    };
+    let line = match (today, tomorrow) {
+        (Some(reason), _) => Line::from(vec![
+            Span::styled("  Sunnah fast recommended today ", theme::bold()),
+            Span::styled(format!("({})", reason.display_name()), theme::dim()),
+            status,
+        ]),
+        (None, _) if fasted => Line::from(vec![Span::styled("  Fasting today", theme::bold()), status]),
+        (None, Some(reason)) => Line::from(Span::styled(
+            format!("  Tomorrow: {} — a sunnah fast", reason.display_name()),
+            theme::dim(),
+        )),
+        (None, None) => Line::from(""),
+    };
+
+    frame.render_widget(Paragraph::new(vec![line]).block(block), area);
+}
@@ -1,4 +1,4 @@
-use chrono::Local;
+use chrono::{NaiveDate, NaiveTime};
 use ratatui::{
     layout::{Alignment, Rect},
     style::Modifier,
@@ -7,29 +7,132 @@ use ratatui::{
     Frame,
 };
 
+use crate::models::Eid;
 use crate::tui::theme;
 
-pub fn render(frame: &mut Frame, area: Rect, hijri_str: &str) {
-    let today = Local::now();
+/// What the header shows for the day on screen.
+pub struct HeaderInfo<'a> {
+    pub today: NaiveDate,
+    pub hijri_str: &'a str,
+    /// The Hijri date is shown without the moon-sighting offset
+    pub hijri_unadjusted: bool,
+    /// Fard prayers still pending
+    pub remaining: usize,
+    /// A past day is being reviewed
+    pub past: bool,
+    /// Today's Eid and whether its prayer was attended
+    pub eid: Option<(Eid, Option<bool>)>,
+    /// On Fridays, whether Jummah was attended and the weekly streak
+    pub jummah: Option<(Option<bool>, u32)>,
+    /// When Jummah starts, once it's time to leave for it
+    pub leave_for_jummah: Option<NaiveTime>,
+    pub profile: Option<&'a str>,
+}
+
+pub fn render(frame: &mut Frame, area: Rect, info: &HeaderInfo) {
+    let HeaderInfo {
+        today,
+        hijri_str,
+        hijri_unadjusted,
+        remaining,
+        past,
+        eid,
+        jummah,
+        leave_for_jummah,
+        profile,
+    } = *info;
     let gregorian_str = today.format("%A, %b %d, %Y").to_string();
 
-    let title_line = Line::from(vec![
+    let mut title_line = Line::from(vec![
         Span::styled("  سُجُود  ", theme::gold().add_modifier(Modifier::BOLD)),
         Span::styled("sujood", theme::gold()),
     ]);
+    if let Some(profile) = profile {
+        title_line.push_span(Span::styled("  ·  ", theme::dim()));
+        title_line.push_span(Span::styled(profile.to_string(), theme::dim()));
+    }
 
-    let date_line = Line::from(vec![
-        Span::styled(hijri_str, theme::amber()),
-        Span::styled("  ·  ", theme::dim()),
-        Span::styled(&gregorian_str, theme::dim()),
-    ]);
+    let remaining_span = if past {
+        Span::styled("viewing past day · [g] today", theme::amber().add_modifier(Modifier::BOLD))
+    } else if remaining == 0 {
+        Span::styled("all prayers done ✓", theme::green())
+    } else {
+        Span::styled(
+            format!("{} left today", remaining),
+            theme::amber().add_modifier(Modifier::BOLD),
+        )
+    };
+
+    // A reviewed day's date stands out so it isn't mistaken for today
+    let date_style = if past {
+        theme::amber().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+    } else {
+        theme::dim()
+    };
+
+    let mut date_line = Line::from(Span::styled(hijri_str, theme::amber()));
+    // The offset couldn't be applied, so this is the unadjusted Hijri date
+    if hijri_unadjusted {
+        date_line.push_span(Span::styled(" (no offset)", theme::dim()));
+    }
+    date_line.push_span(Span::styled("  ·  ", theme::dim()));
+    date_line.push_span(Span::styled(&gregorian_str, date_style));
+    date_line.push_span(Span::styled("  ·  ", theme::dim()));
+    date_line.push_span(remaining_span);
+
+    // On Eid the spacer line carries the Eid prayer tracker, on Fridays Jummah's
+    let event_line = match eid {
+        Some((eid, attended)) => {
+            let status = match attended {
+                Some(true) => Span::styled("prayer attended ✓", theme::green()),
+                Some(false) => Span::styled("prayer not attended · [e] mark", theme::dim()),
+                None => Span::styled("[e] mark Eid prayer attended", theme::amber()),
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{} Mubarak", eid.display_name()),
+                    theme::gold().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("  ·  ", theme::dim()),
+                status,
+            ])
+        }
+        None => match (jummah, leave_for_jummah) {
+            (Some(_), Some(starts)) => Line::from(vec![
+                Span::styled("Time to leave for Jummah", theme::amber().add_modifier(Modifier::BOLD)),
+                Span::styled("  ·  ", theme::dim()),
+                Span::styled(
+                    format!("starts {}", starts.format("%H:%M")),
+                    theme::amber(),
+                ),
+            ]),
+            (Some((attended, streak)), None) => {
+                let status = match attended {
+                    Some(true) => Span::styled("attended ✓", theme::green()),
+                    Some(false) => Span::styled("not attended · [j] mark", theme::dim()),
+                    None => Span::styled("[j] mark attended", theme::amber()),
+                };
+                let mut spans = vec![
+                    Span::styled("Jummah Mubarak", theme::gold().add_modifier(Modifier::BOLD)),
+                    Span::styled("  ·  ", theme::dim()),
+                    status,
+                ];
+                if streak > 0 {
+                    spans.push(Span::styled("  ·  ", theme::dim()));
+                    spans.push(Span::styled(format!("{}-week streak", streak), theme::dim()));
+                }
+                Line::from(spans)
+            }
+            (None, _) => Line::from(""),
+        },
+    };
 
-    let text = vec![title_line, Line::from(""), date_line];
+    let text = vec![title_line, event_line, date_line];
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(theme::gold().add_modifier(Modifier::BOLD))
+        .border_style(if past { theme::amber() } else { theme::gold() }.add_modifier(Modifier::BOLD))
         .style(theme::base());
 
     let paragraph = Paragraph::new(text)
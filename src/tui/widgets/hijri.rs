@@ -0,0 +1,82 @@
+use chrono::{Datelike, NaiveDate};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+use crate::tui::theme;
+use crate::utils::hijri::{HijriEvent, HijriMonth};
+
+/// Hijri month as a weekday grid of Hijri day numbers, with `today`
+/// highlighted and Eids, Arafah, Ashura and the white days marked.
+pub fn render(frame: &mut Frame, area: Rect, month: &HijriMonth, today: NaiveDate) {
+    let span = match (month.first_date(), month.last_date()) {
+        (Some(first), Some(last)) => format!("  {} – {}", first.format("%-d %b"), last.format("%-d %b %Y")),
+        _ => String::new(),
+    };
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::styled(format!(" {} {}", month.name(), month.year), theme::gold()),
+            Span::styled(format!("{} ", span), theme::dim()),
+        ]))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::border(false))
+        .style(theme::surface());
+
+    let palette = theme::palette();
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("   Mo   Tu   We   Th   Fr   Sa   Su", theme::dim())),
+    ];
+
+    let mut week = vec![Span::raw("  ")];
+    if let Some(first) = month.first_date() {
+        for _ in 0..first.weekday().num_days_from_monday() {
+            week.push(Span::raw("     "));
+        }
+    }
+    for day in &month.days {
+        let style = match day.event {
+            _ if day.date == today => {
+                Style::default().fg(palette.bg).bg(palette.gold).add_modifier(Modifier::BOLD)
+            }
+            Some(HijriEvent::Eid(_)) => {
+                Style::default().fg(palette.bg).bg(palette.green).add_modifier(Modifier::BOLD)
+            }
+            Some(HijriEvent::Arafah | HijriEvent::Ashura) => {
+                Style::default().fg(palette.bg).bg(palette.amber)
+            }
+            Some(HijriEvent::WhiteDay) => Style::default().fg(palette.text).bg(palette.fill),
+            None if month.month == 9 => theme::green(),
+            None => theme::bold(),
+        };
+        week.push(Span::styled(format!(" {:>2} ", day.day), style));
+        week.push(Span::raw(" "));
+        if day.date.weekday().num_days_from_monday() == 6 {
+            lines.push(Line::from(std::mem::replace(&mut week, vec![Span::raw("  ")])));
+            lines.push(Line::from(""));
+        }
+    }
+    if week.len() > 1 {
+        lines.push(Line::from(week));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("  ", theme::dim()),
+        Span::styled("  ", Style::default().bg(palette.gold)),
+        Span::styled(" today  ", theme::dim()),
+        Span::styled("  ", Style::default().bg(palette.green)),
+        Span::styled(" Eid  ", theme::dim()),
+        Span::styled("  ", Style::default().bg(palette.amber)),
+        Span::styled(" Arafah·Ashura  ", theme::dim()),
+        Span::styled("  ", Style::default().bg(palette.fill)),
+        Span::styled(" 13–15", theme::dim()),
+    ]));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -1,8 +1,13 @@
 pub mod adhkar;
+pub mod fasting;
 pub mod header;
+pub mod hijri;
+pub mod month;
+pub mod nafl;
 pub mod next_prayer;
 pub mod prayers;
 pub mod qada;
+pub mod qiyam;
 pub mod quran;
 pub mod statusbar;
 pub mod streak;
@@ -0,0 +1,85 @@
+use chrono::{Datelike, NaiveDate};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use std::collections::HashMap;
+
+use crate::models::DailyStats;
+use crate::tui::theme;
+use crate::utils::date::date_str;
+
+/// Calendar heatmap for the month starting at `first`, shaded by the share of
+/// prayers done (all five, or just the one a filter picked).
+/// Days after `today` are left blank; days with no rows show as a dot.
+pub fn render(frame: &mut Frame, area: Rect, first: NaiveDate, stats: &[DailyStats], today: NaiveDate) {
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" {} ", first.format("%B %Y")),
+            theme::gold(),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::border(false))
+        .style(theme::surface());
+
+    let by_date: HashMap<&str, &DailyStats> = stats.iter().map(|s| (s.date.as_str(), s)).collect();
+    let palette = theme::palette();
+    let days_in_month = first
+        .checked_add_months(chrono::Months::new(1))
+        .map(|next| (next - first).num_days() as u32)
+        .unwrap_or(30);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("   Mo   Tu   We   Th   Fr   Sa   Su", theme::dim())),
+    ];
+
+    // Pad the first week up to the month's first weekday
+    let mut week = vec![Span::raw("  ")];
+    for _ in 0..first.weekday().num_days_from_monday() {
+        week.push(Span::raw("     "));
+    }
+    for day in 1..=days_in_month {
+        let date = first.with_day(day).unwrap_or(first);
+        let style = if date > today {
+            theme::dim()
+        } else {
+            match by_date.get(date_str(date).as_str()).map(|s| s.level()) {
+                Some(5) => Style::default().fg(palette.bg).bg(palette.green).add_modifier(Modifier::BOLD),
+                Some(3 | 4) => Style::default().fg(palette.bg).bg(palette.amber),
+                Some(1 | 2) => Style::default().fg(palette.text).bg(palette.fill),
+                Some(_) => Style::default().fg(palette.text_dim).bg(palette.empty),
+                None => theme::dim(),
+            }
+        };
+        week.push(Span::styled(format!(" {:>2} ", day), style));
+        week.push(Span::raw(" "));
+        if date.weekday().num_days_from_monday() == 6 {
+            lines.push(Line::from(std::mem::replace(&mut week, vec![Span::raw("  ")])));
+            lines.push(Line::from(""));
+        }
+    }
+    if week.len() > 1 {
+        lines.push(Line::from(week));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("  ", theme::dim()),
+        Span::styled("  ", Style::default().bg(palette.green)),
+        Span::styled(" 5/5  ", theme::dim()),
+        Span::styled("  ", Style::default().bg(palette.amber)),
+        Span::styled(" 3–4  ", theme::dim()),
+        Span::styled("  ", Style::default().bg(palette.fill)),
+        Span::styled(" 1–2  ", theme::dim()),
+        Span::styled("  ", Style::default().bg(palette.empty)),
+        Span::styled(" none", theme::dim()),
+    ]));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, area);
+}
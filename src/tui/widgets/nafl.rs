@@ -0,0 +1,70 @@
+use ratatui::{
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem},
+    Frame,
+};
+
+use crate::models::Nafl;
+use crate::tui::theme;
+
+/// Checkbox per configured nafl prayer; `prayed` holds the ones recorded.
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    nafl: &[Nafl],
+    prayed: &[Nafl],
+    focused_idx: usize,
+    focused: bool,
+) {
+    let block = Block::default()
+        .title(Span::styled(" Nafl ", theme::gold()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::border(focused))
+        .style(theme::surface());
+
+    let items: Vec<ListItem> = nafl
+        .iter()
+        .enumerate()
+        .map(|(i, n)| {
+            let name_style = if focused && i == focused_idx {
+                theme::gold().add_modifier(Modifier::BOLD)
+            } else {
+                theme::bold()
+            };
+            let (icon, label, style) = if prayed.contains(n) {
+                ("●", "prayed", theme::green())
+            } else {
+                ("○", "", theme::dim())
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {:<10}", n.display_name()), name_style),
+                Span::styled(icon, style),
+                Span::styled(format!("  {}", label), theme::dim()),
+            ]))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn border_follows_focus() {
+        let corner_color = |focused: bool| {
+            let mut terminal = Terminal::new(TestBackend::new(30, 6)).unwrap();
+            terminal
+                .draw(|frame| render(frame, frame.area(), &[Nafl::Duha], &[], 0, focused))
+                .unwrap();
+            terminal.backend().buffer()[(0, 0)].fg
+        };
+        assert_eq!(corner_color(true), theme::palette().border_focus);
+        assert_eq!(corner_color(false), theme::palette().border);
+    }
+}
@@ -6,23 +6,28 @@ use ratatui::{
     Frame,
 };
 
+use crate::config::TimeFormat;
 use crate::models::PrayerType;
+use crate::prayer_times::calculator::ForbiddenWindow;
 use crate::tui::theme;
-use crate::utils::format::format_duration_secs;
+use crate::utils::format::{format_duration_secs, format_time_as};
 
 pub fn render(
     frame: &mut Frame,
     area: Rect,
     next_prayer: Option<&(PrayerType, i64)>,
+    remaining: Option<&[PrayerType]>,
+    forbidden: Option<&ForbiddenWindow>,
+    clock: TimeFormat,
 ) {
     let block = Block::default()
         .title(Span::styled(" Next Prayer ", theme::gold()))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(ratatui::style::Style::default().fg(crate::tui::theme::BORDER))
+        .border_style(theme::border(false))
         .style(theme::surface());
 
-    let content: Vec<Line> = match next_prayer {
+    let mut content: Vec<Line> = match next_prayer {
         None => vec![
             Line::from(""),
             Line::from(Span::styled("  No data", theme::dim())),
@@ -45,6 +50,32 @@ pub fn render(
         }
     };
 
+    match remaining {
+        Some([]) => {
+            content.push(Line::from(Span::styled("  all prayers marked ✓", theme::green())))
+        }
+        Some(prayers) => {
+            let names: Vec<&str> = prayers.iter().map(|p| p.display_name()).collect();
+            content.push(Line::from(Span::styled(
+                format!("  remaining today: {}", names.join(", ")),
+                theme::dim(),
+            )));
+        }
+        None => {}
+    }
+
+    if let Some(window) = forbidden {
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(
+            format!(
+                "  ⚠ makruh time until {} ({})",
+                format_time_as(window.end, clock),
+                window.label
+            ),
+            theme::amber(),
+        )));
+    }
+
     let paragraph = Paragraph::new(content)
         .block(block)
         .alignment(Alignment::Left);
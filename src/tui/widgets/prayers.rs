@@ -1,3 +1,4 @@
+use chrono::NaiveTime;
 use ratatui::{
     layout::Rect,
     style::Modifier,
@@ -6,10 +7,79 @@ use ratatui::{
     Frame,
 };
 
-use crate::models::{Prayer, PrayerStatus};
+use crate::config::TimeFormat;
+use crate::models::{Prayer, PrayerStatus, PrayerType};
 use crate::tui::theme;
+use crate::utils::format::{format_time_as, time_column_width};
 
-pub fn render(frame: &mut Frame, area: Rect, prayers: &[Prayer], focused_idx: usize, focused: bool) {
+/// A line of the prayer list: a prayer by its index, flagged when its time
+/// has wrapped past midnight, or one of the rows around Fajr that isn't a prayer.
+enum Row<'a> {
+    Imsak(NaiveTime),
+    Prayer(usize, &'a Prayer, bool),
+    Sunrise(NaiveTime),
+}
+
+fn rows<'a>(prayers: &'a [Prayer], imsak: Option<NaiveTime>, sunrise: Option<NaiveTime>) -> Vec<Row<'a>> {
+    let mut rows = Vec::new();
+    let mut previous = NaiveTime::MIN;
+    let mut past_midnight = false;
+    for (i, p) in prayers.iter().enumerate() {
+        let fajr = p.prayer_type == PrayerType::Fajr;
+        if let Some(imsak) = imsak.filter(|_| fajr) {
+            rows.push(Row::Imsak(imsak));
+        }
+        if let Some(time) = p.time {
+            past_midnight |= time < previous;
+            previous = time;
+        }
+        rows.push(Row::Prayer(i, p, past_midnight));
+        if let Some(sunrise) = sunrise.filter(|_| fajr) {
+            rows.push(Row::Sunrise(sunrise));
+        }
+    }
+    rows
+}
+
+/// The prayer drawn on line `row` of the list (0 is the first line inside
+/// the border), given the same `imsak` and `sunrise` as `render`.
+pub fn prayer_at(
+    prayers: &[Prayer],
+    imsak: Option<NaiveTime>,
+    sunrise: Option<NaiveTime>,
+    row: usize,
+) -> Option<usize> {
+    match rows(prayers, imsak, sunrise).get(row)? {
+        Row::Prayer(i, ..) => Some(*i),
+        _ => None,
+    }
+}
+
+/// Times shown around the five prayers without being prayers themselves.
+pub struct ExtraTimes<'a> {
+    /// Shown as a dim row above Fajr
+    pub imsak: Option<NaiveTime>,
+    /// Shown as a dim row below Fajr
+    pub sunrise: Option<NaiveTime>,
+    /// On Fridays, relabels the Zuhr row and shows its time instead
+    pub jummah: Option<NaiveTime>,
+    /// Asr by the other madhab, dimmed at the end of the Asr row
+    pub other_asr: Option<(&'a str, NaiveTime)>,
+}
+
+/// The imsak and sunrise rows of `extra` aren't prayers, so they can't be
+/// focused or marked. A prayer whose time falls after midnight says so.
+/// Times use the `clock` format.
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    prayers: &[Prayer],
+    extra: &ExtraTimes,
+    clock: TimeFormat,
+    focused_idx: usize,
+    focused: bool,
+) {
+    let ExtraTimes { imsak, sunrise, jummah, other_asr } = *extra;
     let block = Block::default()
         .title(Span::styled(
             " Prayers ",
@@ -17,52 +87,80 @@ pub fn render(frame: &mut Frame, area: Rect, prayers: &[Prayer], focused_idx: us
         ))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(if focused {
-            theme::gold()
-        } else {
-            ratatui::style::Style::default().fg(crate::tui::theme::BORDER)
-        })
+        .border_style(theme::border(focused))
         .style(theme::surface());
 
-    let items: Vec<ListItem> = prayers
-        .iter()
-        .enumerate()
-        .map(|(i, p)| {
-            let is_focused = focused && i == focused_idx;
+    let width = time_column_width(clock);
+    let mut items: Vec<ListItem> = Vec::new();
+    for row in rows(prayers, imsak, sunrise) {
+        let (i, p, past_midnight) = match row {
+            Row::Imsak(imsak) => {
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled(format!("  {:<8}", "Imsak"), theme::dim()),
+                    Span::styled(format!("{:<width$}", format_time_as(imsak, clock)), theme::dim()),
+                    Span::styled("☾", theme::dim()),
+                    Span::styled("  stop eating", theme::dim()),
+                ])));
+                continue;
+            }
+            Row::Sunrise(sunrise) => {
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled(format!("  {:<8}", "Sunrise"), theme::dim()),
+                    Span::styled(format!("{:<width$}", format_time_as(sunrise, clock)), theme::dim()),
+                    Span::styled("☼", theme::dim()),
+                    Span::styled("  Fajr ends", theme::dim()),
+                ])));
+                continue;
+            }
+            Row::Prayer(i, p, past_midnight) => (i, p, past_midnight),
+        };
+        let is_focused = focused && i == focused_idx;
 
-            let time_str = p
-                .time
-                .map(|t| t.format("%H:%M").to_string())
-                .unwrap_or_else(|| "--:--".to_string());
+        let (name, time) = match (&p.prayer_type, jummah) {
+            (PrayerType::Zuhr, Some(start)) => ("Jummah", Some(start)),
+            _ => (p.prayer_type.display_name(), p.time),
+        };
+        let time_str = time
+            .map(|t| format_time_as(t, clock))
+            .unwrap_or_else(|| "--:--".to_string());
 
-            let (icon, status_style) = match p.status {
-                PrayerStatus::Done => ("●", theme::green()),
-                PrayerStatus::Missed => ("✗", theme::red()),
-                PrayerStatus::Pending => ("○", theme::dim()),
-            };
+        let (icon, status_style) = match p.status {
+            PrayerStatus::Done => ("●", theme::green()),
+            PrayerStatus::Missed => ("✗", theme::red()),
+            PrayerStatus::Pending => ("○", theme::dim()),
+        };
 
-            let status_label = match p.status {
-                PrayerStatus::Done => "done",
-                PrayerStatus::Missed => "missed",
-                PrayerStatus::Pending => "upcoming",
-            };
+        let status_label = match p.status {
+            PrayerStatus::Done => "done",
+            PrayerStatus::Missed => "missed",
+            PrayerStatus::Pending => "upcoming",
+        };
 
-            let name_style = if is_focused {
-                theme::gold().add_modifier(Modifier::BOLD)
-            } else {
-                theme::bold()
-            };
+        let name_style = if is_focused {
+            theme::gold().add_modifier(Modifier::BOLD)
+        } else {
+            theme::bold()
+        };
 
-            let line = Line::from(vec![
-                Span::styled(format!("  {:<8}", p.prayer_type.display_name()), name_style),
-                Span::styled(format!("{:<7}", time_str), theme::dim()),
-                Span::styled(icon, status_style),
-                Span::styled(format!("  {}", status_label), theme::dim()),
-            ]);
+        let mut line = Line::from(vec![
+            Span::styled(format!("  {:<8}", name), name_style),
+            Span::styled(format!("{:<width$}", time_str), theme::dim()),
+            Span::styled(icon, status_style),
+            Span::styled(format!("  {:<9}", status_label), theme::dim()),
+            Span::styled(if p.note.is_some() { "✎" } else { " " }, theme::dim()),
+        ]);
+        if let (PrayerType::Asr, Some((madhab, asr))) = (&p.prayer_type, other_asr) {
+            line.push_span(Span::styled(
+                format!("  {} {}", madhab, format_time_as(asr, clock)),
+                theme::dim(),
+            ));
+        }
+        if past_midnight {
+            line.push_span(Span::styled("  after midnight", theme::dim()));
+        }
 
-            ListItem::new(line)
-        })
-        .collect();
+        items.push(ListItem::new(line));
+    }
 
     let list = List::new(items).block(block);
     frame.render_widget(list, area);
@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use ratatui::{
     layout::Rect,
     style::Modifier,
@@ -6,17 +7,18 @@ use ratatui::{
     Frame,
 };
 
+use crate::models::QadaPace;
 use crate::tui::theme;
 
-pub fn render(frame: &mut Frame, area: Rect, qada_count: i64) {
+pub fn render(frame: &mut Frame, area: Rect, pace: &QadaPace, today: NaiveDate) {
     let block = Block::default()
         .title(Span::styled(" Qada ", theme::gold()))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(ratatui::style::Style::default().fg(crate::tui::theme::BORDER))
+        .border_style(theme::border(false))
         .style(theme::surface());
 
-    let content = if qada_count == 0 {
+    let content = if pace.owed == 0 {
         vec![
             Line::from(""),
             Line::from(vec![
@@ -30,15 +32,13 @@ pub fn render(frame: &mut Frame, area: Rect, qada_count: i64) {
             Line::from(vec![
                 Span::styled("  ", theme::dim()),
                 Span::styled(
-                    format!("{} prayers owed", qada_count),
+                    format!("{} prayers owed", pace.owed),
                     theme::amber().add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(""),
-            Line::from(Span::styled(
-                format!("  ~{} days to clear", qada_count),
-                theme::dim(),
-            )),
+            Line::from(Span::styled(format!("  {}", pace.estimate(today)), theme::dim())),
+            Line::from(Span::styled(format!("  {}", pace.rate_label()), theme::dim())),
         ]
     };
 
@@ -0,0 +1,46 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+use crate::models::QiyamEntry;
+use crate::prayer_times::calculator::NightWindow;
+use crate::tui::theme;
+
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    entry: Option<&QiyamEntry>,
+    night: Option<&NightWindow>,
+    streak: u32,
+) {
+    let block = Block::default()
+        .title(Span::styled(" Qiyam ", theme::gold()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(theme::border(false))
+        .style(theme::surface());
+
+    let status = match entry {
+        Some(e) if e.in_last_third => Span::styled("prayed (in last third)", theme::green()),
+        Some(_) => Span::styled("prayed", theme::green()),
+        None => match night {
+            Some(n) => Span::styled(
+                format!("last third from {}", n.last_third.format("%H:%M")),
+                theme::dim(),
+            ),
+            None => Span::styled("not yet", theme::dim()),
+        },
+    };
+
+    let line = Line::from(vec![
+        Span::styled("  Qiyam: ", theme::dim()),
+        status,
+        Span::styled(format!("  ·  {} night streak", streak), theme::dim()),
+    ]);
+
+    let paragraph = Paragraph::new(vec![line]).block(block);
+    frame.render_widget(paragraph, area);
+}
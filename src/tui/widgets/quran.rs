@@ -5,28 +5,33 @@ use ratatui::{
     Frame,
 };
 
+use crate::models::{Khatm, KhatmPlan};
 use crate::tui::theme;
 use crate::utils::format::format_pages;
 
+/// `target` is the viewed day's page goal; `plan` replaces the finish
+/// projection when `quran.khatm_deadline` is set.
 pub fn render(
     frame: &mut Frame,
     area: Rect,
     today_pages: f64,
     weekly_pages: f64,
-    daily_target: f64,
+    target: f64,
+    khatm: &Khatm,
+    plan: Option<&KhatmPlan>,
 ) {
     let block = Block::default()
         .title(Span::styled(" Quran ", theme::gold()))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(ratatui::style::Style::default().fg(crate::tui::theme::BORDER))
+        .border_style(theme::border(false))
         .style(theme::surface());
 
     let inner_width = area.width.saturating_sub(4) as usize;
     let bar_width = inner_width.min(24);
 
-    let ratio = if daily_target > 0.0 {
-        (today_pages / daily_target).min(1.0)
+    let ratio = if target > 0.0 {
+        (today_pages / target).min(1.0)
     } else {
         0.0
     };
@@ -35,7 +40,7 @@ pub fn render(
 
     let bar = format!("{}{}", "▓".repeat(filled), "░".repeat(empty));
 
-    let progress_style = if today_pages >= daily_target {
+    let progress_style = if today_pages >= target {
         theme::green()
     } else {
         theme::amber()
@@ -48,13 +53,36 @@ pub fn render(
             format!(
                 "  {} / {} pages  ·  Week: {}",
                 format_pages(today_pages),
-                format_pages(daily_target),
+                format_pages(target),
                 format_pages(weekly_pages)
             ),
             theme::dim(),
         ),
     ]);
 
-    let paragraph = Paragraph::new(vec![Line::from(""), line]).block(block);
+    // With a deadline, say whether the recent pace makes it instead of projecting a finish
+    let khatm_line = match plan {
+        Some(plan) => {
+            let head = format!(
+                "  Khatm: {}/{} ({}%) · {}",
+                format_pages(khatm.pages),
+                khatm.mushaf_pages,
+                khatm.percent(),
+                plan.summary()
+            );
+            if plan.days_left == 0 {
+                Line::from(Span::styled(head, theme::red()))
+            } else {
+                let pace_style = if plan.on_pace { theme::green() } else { theme::amber() };
+                Line::from(vec![
+                    Span::styled(head, theme::dim()),
+                    Span::styled(format!(" · {}", plan.pace_label()), pace_style),
+                ])
+            }
+        }
+        None => Line::from(Span::styled(format!("  {}", khatm.summary()), theme::dim())),
+    };
+
+    let paragraph = Paragraph::new(vec![line, khatm_line]).block(block);
     frame.render_widget(paragraph, area);
 }
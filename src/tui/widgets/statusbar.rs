@@ -5,27 +5,61 @@ use ratatui::{
     Frame,
 };
 
+use crate::tui::keymap::{Action, Keymap};
 use crate::tui::theme;
 
-pub fn render(frame: &mut Frame, area: Rect) {
-    let hints = vec![
-        ("[m]", " mark  "),
-        ("[M]", " missed  "),
-        ("[q]", " qada  "),
-        ("[d]", " dhikr  "),
-        ("[r]", " quran  "),
-        ("[s]", " stats  "),
-        ("[?]", " help  "),
-        ("[Esc]", " quit"),
-    ];
+/// Each hint's key, label, and the action a click on it runs.
+fn hints(keymap: &Keymap) -> Vec<(String, &'static str, Option<Action>)> {
+    let bound = |action: Action, label| (keymap.hint(action), label, Some(action));
+    vec![
+        bound(Action::Mark, " mark  "),
+        bound(Action::Missed, " missed  "),
+        bound(Action::Qada, " qada  "),
+        bound(Action::Dhikr, " dhikr  "),
+        bound(Action::Quran, " quran  "),
+        ("[t]".to_string(), " qiyam  ", None),
+        ("[ ]".to_string(), " day  ", None),
+        bound(Action::Stats, " stats  "),
+        bound(Action::Help, " help  "),
+        ("[Esc]".to_string(), " quit", None),
+    ]
+}
+
+/// The action of the hint drawn at `column`, for a bar rendered in `area`.
+pub fn action_at(keymap: &Keymap, area: Rect, column: u16) -> Option<Action> {
+    let hints = hints(keymap);
+    let width: usize = hints.iter().map(|(key, label, _)| key.chars().count() + label.chars().count()).sum();
+    // Centred like the paragraph; a bar wider than the area is cut evenly on both sides
+    let mut x = area.x as i32 + (area.width as i32 - width as i32) / 2;
+    for (key, label, action) in hints {
+        let end = x + (key.chars().count() + label.chars().count()) as i32;
+        if (x..end).contains(&(column as i32)) {
+            return action;
+        }
+        x = end;
+    }
+    None
+}
 
+pub fn render(frame: &mut Frame, area: Rect, keymap: &Keymap) {
     let mut spans = Vec::new();
-    for (key, label) in &hints {
-        spans.push(Span::styled(*key, theme::gold()));
-        spans.push(Span::styled(*label, theme::dim()));
+    for (key, label, _) in hints(keymap) {
+        spans.push(Span::styled(key, theme::gold()));
+        spans.push(Span::styled(label, theme::dim()));
     }
 
     let line = Line::from(spans);
     let paragraph = Paragraph::new(line).alignment(Alignment::Center);
     frame.render_widget(paragraph, area);
 }
+
+/// Shown in place of the hints while quitting waits for a second Esc.
+pub fn render_confirm_quit(frame: &mut Frame, area: Rect) {
+    let line = Line::from(vec![
+        Span::styled("Press ", theme::dim()),
+        Span::styled("[Esc]", theme::amber()),
+        Span::styled(" again to quit  ·  any key to cancel", theme::dim()),
+    ]);
+    let paragraph = Paragraph::new(line).alignment(Alignment::Center);
+    frame.render_widget(paragraph, area);
+}
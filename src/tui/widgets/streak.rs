@@ -6,20 +6,39 @@ use ratatui::{
     Frame,
 };
 
-use crate::models::{DailyStats, Streak};
+use crate::models::{DailyStats, Goal, Streak};
 use crate::tui::theme;
+use crate::utils::format::progress_bar;
+
+/// A `[goals]` target's bar and count, celebrated once it's met.
+pub fn goal_spans(goal: &Goal) -> Vec<Span<'static>> {
+    let bar = progress_bar(goal.done, goal.target, 12);
+    let count = format!("  {}/{} this {}", goal.done, goal.target, goal.range.as_str());
+    if goal.met() {
+        vec![
+            Span::styled(bar, theme::gold()),
+            Span::styled(
+                format!("{}  ✓ goal met", count),
+                theme::gold().add_modifier(Modifier::BOLD),
+            ),
+        ]
+    } else {
+        vec![Span::styled(bar, theme::green()), Span::styled(count, theme::dim())]
+    }
+}
 
 pub fn render(
     frame: &mut Frame,
     area: Rect,
     streak: &Streak,
     weekly: &[DailyStats],
+    goal: Option<&Goal>,
 ) {
     let block = Block::default()
         .title(Span::styled(" Streak ", theme::gold()))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(ratatui::style::Style::default().fg(crate::tui::theme::BORDER))
+        .border_style(theme::border(false))
         .style(theme::surface());
 
     // Weekly dots
@@ -69,7 +88,12 @@ pub fn render(
         ),
     ]);
 
-    let text = vec![Line::from(""), streak_line, Line::from(""), meta_line];
+    let mut text = vec![Line::from(""), streak_line, Line::from(""), meta_line];
+    if let Some(goal) = goal {
+        let mut spans = vec![Span::styled("  ", theme::dim())];
+        spans.extend(goal_spans(goal));
+        text.push(Line::from(spans));
+    }
     let paragraph = Paragraph::new(text).block(block);
     frame.render_widget(paragraph, area);
 }
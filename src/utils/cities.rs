@@ -0,0 +1,283 @@
+/// A city in the bundled table, enough to place prayer times without
+/// looking up coordinates by hand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct City {
+    pub name: &'static str,
+    /// ISO 3166-1 alpha-2 code
+    pub country: &'static str,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Most matches `search` returns, so the wizard list stays short.
+pub const MAX_MATCHES: usize = 8;
+
+/// Offline table of cities with sizeable Muslim communities, plus other
+/// major cities. Names may repeat across countries (Hyderabad, Tripoli).
+pub const CITIES: &[City] = &[
+    City { name: "Mecca", country: "SA", latitude: 21.3891, longitude: 39.8579 },
+    City { name: "Medina", country: "SA", latitude: 24.5247, longitude: 39.5692 },
+    City { name: "Riyadh", country: "SA", latitude: 24.7136, longitude: 46.6753 },
+    City { name: "Jeddah", country: "SA", latitude: 21.4858, longitude: 39.1925 },
+    City { name: "Dammam", country: "SA", latitude: 26.4207, longitude: 50.0888 },
+    City { name: "Dubai", country: "AE", latitude: 25.2048, longitude: 55.2708 },
+    City { name: "Abu Dhabi", country: "AE", latitude: 24.4539, longitude: 54.3773 },
+    City { name: "Sharjah", country: "AE", latitude: 25.3463, longitude: 55.4209 },
+    City { name: "Doha", country: "QA", latitude: 25.2854, longitude: 51.5310 },
+    City { name: "Manama", country: "BH", latitude: 26.2285, longitude: 50.5860 },
+    City { name: "Kuwait City", country: "KW", latitude: 29.3759, longitude: 47.9774 },
+    City { name: "Muscat", country: "OM", latitude: 23.5880, longitude: 58.3829 },
+    City { name: "Sanaa", country: "YE", latitude: 15.3694, longitude: 44.1910 },
+    City { name: "Aden", country: "YE", latitude: 12.7855, longitude: 45.0187 },
+    City { name: "Amman", country: "JO", latitude: 31.9454, longitude: 35.9284 },
+    City { name: "Jerusalem", country: "PS", latitude: 31.7683, longitude: 35.2137 },
+    City { name: "Gaza", country: "PS", latitude: 31.5017, longitude: 34.4668 },
+    City { name: "Beirut", country: "LB", latitude: 33.8938, longitude: 35.5018 },
+    City { name: "Tripoli", country: "LB", latitude: 34.4367, longitude: 35.8497 },
+    City { name: "Damascus", country: "SY", latitude: 33.5138, longitude: 36.2765 },
+    City { name: "Aleppo", country: "SY", latitude: 36.2021, longitude: 37.1343 },
+    City { name: "Baghdad", country: "IQ", latitude: 33.3152, longitude: 44.3661 },
+    City { name: "Basra", country: "IQ", latitude: 30.5085, longitude: 47.7804 },
+    City { name: "Erbil", country: "IQ", latitude: 36.1911, longitude: 44.0092 },
+    City { name: "Najaf", country: "IQ", latitude: 32.0000, longitude: 44.3350 },
+    City { name: "Tehran", country: "IR", latitude: 35.6892, longitude: 51.3890 },
+    City { name: "Mashhad", country: "IR", latitude: 36.2605, longitude: 59.6168 },
+    City { name: "Isfahan", country: "IR", latitude: 32.6546, longitude: 51.6680 },
+    City { name: "Istanbul", country: "TR", latitude: 41.0082, longitude: 28.9784 },
+    City { name: "Ankara", country: "TR", latitude: 39.9334, longitude: 32.8597 },
+    City { name: "Izmir", country: "TR", latitude: 38.4237, longitude: 27.1428 },
+    City { name: "Bursa", country: "TR", latitude: 40.1885, longitude: 29.0610 },
+    City { name: "Konya", country: "TR", latitude: 37.8746, longitude: 32.4932 },
+    City { name: "Cairo", country: "EG", latitude: 30.0444, longitude: 31.2357 },
+    City { name: "Alexandria", country: "EG", latitude: 31.2001, longitude: 29.9187 },
+    City { name: "Khartoum", country: "SD", latitude: 15.5007, longitude: 32.5599 },
+    City { name: "Tripoli", country: "LY", latitude: 32.8872, longitude: 13.1913 },
+    City { name: "Benghazi", country: "LY", latitude: 32.1167, longitude: 20.0667 },
+    City { name: "Tunis", country: "TN", latitude: 36.8065, longitude: 10.1815 },
+    City { name: "Algiers", country: "DZ", latitude: 36.7538, longitude: 3.0588 },
+    City { name: "Oran", country: "DZ", latitude: 35.6971, longitude: -0.6308 },
+    City { name: "Casablanca", country: "MA", latitude: 33.5731, longitude: -7.5898 },
+    City { name: "Rabat", country: "MA", latitude: 34.0209, longitude: -6.8416 },
+    City { name: "Marrakesh", country: "MA", latitude: 31.6295, longitude: -7.9811 },
+    City { name: "Fez", country: "MA", latitude: 34.0181, longitude: -5.0078 },
+    City { name: "Nouakchott", country: "MR", latitude: 18.0735, longitude: -15.9582 },
+    City { name: "Dakar", country: "SN", latitude: 14.7167, longitude: -17.4677 },
+    City { name: "Bamako", country: "ML", latitude: 12.6392, longitude: -8.0029 },
+    City { name: "Niamey", country: "NE", latitude: 13.5116, longitude: 2.1254 },
+    City { name: "Kano", country: "NG", latitude: 12.0022, longitude: 8.5920 },
+    City { name: "Lagos", country: "NG", latitude: 6.5244, longitude: 3.3792 },
+    City { name: "Abuja", country: "NG", latitude: 9.0765, longitude: 7.3986 },
+    City { name: "Accra", country: "GH", latitude: 5.6037, longitude: -0.1870 },
+    City { name: "Mogadishu", country: "SO", latitude: 2.0469, longitude: 45.3182 },
+    City { name: "Djibouti", country: "DJ", latitude: 11.5721, longitude: 43.1456 },
+    City { name: "Addis Ababa", country: "ET", latitude: 9.0300, longitude: 38.7400 },
+    City { name: "Nairobi", country: "KE", latitude: -1.2921, longitude: 36.8219 },
+    City { name: "Mombasa", country: "KE", latitude: -4.0435, longitude: 39.6682 },
+    City { name: "Dar es Salaam", country: "TZ", latitude: -6.7924, longitude: 39.2083 },
+    City { name: "Zanzibar", country: "TZ", latitude: -6.1659, longitude: 39.2026 },
+    City { name: "Kampala", country: "UG", latitude: 0.3476, longitude: 32.5825 },
+    City { name: "Johannesburg", country: "ZA", latitude: -26.2041, longitude: 28.0473 },
+    City { name: "Cape Town", country: "ZA", latitude: -33.9249, longitude: 18.4241 },
+    City { name: "Durban", country: "ZA", latitude: -29.8587, longitude: 31.0218 },
+    City { name: "Karachi", country: "PK", latitude: 24.8607, longitude: 67.0011 },
+    City { name: "Lahore", country: "PK", latitude: 31.5204, longitude: 74.3587 },
+    City { name: "Islamabad", country: "PK", latitude: 33.6844, longitude: 73.0479 },
+    City { name: "Rawalpindi", country: "PK", latitude: 33.5651, longitude: 73.0169 },
+    City { name: "Faisalabad", country: "PK", latitude: 31.4504, longitude: 73.1350 },
+    City { name: "Peshawar", country: "PK", latitude: 34.0151, longitude: 71.5249 },
+    City { name: "Multan", country: "PK", latitude: 30.1575, longitude: 71.5249 },
+    City { name: "Hyderabad", country: "PK", latitude: 25.3960, longitude: 68.3578 },
+    City { name: "Quetta", country: "PK", latitude: 30.1798, longitude: 66.9750 },
+    City { name: "Kabul", country: "AF", latitude: 34.5553, longitude: 69.2075 },
+    City { name: "Kandahar", country: "AF", latitude: 31.6289, longitude: 65.7372 },
+    City { name: "Herat", country: "AF", latitude: 34.3529, longitude: 62.2040 },
+    City { name: "Delhi", country: "IN", latitude: 28.7041, longitude: 77.1025 },
+    City { name: "Mumbai", country: "IN", latitude: 19.0760, longitude: 72.8777 },
+    City { name: "Hyderabad", country: "IN", latitude: 17.3850, longitude: 78.4867 },
+    City { name: "Bangalore", country: "IN", latitude: 12.9716, longitude: 77.5946 },
+    City { name: "Chennai", country: "IN", latitude: 13.0827, longitude: 80.2707 },
+    City { name: "Kolkata", country: "IN", latitude: 22.5726, longitude: 88.3639 },
+    City { name: "Lucknow", country: "IN", latitude: 26.8467, longitude: 80.9462 },
+    City { name: "Ahmedabad", country: "IN", latitude: 23.0225, longitude: 72.5714 },
+    City { name: "Srinagar", country: "IN", latitude: 34.0837, longitude: 74.7973 },
+    City { name: "Dhaka", country: "BD", latitude: 23.8103, longitude: 90.4125 },
+    City { name: "Chittagong", country: "BD", latitude: 22.3569, longitude: 91.7832 },
+    City { name: "Sylhet", country: "BD", latitude: 24.8949, longitude: 91.8687 },
+    City { name: "Colombo", country: "LK", latitude: 6.9271, longitude: 79.8612 },
+    City { name: "Malé", country: "MV", latitude: 4.1755, longitude: 73.5093 },
+    City { name: "Kathmandu", country: "NP", latitude: 27.7172, longitude: 85.3240 },
+    City { name: "Tashkent", country: "UZ", latitude: 41.2995, longitude: 69.2401 },
+    City { name: "Samarkand", country: "UZ", latitude: 39.6270, longitude: 66.9750 },
+    City { name: "Bukhara", country: "UZ", latitude: 39.7681, longitude: 64.4556 },
+    City { name: "Almaty", country: "KZ", latitude: 43.2220, longitude: 76.8512 },
+    City { name: "Astana", country: "KZ", latitude: 51.1694, longitude: 71.4491 },
+    City { name: "Bishkek", country: "KG", latitude: 42.8746, longitude: 74.5698 },
+    City { name: "Dushanbe", country: "TJ", latitude: 38.5598, longitude: 68.7870 },
+    City { name: "Ashgabat", country: "TM", latitude: 37.9601, longitude: 58.3261 },
+    City { name: "Baku", country: "AZ", latitude: 40.4093, longitude: 49.8671 },
+    City { name: "Kazan", country: "RU", latitude: 55.7963, longitude: 49.1088 },
+    City { name: "Grozny", country: "RU", latitude: 43.3178, longitude: 45.6949 },
+    City { name: "Makhachkala", country: "RU", latitude: 42.9849, longitude: 47.5047 },
+    City { name: "Moscow", country: "RU", latitude: 55.7558, longitude: 37.6173 },
+    City { name: "Jakarta", country: "ID", latitude: -6.2088, longitude: 106.8456 },
+    City { name: "Surabaya", country: "ID", latitude: -7.2575, longitude: 112.7521 },
+    City { name: "Bandung", country: "ID", latitude: -6.9175, longitude: 107.6191 },
+    City { name: "Medan", country: "ID", latitude: 3.5952, longitude: 98.6722 },
+    City { name: "Banda Aceh", country: "ID", latitude: 5.5483, longitude: 95.3238 },
+    City { name: "Makassar", country: "ID", latitude: -5.1477, longitude: 119.4327 },
+    City { name: "Yogyakarta", country: "ID", latitude: -7.7956, longitude: 110.3695 },
+    City { name: "Kuala Lumpur", country: "MY", latitude: 3.1390, longitude: 101.6869 },
+    City { name: "George Town", country: "MY", latitude: 5.4141, longitude: 100.3288 },
+    City { name: "Johor Bahru", country: "MY", latitude: 1.4927, longitude: 103.7414 },
+    City { name: "Kota Bharu", country: "MY", latitude: 6.1254, longitude: 102.2381 },
+    City { name: "Singapore", country: "SG", latitude: 1.3521, longitude: 103.8198 },
+    City { name: "Bandar Seri Begawan", country: "BN", latitude: 4.9031, longitude: 114.9398 },
+    City { name: "Manila", country: "PH", latitude: 14.5995, longitude: 120.9842 },
+    City { name: "Cotabato", country: "PH", latitude: 7.2047, longitude: 124.2310 },
+    City { name: "Bangkok", country: "TH", latitude: 13.7563, longitude: 100.5018 },
+    City { name: "Beijing", country: "CN", latitude: 39.9042, longitude: 116.4074 },
+    City { name: "Urumqi", country: "CN", latitude: 43.8256, longitude: 87.6168 },
+    City { name: "Hong Kong", country: "HK", latitude: 22.3193, longitude: 114.1694 },
+    City { name: "Tokyo", country: "JP", latitude: 35.6762, longitude: 139.6503 },
+    City { name: "Seoul", country: "KR", latitude: 37.5665, longitude: 126.9780 },
+    City { name: "Sydney", country: "AU", latitude: -33.8688, longitude: 151.2093 },
+    City { name: "Melbourne", country: "AU", latitude: -37.8136, longitude: 144.9631 },
+    City { name: "Perth", country: "AU", latitude: -31.9505, longitude: 115.8605 },
+    City { name: "Auckland", country: "NZ", latitude: -36.8485, longitude: 174.7633 },
+    City { name: "London", country: "GB", latitude: 51.5074, longitude: -0.1278 },
+    City { name: "Birmingham", country: "GB", latitude: 52.4862, longitude: -1.8904 },
+    City { name: "Manchester", country: "GB", latitude: 53.4808, longitude: -2.2426 },
+    City { name: "Bradford", country: "GB", latitude: 53.7960, longitude: -1.7594 },
+    City { name: "Leicester", country: "GB", latitude: 52.6369, longitude: -1.1398 },
+    City { name: "Glasgow", country: "GB", latitude: 55.8642, longitude: -4.2518 },
+    City { name: "Dublin", country: "IE", latitude: 53.3498, longitude: -6.2603 },
+    City { name: "Paris", country: "FR", latitude: 48.8566, longitude: 2.3522 },
+    City { name: "Marseille", country: "FR", latitude: 43.2965, longitude: 5.3698 },
+    City { name: "Lyon", country: "FR", latitude: 45.7640, longitude: 4.8357 },
+    City { name: "Brussels", country: "BE", latitude: 50.8503, longitude: 4.3517 },
+    City { name: "Amsterdam", country: "NL", latitude: 52.3676, longitude: 4.9041 },
+    City { name: "Rotterdam", country: "NL", latitude: 51.9244, longitude: 4.4777 },
+    City { name: "Berlin", country: "DE", latitude: 52.5200, longitude: 13.4050 },
+    City { name: "Hamburg", country: "DE", latitude: 53.5511, longitude: 9.9937 },
+    City { name: "Frankfurt", country: "DE", latitude: 50.1109, longitude: 8.6821 },
+    City { name: "Cologne", country: "DE", latitude: 50.9375, longitude: 6.9603 },
+    City { name: "Munich", country: "DE", latitude: 48.1351, longitude: 11.5820 },
+    City { name: "Vienna", country: "AT", latitude: 48.2082, longitude: 16.3738 },
+    City { name: "Zurich", country: "CH", latitude: 47.3769, longitude: 8.5417 },
+    City { name: "Copenhagen", country: "DK", latitude: 55.6761, longitude: 12.5683 },
+    City { name: "Stockholm", country: "SE", latitude: 59.3293, longitude: 18.0686 },
+    City { name: "Oslo", country: "NO", latitude: 59.9139, longitude: 10.7522 },
+    City { name: "Helsinki", country: "FI", latitude: 60.1699, longitude: 24.9384 },
+    City { name: "Madrid", country: "ES", latitude: 40.4168, longitude: -3.7038 },
+    City { name: "Barcelona", country: "ES", latitude: 41.3851, longitude: 2.1734 },
+    City { name: "Granada", country: "ES", latitude: 37.1773, longitude: -3.5986 },
+    City { name: "Lisbon", country: "PT", latitude: 38.7223, longitude: -9.1393 },
+    City { name: "Rome", country: "IT", latitude: 41.9028, longitude: 12.4964 },
+    City { name: "Milan", country: "IT", latitude: 45.4642, longitude: 9.1900 },
+    City { name: "Athens", country: "GR", latitude: 37.9838, longitude: 23.7275 },
+    City { name: "Sarajevo", country: "BA", latitude: 43.8563, longitude: 18.4131 },
+    City { name: "Tirana", country: "AL", latitude: 41.3275, longitude: 19.8187 },
+    City { name: "Pristina", country: "XK", latitude: 42.6629, longitude: 21.1655 },
+    City { name: "Skopje", country: "MK", latitude: 41.9981, longitude: 21.4254 },
+    City { name: "Sofia", country: "BG", latitude: 42.6977, longitude: 23.3219 },
+    City { name: "Warsaw", country: "PL", latitude: 52.2297, longitude: 21.0122 },
+    City { name: "New York", country: "US", latitude: 40.7128, longitude: -74.0060 },
+    City { name: "Chicago", country: "US", latitude: 41.8781, longitude: -87.6298 },
+    City { name: "Los Angeles", country: "US", latitude: 34.0522, longitude: -118.2437 },
+    City { name: "Houston", country: "US", latitude: 29.7604, longitude: -95.3698 },
+    City { name: "Dallas", country: "US", latitude: 32.7767, longitude: -96.7970 },
+    City { name: "Dearborn", country: "US", latitude: 42.3223, longitude: -83.1763 },
+    City { name: "Washington", country: "US", latitude: 38.9072, longitude: -77.0369 },
+    City { name: "Philadelphia", country: "US", latitude: 39.9526, longitude: -75.1652 },
+    City { name: "Atlanta", country: "US", latitude: 33.7490, longitude: -84.3880 },
+    City { name: "San Francisco", country: "US", latitude: 37.7749, longitude: -122.4194 },
+    City { name: "Seattle", country: "US", latitude: 47.6062, longitude: -122.3321 },
+    City { name: "Minneapolis", country: "US", latitude: 44.9778, longitude: -93.2650 },
+    City { name: "Toronto", country: "CA", latitude: 43.6532, longitude: -79.3832 },
+    City { name: "Mississauga", country: "CA", latitude: 43.5890, longitude: -79.6441 },
+    City { name: "Montreal", country: "CA", latitude: 45.5017, longitude: -73.5673 },
+    City { name: "Ottawa", country: "CA", latitude: 45.4215, longitude: -75.6972 },
+    City { name: "Vancouver", country: "CA", latitude: 49.2827, longitude: -123.1207 },
+    City { name: "Calgary", country: "CA", latitude: 51.0447, longitude: -114.0719 },
+    City { name: "Edmonton", country: "CA", latitude: 53.5461, longitude: -113.4938 },
+    City { name: "Mexico City", country: "MX", latitude: 19.4326, longitude: -99.1332 },
+    City { name: "São Paulo", country: "BR", latitude: -23.5505, longitude: -46.6333 },
+    City { name: "Buenos Aires", country: "AR", latitude: -34.6037, longitude: -58.3816 },
+];
+
+/// Other spellings people type, mapped to the name used in `CITIES`.
+const ALIASES: &[(&str, &str)] = &[
+    ("Makkah", "Mecca"),
+    ("Madinah", "Medina"),
+    ("Al-Quds", "Jerusalem"),
+    ("Bombay", "Mumbai"),
+    ("Calcutta", "Kolkata"),
+    ("Madras", "Chennai"),
+    ("Bengaluru", "Bangalore"),
+    ("New Delhi", "Delhi"),
+    ("Dacca", "Dhaka"),
+    ("Chattogram", "Chittagong"),
+    ("Kuwait", "Kuwait City"),
+    ("Penang", "George Town"),
+    ("Sana'a", "Sanaa"),
+    ("Marrakech", "Marrakesh"),
+    ("Fes", "Fez"),
+    ("Nur-Sultan", "Astana"),
+    ("Washington DC", "Washington"),
+    ("NYC", "New York"),
+    ("Köln", "Cologne"),
+    ("München", "Munich"),
+    ("Wien", "Vienna"),
+    ("Roma", "Rome"),
+    ("Lisboa", "Lisbon"),
+    ("Constantinople", "Istanbul"),
+];
+
+/// Cities matching `query` by name or common alternate spelling, ignoring
+/// case and accents: exact matches first, then names starting with it.
+pub fn search(query: &str) -> Vec<&'static City> {
+    let wanted = fold(query.trim());
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let canonical: Vec<&str> = ALIASES
+        .iter()
+        .filter(|(alias, _)| fold(alias) == wanted)
+        .map(|(_, name)| *name)
+        .collect();
+
+    let mut exact = Vec::new();
+    let mut prefix = Vec::new();
+    for city in CITIES {
+        let name = fold(city.name);
+        if name == wanted || canonical.contains(&city.name) {
+            exact.push(city);
+        } else if wanted.len() >= 3 && name.starts_with(&wanted) {
+            prefix.push(city);
+        }
+    }
+    prefix.sort_by_key(|c| c.name);
+    exact.extend(prefix);
+    exact.truncate(MAX_MATCHES);
+    exact
+}
+
+/// Lowercase with the common Latin accents stripped, so "Sao Paulo" finds
+/// "São Paulo" and "male" finds "Malé".
+fn fold(s: &str) -> String {
+    s.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            '\'' | '’' => '\0',
+            c => c,
+        })
+        .filter(|c| *c != '\0')
+        .collect()
+}
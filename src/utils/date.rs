@@ -0,0 +1,197 @@
+use anyhow::{anyhow, Result};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc,
+};
+
+use crate::config::AppConfig;
+
+/// Storage format for dates in the database and cache.
+pub const DATE_FMT: &str = "%Y-%m-%d";
+/// Storage format for local timestamps.
+pub const DATETIME_FMT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Current wall-clock time in the configured timezone.
+/// Prayer times are computed in this offset, so countdowns must compare against it.
+pub fn now_local(config: &AppConfig) -> NaiveDateTime {
+    local_at(config, Utc::now())
+}
+
+/// `instant` on the configured timezone's wall clock.
+pub fn local_at(config: &AppConfig, instant: DateTime<Utc>) -> NaiveDateTime {
+    let offset = FixedOffset::east_opt(config.salah.timezone_offset * 60)
+        .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+    instant.with_timezone(&offset).naive_local()
+}
+
+/// The tracking day a given moment belongs to.
+/// Before `rollover_hour`, the moment still counts toward the previous day.
+pub fn logical_date(now: NaiveDateTime, rollover_hour: u32) -> NaiveDate {
+    if now.hour() < rollover_hour.min(23) {
+        now.date() - Duration::days(1)
+    } else {
+        now.date()
+    }
+}
+
+/// The logical "today" that prayer rows, dhikr logs and Quran logs are recorded against.
+pub fn today_date(config: &AppConfig) -> NaiveDate {
+    today_date_at(config, Utc::now())
+}
+
+/// `today_date` formatted for storage.
+pub fn today_str(config: &AppConfig) -> String {
+    today_str_at(config, Utc::now())
+}
+
+/// The logical today as of `instant`, under the config's offset and rollover.
+pub fn today_date_at(config: &AppConfig, instant: DateTime<Utc>) -> NaiveDate {
+    logical_date(local_at(config, instant), config.day.rollover_hour)
+}
+
+/// `today_date_at` formatted for storage.
+pub fn today_str_at(config: &AppConfig, instant: DateTime<Utc>) -> String {
+    date_str(today_date_at(config, instant))
+}
+
+pub fn date_str(date: NaiveDate) -> String {
+    date.format(DATE_FMT).to_string()
+}
+
+/// Put a day's times, given in the order they happen, on the dates they
+/// fall. A time earlier than the one before it has wrapped past midnight,
+/// as Isha can at high latitudes in summer.
+pub fn place_in_order(date: NaiveDate, times: &[NaiveTime]) -> Vec<NaiveDateTime> {
+    let mut day = date;
+    let mut previous = NaiveTime::MIN;
+    times
+        .iter()
+        .map(|&time| {
+            if time < previous {
+                day = day.succ_opt().unwrap_or(day);
+            }
+            previous = time;
+            day.and_time(time)
+        })
+        .collect()
+}
+
+/// The Saturday that starts the week containing `date`.
+/// Weeks run Saturday–Friday so that Jumu'ah closes them.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    let since_saturday = (date.weekday().num_days_from_sunday() + 1) % 7;
+    date - Duration::days(since_saturday as i64)
+}
+
+/// The most recent Friday on or before `date`.
+pub fn last_friday(date: NaiveDate) -> NaiveDate {
+    let since_friday = (date.weekday().num_days_from_monday() + 3) % 7;
+    date - Duration::days(since_friday as i64)
+}
+
+/// When Jummah starts on `date`, if it's a Friday and Jummah is on: the
+/// configured `jummah_time`, or else the computed Zuhr.
+pub fn jummah_start(config: &AppConfig, date: NaiveDate, zuhr: NaiveTime) -> Option<NaiveTime> {
+    (config.jummah.enabled && last_friday(date) == date).then(|| {
+        config
+            .salah
+            .jummah_time
+            .as_deref()
+            .and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok())
+            .unwrap_or(zuhr)
+    })
+}
+
+/// When to leave for Jummah on `date`, if it's a Friday and the reminder is on.
+pub fn jummah_leave_at(config: &AppConfig, date: NaiveDate, zuhr: NaiveTime) -> Option<NaiveTime> {
+    let minutes = config.jummah.leave_early_minutes;
+    let start = jummah_start(config, date, zuhr)?;
+    (minutes > 0).then(|| start - Duration::minutes(minutes as i64))
+}
+
+/// When a tracking day counts as closed, from `[day] close_at`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DayCutoff {
+    /// Minutes after the day's Isha time
+    AfterIsha(i64),
+    /// A fixed clock time
+    At(NaiveTime),
+}
+
+impl DayCutoff {
+    /// Accepts `isha`, `isha+60` or a clock time like `23:30`.
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        if let Some(rest) = s.strip_prefix("isha") {
+            let rest = rest.trim();
+            if rest.is_empty() {
+                return Ok(Self::AfterIsha(0));
+            }
+            let minutes = rest
+                .strip_prefix('+')
+                .and_then(|m| m.trim().parse::<i64>().ok())
+                .ok_or_else(|| anyhow!("Invalid close_at '{}'. Use e.g. \"isha+60\"", s))?;
+            return Ok(Self::AfterIsha(minutes));
+        }
+        NaiveTime::parse_from_str(&s, "%H:%M")
+            .map(Self::At)
+            .map_err(|_| anyhow!("Invalid close_at '{}'. Use \"isha+MINUTES\" or \"HH:MM\"", s))
+    }
+
+    /// The moment `day` closes, given when its Isha comes in. Clock times
+    /// before `rollover_hour` still belong to `day`, so they fall on the
+    /// next calendar date.
+    pub fn moment(&self, day: NaiveDate, isha: NaiveDateTime, rollover_hour: u32) -> NaiveDateTime {
+        match self {
+            Self::AfterIsha(minutes) => isha + Duration::minutes(*minutes),
+            Self::At(time) if time.hour() < rollover_hour => {
+                day.and_time(*time) + Duration::days(1)
+            }
+            Self::At(time) => day.and_time(*time),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{date} {time}"), DATETIME_FMT).unwrap()
+    }
+
+    #[test]
+    fn rollover_hour_keeps_small_hours_on_the_previous_day() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        assert_eq!(logical_date(at("2026-10-16", "00:30:00"), 0), day);
+        assert_eq!(logical_date(at("2026-10-17", "02:59:59"), 3), day);
+        assert_eq!(logical_date(at("2026-10-17", "03:00:00"), 3), day.succ_opt().unwrap());
+    }
+
+    #[test]
+    fn today_values_agree_for_a_config() {
+        // (offset, rollover, UTC instant, expected logical today)
+        let cases = [
+            (0, 0, "2026-10-16 23:59:59", "2026-10-16"),
+            (0, 0, "2026-10-17 00:00:00", "2026-10-17"),
+            // 23:59 UTC is already 02:59 the next day at +3, still before a 03:00 rollover
+            (180, 3, "2026-10-16 23:59:59", "2026-10-16"),
+            (180, 3, "2026-10-17 00:00:00", "2026-10-17"),
+            // 08:59 UTC is 03:59 at -5, just before a 04:00 rollover
+            (-300, 4, "2026-10-17 08:59:59", "2026-10-16"),
+            (-300, 4, "2026-10-17 09:00:00", "2026-10-17"),
+            // +14 puts 08:59 UTC at 22:59, before a rollover at 23:00
+            (840, 23, "2026-10-16 08:59:59", "2026-10-15"),
+            (840, 23, "2026-10-16 09:00:00", "2026-10-16"),
+        ];
+        for (offset, rollover, utc, expected) in cases {
+            let mut config = AppConfig::default();
+            config.salah.timezone_offset = offset;
+            config.day.rollover_hour = rollover;
+            let instant = NaiveDateTime::parse_from_str(utc, DATETIME_FMT).unwrap().and_utc();
+            let date = today_date_at(&config, instant);
+            assert_eq!(date_str(date), expected, "{utc} at {offset:+} min, rollover {rollover}");
+            assert_eq!(today_str_at(&config, instant), expected);
+            assert_eq!(date, logical_date(local_at(&config, instant), rollover));
+        }
+    }
+}
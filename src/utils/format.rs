@@ -1,5 +1,7 @@
 use chrono::NaiveTime;
 
+use crate::config::TimeFormat;
+
 /// Format a duration in seconds to "Xh Ym" or "Ym" string
 pub fn format_duration_secs(secs: i64) -> String {
     if secs <= 0 {
@@ -14,11 +16,57 @@ pub fn format_duration_secs(secs: i64) -> String {
     }
 }
 
+/// Format an elapsed duration in seconds as "just now", "5m ago", "2h ago" or "3d ago"
+pub fn format_ago(secs: i64) -> String {
+    if secs < 60 {
+        "just now".to_string()
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86_400 {
+        format!("{}h ago", secs / 3600)
+    } else {
+        format!("{}d ago", secs / 86_400)
+    }
+}
+
+/// Format a day count loosely: "3 days", "6 weeks", "4 months"
+pub fn format_days_approx(days: u32) -> String {
+    let (n, unit) = if days < 14 {
+        (days, "day")
+    } else if days < 60 {
+        ((days + 3) / 7, "week")
+    } else {
+        ((days + 15) / 30, "month")
+    };
+    format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" })
+}
+
 /// Format a NaiveTime to "HH:MM"
 pub fn format_time(t: NaiveTime) -> String {
     t.format("%H:%M").to_string()
 }
 
+/// Format a NaiveTime to "H:MM AM/PM" — 12:00 PM is noon, 12:00 AM midnight
+pub fn format_time_12h(t: NaiveTime) -> String {
+    t.format("%-I:%M %p").to_string()
+}
+
+/// Format a NaiveTime on the clock picked by `ui.time_format`
+pub fn format_time_as(t: NaiveTime, format: TimeFormat) -> String {
+    match format {
+        TimeFormat::H24 => format_time(t),
+        TimeFormat::H12 => format_time_12h(t),
+    }
+}
+
+/// Column width that fits any time on the given clock, plus a space
+pub fn time_column_width(format: TimeFormat) -> usize {
+    match format {
+        TimeFormat::H24 => 7,
+        TimeFormat::H12 => 10,
+    }
+}
+
 /// Format pages as a decimal string, trimming trailing zeros
 pub fn format_pages(pages: f64) -> String {
     if pages == pages.floor() {
@@ -28,6 +76,22 @@ pub fn format_pages(pages: f64) -> String {
     }
 }
 
+/// Round to `decimals` places (at most 8) via its decimal text, so the result
+/// prints back as exactly that text rather than picking up float noise.
+pub fn round_decimals(value: f64, decimals: usize) -> f64 {
+    format!("{:.*}", decimals.min(8), value).parse().unwrap_or(value)
+}
+
+/// Format a coordinate to `decimals` places (at most 8), trimming trailing zeros
+pub fn format_coord(value: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals.min(8), value);
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
 /// Create a simple ASCII progress bar
 pub fn progress_bar(filled: u32, total: u32, width: usize) -> String {
     if total == 0 {
@@ -38,3 +102,29 @@ pub fn progress_bar(filled: u32, total: u32, width: usize) -> String {
     let empty_count = width.saturating_sub(filled_count);
     format!("{}{}", "█".repeat(filled_count), "░".repeat(empty_count))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn both_clocks_around_noon_and_midnight() {
+        let cases = [
+            (at(0, 0), "00:00", "12:00 AM"),
+            (at(0, 5), "00:05", "12:05 AM"),
+            (at(11, 59), "11:59", "11:59 AM"),
+            (at(12, 0), "12:00", "12:00 PM"),
+            (at(12, 30), "12:30", "12:30 PM"),
+            (at(23, 59), "23:59", "11:59 PM"),
+        ];
+        for (time, h24, h12) in cases {
+            assert_eq!(format_time_as(time, TimeFormat::H24), h24);
+            assert_eq!(format_time_as(time, TimeFormat::H12), h12);
+            assert!(h12.len() < time_column_width(TimeFormat::H12));
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::Result;
-use chrono::{Datelike, Duration, NaiveDate};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
 use hijri_date::HijriDate;
 
+use crate::models::{Eid, SunnahFast};
+
 /// Islamic month names in English (index 0 = Muharram = month 1)
 const HIJRI_MONTH_NAMES: &[&str] = &[
     "Muharram",
@@ -31,7 +33,6 @@ pub struct HijriInfo {
     pub month: usize,
     pub year: usize,
     pub month_name: String,
-    pub day_name: String,
 }
 
 impl HijriInfo {
@@ -54,27 +55,182 @@ pub fn to_hijri(date: NaiveDate) -> Result<HijriInfo> {
         month,
         year: hd.year(),
         month_name: hijri_month_name(month).to_string(),
-        day_name: hd.day_name_en(),
     })
 }
 
-/// Returns the Hijri date string for today, with an optional day offset.
+/// The Eid falling on `date` (after the moon-sighting offset) and its Hijri year.
+pub fn eid_on(date: NaiveDate, offset_days: i32) -> Option<(Eid, usize)> {
+    let hijri = to_hijri(date + Duration::days(offset_days as i64)).ok()?;
+    Eid::on_hijri(hijri.month, hijri.day).map(|eid| (eid, hijri.year))
+}
+
+/// Whether `date` falls in Ramadan after the moon-sighting offset.
+pub fn is_ramadan(date: NaiveDate, offset_days: i32) -> bool {
+    to_hijri(date + Duration::days(offset_days as i64)).is_ok_and(|h| h.month == 9)
+}
+
+/// The sunnah fast recommended on `date`, if any. Ramadan, the Eids and the
+/// days of tashreeq (11–13 Dhu al-Hijjah) never get one.
+pub fn sunnah_fast_on(date: NaiveDate, offset_days: i32) -> Option<SunnahFast> {
+    if let Ok(hijri) = to_hijri(date + Duration::days(offset_days as i64)) {
+        match (hijri.month, hijri.day) {
+            (9, _) | (10, 1) => return None,
+            (12, day) if (10..=13).contains(&day) => return None,
+            (12, 9) => return Some(SunnahFast::Arafah),
+            (1, 10) => return Some(SunnahFast::Ashura),
+            (_, 13..=15) => return Some(SunnahFast::WhiteDay),
+            _ => {}
+        }
+    }
+    match date.weekday() {
+        Weekday::Mon => Some(SunnahFast::Monday),
+        Weekday::Thu => Some(SunnahFast::Thursday),
+        _ => None,
+    }
+}
+
+/// Returns the Hijri date string for `today`, with an optional day offset.
 /// `offset_days` lets users adjust for local moon sighting differences
 /// (e.g., -1 if your country is one day behind Saudi Arabia).
-pub fn today_hijri_string(offset_days: i32) -> String {
-    let today = chrono::Local::now().date_naive();
-    let adjusted = today + Duration::days(offset_days as i64);
-
-    match HijriDate::from_gr(
-        adjusted.year() as usize,
-        adjusted.month() as usize,
-        adjusted.day() as usize,
-    ) {
-        Ok(hd) => format!("{} {} {}", hd.day(), hijri_month_name(hd.month()), hd.year()),
-        Err(_) => {
-            // Fallback: use today without offset
-            let hd = HijriDate::today();
-            format!("{} {} {}", hd.day(), hijri_month_name(hd.month()), hd.year())
+pub fn today_hijri_string(today: NaiveDate, offset_days: i32) -> Result<String> {
+    to_hijri(today + Duration::days(offset_days as i64)).map(|h| h.formatted())
+}
+
+/// A day the Hijri calendar view marks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HijriEvent {
+    Eid(Eid),
+    /// 9 Dhu al-Hijjah
+    Arafah,
+    /// 10 Muharram
+    Ashura,
+    /// 13–15 of each month, traditionally fasted
+    WhiteDay,
+}
+
+impl HijriEvent {
+    pub fn on_hijri(month: usize, day: usize) -> Option<HijriEvent> {
+        if let Some(eid) = Eid::on_hijri(month, day) {
+            return Some(HijriEvent::Eid(eid));
+        }
+        match (month, day) {
+            (12, 9) => Some(HijriEvent::Arafah),
+            (1, 10) => Some(HijriEvent::Ashura),
+            // 13 Dhu al-Hijjah is a day of tashreeq, when fasting is not allowed
+            (12, 13) => None,
+            (_, 13..=15) => Some(HijriEvent::WhiteDay),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HijriEvent::Eid(eid) => eid.display_name(),
+            HijriEvent::Arafah => "Day of Arafah",
+            HijriEvent::Ashura => "Ashura",
+            HijriEvent::WhiteDay => "White day",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HijriDay {
+    pub day: usize,
+    /// The Gregorian day this Hijri day falls on, offset already applied
+    pub date: NaiveDate,
+    pub event: Option<HijriEvent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HijriMonth {
+    pub month: usize,
+    pub year: usize,
+    pub days: Vec<HijriDay>,
+}
+
+impl HijriMonth {
+    pub fn name(&self) -> &'static str {
+        hijri_month_name(self.month)
+    }
+
+    pub fn first_date(&self) -> Option<NaiveDate> {
+        self.days.first().map(|d| d.date)
+    }
+
+    pub fn last_date(&self) -> Option<NaiveDate> {
+        self.days.last().map(|d| d.date)
+    }
+}
+
+/// Every day of the Hijri month containing `date`, shifted by the same
+/// moon-sighting offset as the header, so day numbers always agree with it.
+pub fn hijri_month_days(date: NaiveDate, offset_days: i32) -> Result<HijriMonth> {
+    let offset = Duration::days(offset_days as i64);
+    let adjusted = date + offset;
+    let current = to_hijri(adjusted)?;
+    let start = adjusted - Duration::days(current.day as i64 - 1);
+
+    let mut days = Vec::with_capacity(30);
+    for day in start.iter_days().take(30) {
+        let hijri = to_hijri(day)?;
+        if hijri.month != current.month {
+            break;
+        }
+        days.push(HijriDay {
+            day: hijri.day,
+            date: day - offset,
+            event: HijriEvent::on_hijri(hijri.month, hijri.day),
+        });
+    }
+    Ok(HijriMonth { month: current.month, year: current.year, days })
+}
+
+/// Days from `date` until the next 1st of Hijri `month` (0 on the day itself),
+/// or None if the conversion fails within a year.
+pub fn days_until_month(date: NaiveDate, offset_days: i32, month: usize) -> Option<i64> {
+    let adjusted = date + Duration::days(offset_days as i64);
+    (0..=366).find(|&n| {
+        to_hijri(adjusted + Duration::days(n))
+            .map(|h| h.month == month && h.day == 1)
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The first day of a Hijri month on or after `from`.
+    fn first_of_month(from: NaiveDate) -> NaiveDate {
+        from.iter_days().find(|d| to_hijri(*d).unwrap().day == 1).unwrap()
+    }
+
+    #[test]
+    fn offset_moves_the_date_across_a_month_boundary() {
+        let first = first_of_month(NaiveDate::from_ymd_opt(2026, 10, 1).unwrap());
+        let today = to_hijri(first).unwrap();
+        let eve = to_hijri(first - Duration::days(1)).unwrap();
+        assert_eq!(eve.month % 12 + 1, today.month);
+        assert!((29..=30).contains(&eve.day));
+
+        assert_eq!(today_hijri_string(first, 0).unwrap(), today.formatted());
+        assert_eq!(today_hijri_string(first, -1).unwrap(), eve.formatted());
+        assert_eq!(today_hijri_string(first - Duration::days(1), 1).unwrap(), today.formatted());
+    }
+
+    #[test]
+    fn month_days_stop_at_the_boundary_and_keep_the_offset() {
+        let first = first_of_month(NaiveDate::from_ymd_opt(2026, 10, 1).unwrap());
+        for offset in [-1, 0, 1] {
+            // The Gregorian day shown as the 1st moves opposite to the offset
+            let shown_first = first - Duration::days(offset as i64);
+            let month = hijri_month_days(shown_first, offset).unwrap();
+            assert_eq!(month.first_date(), Some(shown_first));
+            assert_eq!(month.days[0].day, 1);
+            assert!((29..=30).contains(&month.days.len()));
+
+            let after = month.last_date().unwrap() + Duration::days(1);
+            assert_eq!(days_until_month(after, offset, month.month % 12 + 1), Some(0));
         }
     }
 }
@@ -1,2 +1,5 @@
+pub mod cities;
+pub mod date;
 pub mod format;
 pub mod hijri;
+pub mod quran;
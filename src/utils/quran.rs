@@ -0,0 +1,179 @@
+/// Pages in the Madani mushaf, which `SURAHS` start pages refer to.
+pub const MADANI_PAGES: u32 = 604;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Surah {
+    pub name: &'static str,
+    pub ayahs: u16,
+    /// First page in the Madani mushaf
+    pub start_page: u16,
+}
+
+/// All 114 surahs in order; `SURAHS[0]` is surah 1. Used to turn an ayah
+/// range into an approximate page count for the page-based stats.
+pub const SURAHS: [Surah; 114] = [
+    Surah { name: "Al-Fatihah", ayahs: 7, start_page: 1 },
+    Surah { name: "Al-Baqarah", ayahs: 286, start_page: 2 },
+    Surah { name: "Al-Imran", ayahs: 200, start_page: 50 },
+    Surah { name: "An-Nisa", ayahs: 176, start_page: 77 },
+    Surah { name: "Al-Ma'idah", ayahs: 120, start_page: 106 },
+    Surah { name: "Al-An'am", ayahs: 165, start_page: 128 },
+    Surah { name: "Al-A'raf", ayahs: 206, start_page: 151 },
+    Surah { name: "Al-Anfal", ayahs: 75, start_page: 177 },
+    Surah { name: "At-Tawbah", ayahs: 129, start_page: 187 },
+    Surah { name: "Yunus", ayahs: 109, start_page: 208 },
+    Surah { name: "Hud", ayahs: 123, start_page: 221 },
+    Surah { name: "Yusuf", ayahs: 111, start_page: 235 },
+    Surah { name: "Ar-Ra'd", ayahs: 43, start_page: 249 },
+    Surah { name: "Ibrahim", ayahs: 52, start_page: 255 },
+    Surah { name: "Al-Hijr", ayahs: 99, start_page: 262 },
+    Surah { name: "An-Nahl", ayahs: 128, start_page: 267 },
+    Surah { name: "Al-Isra", ayahs: 111, start_page: 282 },
+    Surah { name: "Al-Kahf", ayahs: 110, start_page: 293 },
+    Surah { name: "Maryam", ayahs: 98, start_page: 305 },
+    Surah { name: "Ta-Ha", ayahs: 135, start_page: 312 },
+    Surah { name: "Al-Anbiya", ayahs: 112, start_page: 322 },
+    Surah { name: "Al-Hajj", ayahs: 78, start_page: 332 },
+    Surah { name: "Al-Mu'minun", ayahs: 118, start_page: 342 },
+    Surah { name: "An-Nur", ayahs: 64, start_page: 350 },
+    Surah { name: "Al-Furqan", ayahs: 77, start_page: 359 },
+    Surah { name: "Ash-Shu'ara", ayahs: 227, start_page: 367 },
+    Surah { name: "An-Naml", ayahs: 93, start_page: 377 },
+    Surah { name: "Al-Qasas", ayahs: 88, start_page: 385 },
+    Surah { name: "Al-Ankabut", ayahs: 69, start_page: 396 },
+    Surah { name: "Ar-Rum", ayahs: 60, start_page: 404 },
+    Surah { name: "Luqman", ayahs: 34, start_page: 411 },
+    Surah { name: "As-Sajdah", ayahs: 30, start_page: 415 },
+    Surah { name: "Al-Ahzab", ayahs: 73, start_page: 418 },
+    Surah { name: "Saba", ayahs: 54, start_page: 428 },
+    Surah { name: "Fatir", ayahs: 45, start_page: 434 },
+    Surah { name: "Ya-Sin", ayahs: 83, start_page: 440 },
+    Surah { name: "As-Saffat", ayahs: 182, start_page: 446 },
+    Surah { name: "Sad", ayahs: 88, start_page: 453 },
+    Surah { name: "Az-Zumar", ayahs: 75, start_page: 458 },
+    Surah { name: "Ghafir", ayahs: 85, start_page: 467 },
+    Surah { name: "Fussilat", ayahs: 54, start_page: 477 },
+    Surah { name: "Ash-Shura", ayahs: 53, start_page: 483 },
+    Surah { name: "Az-Zukhruf", ayahs: 89, start_page: 489 },
+    Surah { name: "Ad-Dukhan", ayahs: 59, start_page: 496 },
+    Surah { name: "Al-Jathiyah", ayahs: 37, start_page: 499 },
+    Surah { name: "Al-Ahqaf", ayahs: 35, start_page: 502 },
+    Surah { name: "Muhammad", ayahs: 38, start_page: 507 },
+    Surah { name: "Al-Fath", ayahs: 29, start_page: 511 },
+    Surah { name: "Al-Hujurat", ayahs: 18, start_page: 515 },
+    Surah { name: "Qaf", ayahs: 45, start_page: 518 },
+    Surah { name: "Adh-Dhariyat", ayahs: 60, start_page: 520 },
+    Surah { name: "At-Tur", ayahs: 49, start_page: 523 },
+    Surah { name: "An-Najm", ayahs: 62, start_page: 526 },
+    Surah { name: "Al-Qamar", ayahs: 55, start_page: 528 },
+    Surah { name: "Ar-Rahman", ayahs: 78, start_page: 531 },
+    Surah { name: "Al-Waqi'ah", ayahs: 96, start_page: 534 },
+    Surah { name: "Al-Hadid", ayahs: 29, start_page: 537 },
+    Surah { name: "Al-Mujadilah", ayahs: 22, start_page: 542 },
+    Surah { name: "Al-Hashr", ayahs: 24, start_page: 545 },
+    Surah { name: "Al-Mumtahanah", ayahs: 13, start_page: 549 },
+    Surah { name: "As-Saff", ayahs: 14, start_page: 551 },
+    Surah { name: "Al-Jumu'ah", ayahs: 11, start_page: 553 },
+    Surah { name: "Al-Munafiqun", ayahs: 11, start_page: 554 },
+    Surah { name: "At-Taghabun", ayahs: 18, start_page: 556 },
+    Surah { name: "At-Talaq", ayahs: 12, start_page: 558 },
+    Surah { name: "At-Tahrim", ayahs: 12, start_page: 560 },
+    Surah { name: "Al-Mulk", ayahs: 30, start_page: 562 },
+    Surah { name: "Al-Qalam", ayahs: 52, start_page: 564 },
+    Surah { name: "Al-Haqqah", ayahs: 52, start_page: 566 },
+    Surah { name: "Al-Ma'arij", ayahs: 44, start_page: 568 },
+    Surah { name: "Nuh", ayahs: 28, start_page: 570 },
+    Surah { name: "Al-Jinn", ayahs: 28, start_page: 572 },
+    Surah { name: "Al-Muzzammil", ayahs: 20, start_page: 574 },
+    Surah { name: "Al-Muddaththir", ayahs: 56, start_page: 575 },
+    Surah { name: "Al-Qiyamah", ayahs: 40, start_page: 577 },
+    Surah { name: "Al-Insan", ayahs: 31, start_page: 578 },
+    Surah { name: "Al-Mursalat", ayahs: 50, start_page: 580 },
+    Surah { name: "An-Naba", ayahs: 40, start_page: 582 },
+    Surah { name: "An-Nazi'at", ayahs: 46, start_page: 583 },
+    Surah { name: "Abasa", ayahs: 42, start_page: 585 },
+    Surah { name: "At-Takwir", ayahs: 29, start_page: 586 },
+    Surah { name: "Al-Infitar", ayahs: 19, start_page: 587 },
+    Surah { name: "Al-Mutaffifin", ayahs: 36, start_page: 587 },
+    Surah { name: "Al-Inshiqaq", ayahs: 25, start_page: 589 },
+    Surah { name: "Al-Buruj", ayahs: 22, start_page: 590 },
+    Surah { name: "At-Tariq", ayahs: 17, start_page: 591 },
+    Surah { name: "Al-A'la", ayahs: 19, start_page: 591 },
+    Surah { name: "Al-Ghashiyah", ayahs: 26, start_page: 592 },
+    Surah { name: "Al-Fajr", ayahs: 30, start_page: 593 },
+    Surah { name: "Al-Balad", ayahs: 20, start_page: 594 },
+    Surah { name: "Ash-Shams", ayahs: 15, start_page: 595 },
+    Surah { name: "Al-Layl", ayahs: 21, start_page: 595 },
+    Surah { name: "Ad-Duha", ayahs: 11, start_page: 596 },
+    Surah { name: "Ash-Sharh", ayahs: 8, start_page: 596 },
+    Surah { name: "At-Tin", ayahs: 8, start_page: 597 },
+    Surah { name: "Al-Alaq", ayahs: 19, start_page: 597 },
+    Surah { name: "Al-Qadr", ayahs: 5, start_page: 598 },
+    Surah { name: "Al-Bayyinah", ayahs: 8, start_page: 598 },
+    Surah { name: "Az-Zalzalah", ayahs: 8, start_page: 599 },
+    Surah { name: "Al-Adiyat", ayahs: 11, start_page: 599 },
+    Surah { name: "Al-Qari'ah", ayahs: 11, start_page: 600 },
+    Surah { name: "At-Takathur", ayahs: 8, start_page: 600 },
+    Surah { name: "Al-Asr", ayahs: 3, start_page: 601 },
+    Surah { name: "Al-Humazah", ayahs: 9, start_page: 601 },
+    Surah { name: "Al-Fil", ayahs: 5, start_page: 601 },
+    Surah { name: "Quraysh", ayahs: 4, start_page: 602 },
+    Surah { name: "Al-Ma'un", ayahs: 7, start_page: 602 },
+    Surah { name: "Al-Kawthar", ayahs: 3, start_page: 602 },
+    Surah { name: "Al-Kafirun", ayahs: 6, start_page: 603 },
+    Surah { name: "An-Nasr", ayahs: 3, start_page: 603 },
+    Surah { name: "Al-Masad", ayahs: 5, start_page: 603 },
+    Surah { name: "Al-Ikhlas", ayahs: 4, start_page: 604 },
+    Surah { name: "Al-Falaq", ayahs: 5, start_page: 604 },
+    Surah { name: "An-Nas", ayahs: 6, start_page: 604 },
+];
+
+/// Find a surah by number ("18") or name ("al-Kahf", "Kahf", "al kahf").
+/// Returns its number, 1–114.
+pub fn find_surah(query: &str) -> Option<usize> {
+    let query = query.trim();
+    if let Ok(number) = query.parse::<usize>() {
+        return (1..=SURAHS.len()).contains(&number).then_some(number);
+    }
+    let wanted = name_key(&query.replace(' ', "-"));
+    let wanted_full = letters(query);
+    SURAHS
+        .iter()
+        .position(|s| name_key(s.name) == wanted || letters(s.name) == wanted_full)
+        .map(|i| i + 1)
+}
+
+/// Lowercase letters only, with a leading "al-"/"an-"/… article dropped.
+fn name_key(name: &str) -> String {
+    let name = name.to_lowercase();
+    let core = match name.split_once('-') {
+        Some((article, rest)) if article.starts_with('a') && article.len() <= 3 => rest,
+        _ => name.as_str(),
+    };
+    letters(core)
+}
+
+fn letters(s: &str) -> String {
+    s.chars().filter(|c| c.is_ascii_alphabetic()).flat_map(char::to_lowercase).collect()
+}
+
+pub fn surah(number: usize) -> &'static Surah {
+    &SURAHS[number - 1]
+}
+
+/// Approximate pages covered by ayahs `start..=end` of surah `number`,
+/// scaled to a mushaf of `mushaf_pages` pages.
+pub fn approx_pages(number: usize, start: u16, end: u16, mushaf_pages: u32) -> f64 {
+    let s = surah(number);
+    // Surahs that begin on the same page share it evenly
+    let sharing = SURAHS.iter().filter(|o| o.start_page == s.start_page).count() as f64;
+    let next_page = SURAHS
+        .iter()
+        .map(|o| o.start_page)
+        .find(|p| *p > s.start_page)
+        .unwrap_or(MADANI_PAGES as u16 + 1);
+    let span = (next_page - s.start_page) as f64 / sharing;
+
+    let share = (end.saturating_sub(start) + 1) as f64 / s.ayahs as f64;
+    span * share * mushaf_pages as f64 / MADANI_PAGES as f64
+}